@@ -0,0 +1,43 @@
+//! Timing of the world generation passes at each map size, with a fixed seed
+//!
+//! The passes are plain functions over `WorldConfig` and `BiomeTable`, so no Bevy app runs here.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use worldkeeper::resources::{BiomeTable, MapSize, WorldConfig};
+use worldkeeper::systems::world_gen::{generate_biomes, generate_elevation, generate_terrain, WorldMap};
+
+const SEED: u64 = 0x5eed;
+
+fn config_for(size: MapSize) -> WorldConfig {
+    let mut config = WorldConfig {
+        seed: SEED,
+        ..Default::default()
+    };
+    config.set_map_size(size);
+    config
+}
+
+fn world_gen_benchmarks(c: &mut Criterion) {
+    let biome_table = BiomeTable::default();
+    let mut group = c.benchmark_group("world_gen");
+
+    for size in [MapSize::Small, MapSize::Medium, MapSize::Large] {
+        let config = config_for(size);
+        let elevation = generate_elevation(&config);
+
+        group.bench_with_input(BenchmarkId::new("terrain", size.label()), &config, |b, config| {
+            b.iter(|| generate_terrain(black_box(config), black_box(&elevation)))
+        });
+        group.bench_with_input(BenchmarkId::new("biomes", size.label()), &config, |b, config| {
+            b.iter(|| generate_biomes(black_box(config), black_box(&elevation), &biome_table))
+        });
+        group.bench_with_input(BenchmarkId::new("all", size.label()), &config, |b, config| {
+            b.iter(|| WorldMap::generate(black_box(config), &biome_table))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, world_gen_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use crate::resources::MusicTrack;
+
+/// Marker for the entity playing the current background music track
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BackgroundMusic {
+    pub track: MusicTrack,
+}
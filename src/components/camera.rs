@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+
+/// Marker for the camera that renders the game world
+///
+/// Despawned only by the game cleanup, so menu screens never remove it by accident.
+#[derive(Component)]
+pub struct GameCamera;
+
+/// Marker for a camera spawned by a menu screen to render its UI
+///
+/// Each screen also tags its camera with its own screen marker and only despawns that one.
+#[derive(Component)]
+pub struct UiCamera;
+
+/// Zoom state for the gameplay camera
+#[derive(Component, Debug, Clone)]
+pub struct CameraZoom {
+    /// Projection scale the camera is easing toward
+    pub target_scale: f32,
+    pub min_scale: f32,
+    pub max_scale: f32,
+    /// How quickly the projection scale approaches the target, per second
+    pub smoothing: f32,
+}
+
+impl CameraZoom {
+    /// Scale multiplier applied per mouse wheel line
+    pub const ZOOM_FACTOR_PER_LINE: f32 = 1.1;
+    /// Pixel-based scroll deltas (touchpads) are converted to lines with this ratio
+    pub const PIXELS_PER_LINE: f32 = 100.0;
+
+    /// Multiply the target scale, keeping it within the configured bounds
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.target_scale = (self.target_scale * factor).clamp(self.min_scale, self.max_scale);
+    }
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self {
+            target_scale: 1.0,
+            min_scale: 0.25,
+            max_scale: 4.0,
+            smoothing: 12.0,
+        }
+    }
+}
+
+/// Keyboard and gamepad panning state for the gameplay camera
+#[derive(Component, Debug, Clone)]
+pub struct CameraController {
+    /// Current panning velocity in world units per second
+    pub velocity: Vec2,
+    /// How quickly the velocity approaches the input direction, in units per second squared
+    pub acceleration: f32,
+    /// Top panning speed in world units per second
+    pub max_speed: f32,
+    /// How quickly the camera coasts to a stop once input is released, per second
+    pub damping: f32,
+    /// Distance from the window edge, in logical pixels, within which the cursor pans the camera
+    pub edge_scroll_margin: f32,
+    /// World position the camera is easing toward after a focus command, if any
+    pub focus_target: Option<Vec2>,
+    /// How quickly the camera approaches the focus target, per second
+    pub focus_smoothing: f32,
+    /// Projection scale the reset view hotkey zooms back to
+    pub default_scale: f32,
+    /// Rotation the camera is held at; the view never turns, so any other rotation eases back
+    pub default_rotation: Quat,
+    /// Whether releasing a drag-pan leaves the camera gliding, coasting to a stop like a key pan
+    pub inertia: bool,
+    /// Recent speed of the drag-pan in world units per second, carried into the glide on release
+    pub drag_velocity: Vec2,
+    /// Whether a drag-pan is in progress; one only starts when the map, not the UI, is grabbed
+    pub dragging: bool,
+}
+
+impl CameraController {
+    /// Speeds below this snap to zero so the camera comes fully to rest
+    pub const STOP_SPEED: f32 = 1.0;
+    /// Speed multiplier while Shift is held, for crossing large maps quickly
+    pub const FAST_MULTIPLIER: f32 = 3.0;
+    /// Speed multiplier while Ctrl is held, for precise positioning
+    pub const SLOW_MULTIPLIER: f32 = 0.3;
+    /// Focus movement ends once the camera is this close to the target, in world units
+    pub const FOCUS_ARRIVE_DISTANCE: f32 = 0.5;
+    /// Rotation easing ends once the camera is within this angle of its default, in radians
+    pub const ROTATION_ARRIVE_ANGLE: f32 = 0.005;
+    /// How quickly the drag velocity follows the cursor, per second; smooths out uneven mouse
+    /// motion so a release carries the recent drag speed rather than the last frame's
+    pub const DRAG_VELOCITY_SMOOTHING: f32 = 20.0;
+
+    /// Speed multiplier for the held modifier keys
+    pub fn speed_multiplier(keyboard_input: &ButtonInput<KeyCode>) -> f32 {
+        let mut multiplier = 1.0;
+        if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            multiplier *= Self::FAST_MULTIPLIER;
+        }
+        if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+            multiplier *= Self::SLOW_MULTIPLIER;
+        }
+        multiplier
+    }
+
+    /// Velocity the camera accelerates toward for an input direction of at most unit length
+    pub fn target_velocity(&self, direction: Vec2, multiplier: f32) -> Vec2 {
+        direction * self.max_speed * multiplier
+    }
+
+    /// Start easing the camera toward a world position, stopping any coasting pan
+    pub fn focus_camera_on(&mut self, target: Vec2) {
+        self.focus_target = Some(target);
+        self.velocity = Vec2::ZERO;
+    }
+
+    /// Camera position one frame further along the ease toward `target`
+    ///
+    /// Frame-rate independent, and snaps onto the target once it is close enough.
+    pub fn focus_step(&self, position: Vec2, target: Vec2, delta_seconds: f32) -> Vec2 {
+        let blend = 1.0 - (-self.focus_smoothing * delta_seconds).exp();
+        let next = position.lerp(target, blend);
+        if next.distance(target) < Self::FOCUS_ARRIVE_DISTANCE {
+            target
+        } else {
+            next
+        }
+    }
+
+    /// Blend one frame of drag-panning, in world units, into the drag velocity
+    pub fn track_drag(&mut self, world_delta: Vec2, delta_seconds: f32) {
+        if delta_seconds <= 0.0 {
+            return;
+        }
+        let blend = 1.0 - (-Self::DRAG_VELOCITY_SMOOTHING * delta_seconds).exp();
+        self.drag_velocity = self.drag_velocity.lerp(world_delta / delta_seconds, blend);
+    }
+
+    /// Grab the map to start a drag-pan, stopping any glide
+    pub fn start_drag(&mut self) {
+        self.dragging = true;
+        self.velocity = Vec2::ZERO;
+    }
+
+    /// End a drag-pan, gliding on at the drag velocity when inertia is on
+    ///
+    /// The glide is capped at the fast panning speed, so a flick can't fling the camera across
+    /// the map.
+    pub fn release_drag(&mut self) {
+        if self.inertia {
+            self.velocity = self
+                .drag_velocity
+                .clamp_length_max(self.max_speed * Self::FAST_MULTIPLIER);
+        }
+        self.drag_velocity = Vec2::ZERO;
+        self.dragging = false;
+    }
+
+    /// Start easing the camera back to its default zoom
+    ///
+    /// The rotation needs no reset of its own, since it always eases back to the default.
+    pub fn reset_view(&self, zoom: &mut CameraZoom) {
+        zoom.target_scale = self.default_scale.clamp(zoom.min_scale, zoom.max_scale);
+    }
+
+    /// Camera rotation one frame further along the ease back to `default_rotation`
+    ///
+    /// Eases at the same rate as a focus command, and snaps once it is close enough.
+    pub fn rotation_step(&self, rotation: Quat, delta_seconds: f32) -> Quat {
+        let blend = 1.0 - (-self.focus_smoothing * delta_seconds).exp();
+        let next = rotation.slerp(self.default_rotation, blend);
+        if next.angle_between(self.default_rotation) < Self::ROTATION_ARRIVE_ANGLE {
+            self.default_rotation
+        } else {
+            next
+        }
+    }
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            velocity: Vec2::ZERO,
+            acceleration: 1200.0,
+            max_speed: 300.0,
+            damping: 8.0,
+            edge_scroll_margin: 24.0,
+            focus_target: None,
+            focus_smoothing: 5.0,
+            default_scale: 1.0,
+            default_rotation: Quat::IDENTITY,
+            inertia: true,
+            drag_velocity: Vec2::ZERO,
+            dragging: false,
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use crate::components::ui::{MenuAction, MenuButton, MenuTheme, ThemeColor};
+use crate::events::ButtonActivated;
+use crate::i18n::Locale;
+use crate::systems::menu::create_sized_button;
+
+/// Modal asking the player to confirm a menu action, or to pick one of several
+///
+/// Each choice is an ordinary `MenuButton`, so its action runs through the same handler as
+/// any other menu button.
+#[derive(Component)]
+pub struct ConfirmDialog;
+
+/// Marker for UI that takes all input while it is open, such as a `ConfirmDialog`
+///
+/// Gameplay input systems and screen navigation don't run while one exists, see
+/// `no_modal_open`.
+#[derive(Component)]
+pub struct Modal;
+
+/// Marker for the buttons of a `ConfirmDialog`
+///
+/// While a dialog is open, keyboard navigation only moves between these buttons.
+#[derive(Component)]
+pub struct ConfirmDialogButton;
+
+/// Spawn a modal with a message and Yes/No buttons on top of the current screen
+pub fn spawn_confirm_dialog(
+    commands: &mut Commands,
+    theme: &MenuTheme,
+    locale: &Locale,
+    message: &str,
+    on_confirm: MenuAction,
+) {
+    spawn_choice_dialog(
+        commands,
+        theme,
+        message,
+        vec![(locale.t("dialog.yes"), on_confirm)],
+        &locale.t("dialog.no"),
+    );
+}
+
+/// Spawn a modal with a message, a button for each choice, and a button that just closes it
+pub fn spawn_choice_dialog(
+    commands: &mut Commands,
+    theme: &MenuTheme,
+    message: &str,
+    choices: Vec<(String, MenuAction)>,
+    dismiss_label: &str,
+) {
+    spawn_dialog(commands, theme, (), |panel| {
+        panel.spawn((
+            TextBundle::from_section(
+                message,
+                TextStyle {
+                    font_size: theme.button_font_size,
+                    color: theme.title_color,
+                    ..default()
+                },
+            ),
+            ThemeColor::Title,
+        ));
+
+        panel
+            .spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|row| {
+                for (label, action) in choices {
+                    create_sized_button(
+                        row,
+                        theme,
+                        &label,
+                        Val::Px(140.0),
+                        Val::Px(50.0),
+                        (MenuButton::new(action), ConfirmDialogButton),
+                    );
+                }
+                create_sized_button(
+                    row,
+                    theme,
+                    dismiss_label,
+                    Val::Px(140.0),
+                    Val::Px(50.0),
+                    ConfirmDialogButton,
+                );
+            });
+    });
+}
+
+/// Spawn an empty modal panel tagged with the given components, filled in by `content`
+///
+/// Buttons inside should be tagged `ConfirmDialogButton` so they close the dialog and are
+/// reachable with keyboard navigation.
+pub fn spawn_dialog(
+    commands: &mut Commands,
+    theme: &MenuTheme,
+    components: impl Bundle,
+    content: impl FnOnce(&mut ChildBuilder),
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.7).into(),
+                // Swallow clicks so the screen underneath can't be used while the dialog is open
+                focus_policy: FocusPolicy::Block,
+                // Above every screen, but below notifications and the debug overlay
+                z_index: ZIndex::Global(i32::MAX - 2),
+                ..default()
+            },
+            ConfirmDialog,
+            Modal,
+            components,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Val::Px(30.0),
+                            padding: UiRect::all(Val::Px(30.0)),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        background_color: theme.background_color.into(),
+                        border_color: theme.button_hovered.into(),
+                        ..default()
+                    },
+                    ThemeColor::Background,
+                ))
+                .with_children(content);
+        });
+}
+
+/// Run condition that holds while no `Modal` is open
+///
+/// Keeps clicks and keys meant for a dialog from also editing the world or leaving the screen
+/// behind it.
+pub fn no_modal_open(modal_query: Query<(), With<Modal>>) -> bool {
+    modal_query.is_empty()
+}
+
+/// Close the dialog when either of its buttons is activated
+///
+/// A dialog opened in response to the choice, such as one asking to confirm it, stays open.
+pub fn close_confirm_dialog_on_choice(
+    mut commands: Commands,
+    mut activated: EventReader<ButtonActivated>,
+    button_query: Query<(), With<ConfirmDialogButton>>,
+    dialog_query: Query<(Entity, Ref<ConfirmDialog>)>,
+) {
+    if activated.read().any(|event| button_query.contains(event.entity)) {
+        for (entity, dialog) in &dialog_query {
+            if !dialog.is_added() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Escape dismisses the dialog without choosing anything
+///
+/// Consumes the key so it doesn't also back out of the screen underneath.
+pub fn close_confirm_dialog_on_escape(
+    mut commands: Commands,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    dialog_query: Query<Entity, With<ConfirmDialog>>,
+) {
+    if dialog_query.is_empty() || !keyboard_input.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    keyboard_input.clear_just_pressed(KeyCode::Escape);
+
+    for entity in &dialog_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
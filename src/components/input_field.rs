@@ -0,0 +1,205 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use crate::components::ui::{MenuTheme, ThemeColor};
+
+/// Single-line text entry field
+///
+/// Click the field to focus it; only one field is focused at a time. Other systems read
+/// the entered text from `value`.
+#[derive(Component, Debug, Clone)]
+pub struct TextInput {
+    pub value: String,
+    /// Hint shown while the field is empty and unfocused
+    pub placeholder: String,
+    pub max_length: usize,
+    pub focused: bool,
+    /// Send `TextInputSubmitted` when Enter is pressed in this field
+    pub submit_on_enter: bool,
+}
+
+impl TextInput {
+    pub fn new(placeholder: impl Into<String>, max_length: usize) -> Self {
+        Self {
+            value: String::new(),
+            placeholder: placeholder.into(),
+            max_length,
+            focused: false,
+            submit_on_enter: false,
+        }
+    }
+
+    /// Send a `TextInputSubmitted` event when Enter is pressed
+    pub fn with_submit(mut self) -> Self {
+        self.submit_on_enter = true;
+        self
+    }
+
+    /// Start with the given text already entered
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = value.into();
+        self
+    }
+}
+
+/// Marker for the text node that displays a `TextInput`'s contents
+#[derive(Component)]
+pub struct TextInputText;
+
+/// Sent when Enter is pressed in a focused `TextInput` configured to submit
+#[derive(Event, Debug, Clone)]
+pub struct TextInputSubmitted {
+    pub entity: Entity,
+    pub value: String,
+}
+
+/// Helper function to spawn a text input field with the given extra components
+pub fn spawn_text_input(
+    parent: &mut ChildBuilder,
+    theme: &MenuTheme,
+    text_input: TextInput,
+    width: Val,
+    components: impl Bundle,
+) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width,
+                    min_height: Val::Px(50.0),
+                    align_items: AlignItems::Center,
+                    padding: UiRect::horizontal(Val::Px(10.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: theme.button_normal.into(),
+                border_color: theme.button_hovered.into(),
+                ..default()
+            },
+            ThemeColor::Widget,
+            Interaction::default(),
+            text_input,
+            components,
+        ))
+        .with_children(|field| {
+            field.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: theme.button_font_size,
+                        color: theme.button_text,
+                        ..default()
+                    },
+                ),
+                TextInputText,
+                ThemeColor::Text,
+            ));
+        });
+}
+
+/// Focus text inputs on click and type into the focused one
+///
+/// Escape is never consumed so screens can still use it to go back, and Enter is only
+/// handled by fields configured to submit.
+pub fn text_input_system(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut input_query: Query<(Entity, &Interaction, &mut TextInput)>,
+    mut submitted: EventWriter<TextInputSubmitted>,
+) {
+    // Clicking a field focuses it, clicking anywhere else clears focus
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        let clicked = input_query
+            .iter()
+            .find(|(_, interaction, _)| **interaction == Interaction::Pressed)
+            .map(|(entity, _, _)| entity);
+
+        for (entity, _, mut text_input) in &mut input_query {
+            let focused = Some(entity) == clicked;
+            if text_input.focused != focused {
+                text_input.focused = focused;
+            }
+        }
+    }
+
+    let Some((entity, _, mut text_input)) = input_query
+        .iter_mut()
+        .find(|(_, _, text_input)| text_input.focused)
+    else {
+        keyboard_events.clear();
+        return;
+    };
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Backspace => {
+                text_input.value.pop();
+            }
+            Key::Enter if text_input.submit_on_enter => {
+                submitted.send(TextInputSubmitted {
+                    entity,
+                    value: text_input.value.clone(),
+                });
+            }
+            Key::Space => {
+                push_character(&mut text_input, ' ');
+            }
+            Key::Character(characters) => {
+                for character in characters.chars() {
+                    push_character(&mut text_input, character);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Render text input contents with a blinking caret and focus highlight
+pub fn update_text_input_display(
+    time: Res<Time>,
+    theme: Res<MenuTheme>,
+    mut input_query: Query<(&TextInput, &Children, &mut BorderColor)>,
+    mut text_query: Query<&mut Text, With<TextInputText>>,
+) {
+    let caret_visible = ((time.elapsed_seconds() * 2.0) as u32).is_multiple_of(2);
+
+    for (text_input, children, mut border_color) in &mut input_query {
+        let display = if text_input.focused {
+            let caret = if caret_visible { "|" } else { " " };
+            format!("{}{}", text_input.value, caret)
+        } else if text_input.value.is_empty() {
+            text_input.placeholder.clone()
+        } else {
+            text_input.value.clone()
+        };
+
+        let border = if text_input.focused {
+            theme.title_color
+        } else {
+            theme.button_hovered
+        };
+        if border_color.0 != border {
+            border_color.0 = border;
+        }
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                if text.sections[0].value != display {
+                    text.sections[0].value = display.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Append a printable character if the field has room for it
+fn push_character(text_input: &mut TextInput, character: char) {
+    if !character.is_control() && text_input.value.chars().count() < text_input.max_length {
+        text_input.value.push(character);
+    }
+}
@@ -1,3 +1,19 @@
+pub mod audio;
+pub mod camera;
+pub mod confirm_dialog;
+pub mod input_field;
+pub mod placeable;
+pub mod slider;
+pub mod tile;
+pub mod tooltip;
 pub mod ui;
 
+pub use audio::*;
+pub use camera::*;
+pub use confirm_dialog::*;
+pub use input_field::*;
+pub use placeable::*;
+pub use slider::*;
+pub use tile::*;
+pub use tooltip::*;
 pub use ui::*;
\ No newline at end of file
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Kinds of entities the player can place on the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EntityKind {
+    Tree,
+    Animal,
+    Villager,
+}
+
+impl EntityKind {
+    /// Every placeable kind, in toolbar order
+    pub const ALL: [EntityKind; 3] = [EntityKind::Tree, EntityKind::Animal, EntityKind::Villager];
+
+    /// Display name shown on the toolbar
+    pub fn name(self) -> &'static str {
+        match self {
+            EntityKind::Tree => "Tree",
+            EntityKind::Animal => "Animal",
+            EntityKind::Villager => "Villager",
+        }
+    }
+
+    /// Color used to draw entities of this kind
+    pub fn color(self) -> Color {
+        match self {
+            EntityKind::Tree => Color::srgb(0.1, 0.35, 0.1),
+            EntityKind::Animal => Color::srgb(0.6, 0.4, 0.2),
+            EntityKind::Villager => Color::srgb(0.9, 0.75, 0.6),
+        }
+    }
+
+    /// Category that decides which terrain this kind can be placed on
+    pub fn category(self) -> EntityCategory {
+        match self {
+            EntityKind::Tree => EntityCategory::Plant,
+            EntityKind::Animal => EntityCategory::Wildlife,
+            EntityKind::Villager => EntityCategory::Settler,
+        }
+    }
+}
+
+/// Groups of entity kinds that share placement rules in `TerrainRules`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityCategory {
+    Plant,
+    Wildlife,
+    Settler,
+}
+
+/// Marker for entities the delete tool is allowed to remove
+///
+/// Only player-placed entities carry it, so tiles and other parts of the world can't be deleted.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Deletable;
+
+/// An entity placed on the map by the player
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Placed {
+    pub kind: EntityKind,
+    /// Grid coordinates of the tile it stands on
+    pub coord: UVec2,
+}
+
+/// Health of a placed entity, lost on hostile terrain and in harsh weather
+///
+/// The entity dies and is despawned when it reaches zero.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Health(pub f32);
+
+impl Health {
+    pub const MAX: f32 = 100.0;
+
+    /// Remaining health in the range 0.0..=1.0
+    pub fn fraction(self) -> f32 {
+        (self.0 / Self::MAX).clamp(0.0, 1.0)
+    }
+
+    pub fn is_dead(self) -> bool {
+        self.0 <= 0.0
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self(Self::MAX)
+    }
+}
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use crate::components::ui::{FocusedButton, MenuTheme, ThemeColor};
+use crate::systems::gamepad::gamepad_just_pressed;
+
+/// Horizontal slider for picking a number within a range
+///
+/// Drag along the track with the mouse, or focus it with the menu navigation keys and
+/// nudge it with left/right. Every change is reported with a `SliderChanged` event.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Slider {
+    pub min: f32,
+    pub max: f32,
+    pub value: f32,
+    /// Increment used when nudging with the keyboard; dragged values snap to it too
+    pub step: f32,
+}
+
+impl Slider {
+    pub fn new(min: f32, max: f32, value: f32, step: f32) -> Self {
+        Self {
+            min,
+            max,
+            value: value.clamp(min, max),
+            step,
+        }
+    }
+
+    /// Position of the value along the track, in the range 0.0..=1.0
+    pub fn fraction(&self) -> f32 {
+        if self.max > self.min {
+            ((self.value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Set the value from a position along the track, snapped to the step
+    pub fn set_fraction(&mut self, fraction: f32) {
+        self.set_value(self.min + fraction.clamp(0.0, 1.0) * (self.max - self.min));
+    }
+
+    /// Move the value by a number of steps
+    pub fn nudge(&mut self, steps: f32) {
+        self.set_value(self.value + steps * self.step);
+    }
+
+    fn set_value(&mut self, value: f32) {
+        let snapped = if self.step > 0.0 {
+            self.min + ((value - self.min) / self.step).round() * self.step
+        } else {
+            value
+        };
+        self.value = snapped.clamp(self.min, self.max);
+    }
+}
+
+/// Marker for the child node that fills the slider track up to its value
+#[derive(Component)]
+pub struct SliderFill;
+
+/// Sent whenever the user changes a slider's value
+#[derive(Event, Debug, Clone)]
+pub struct SliderChanged {
+    pub entity: Entity,
+    pub value: f32,
+}
+
+/// Helper function to spawn a slider with the given extra components
+///
+/// The track is a `Button` so it takes part in menu focus navigation like any other control.
+pub fn spawn_slider(
+    parent: &mut ChildBuilder,
+    theme: &MenuTheme,
+    slider: Slider,
+    width: Val,
+    components: impl Bundle,
+) {
+    let fill_width = Val::Percent(slider.fraction() * 100.0);
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width,
+                    height: Val::Px(24.0),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                background_color: theme.button_normal.into(),
+                border_color: theme.button_hovered.into(),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            ThemeColor::Widget,
+            slider,
+            components,
+        ))
+        .with_children(|track| {
+            track.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: fill_width,
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    background_color: theme.title_color.into(),
+                    ..default()
+                },
+                SliderFill,
+                ThemeColor::Accent,
+            ));
+        });
+}
+
+/// Drag sliders with the mouse and nudge the focused one with left/right
+pub fn slider_interaction_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    focused: Res<FocusedButton>,
+    mut slider_query: Query<(Entity, &Interaction, &RelativeCursorPosition, &mut Slider)>,
+    mut changed: EventWriter<SliderChanged>,
+) {
+    let left_pressed = keyboard_input.just_pressed(KeyCode::ArrowLeft)
+        || gamepad_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::DPadLeft);
+    let right_pressed = keyboard_input.just_pressed(KeyCode::ArrowRight)
+        || gamepad_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::DPadRight);
+
+    for (entity, interaction, cursor, mut slider) in &mut slider_query {
+        // Work on a copy so sliders are only marked changed when their value moves
+        let mut updated = slider.clone();
+
+        // Interaction stays Pressed while the mouse button is held, so this tracks drags
+        if *interaction == Interaction::Pressed {
+            if let Some(position) = cursor.normalized {
+                updated.set_fraction(position.x);
+            }
+        }
+
+        if focused.0 == Some(entity) {
+            if left_pressed {
+                updated.nudge(-1.0);
+            }
+            if right_pressed {
+                updated.nudge(1.0);
+            }
+        }
+
+        if updated.value != slider.value {
+            slider.value = updated.value;
+            changed.send(SliderChanged {
+                entity,
+                value: slider.value,
+            });
+        }
+    }
+}
+
+/// Resize each slider's fill to match its value
+pub fn update_slider_display(
+    slider_query: Query<(&Slider, &Children), Changed<Slider>>,
+    mut fill_query: Query<&mut Style, With<SliderFill>>,
+) {
+    for (slider, children) in &slider_query {
+        for child in children.iter() {
+            if let Ok(mut style) = fill_query.get_mut(*child) {
+                style.width = Val::Percent(slider.fraction() * 100.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Terrain types a tile can have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Terrain {
+    Water,
+    /// Fresh water carved from the mountains down to the sea
+    River,
+    Sand,
+    Grass,
+    Mountain,
+}
+
+impl Terrain {
+    /// Every terrain type, in order of increasing elevation
+    pub const ALL: [Terrain; 5] = [
+        Terrain::Water,
+        Terrain::River,
+        Terrain::Sand,
+        Terrain::Grass,
+        Terrain::Mountain,
+    ];
+
+    /// Display name of the terrain
+    pub fn name(self) -> &'static str {
+        match self {
+            Terrain::Water => "Water",
+            Terrain::River => "River",
+            Terrain::Sand => "Sand",
+            Terrain::Grass => "Grass",
+            Terrain::Mountain => "Mountain",
+        }
+    }
+
+    /// Whether the terrain is open water or a river
+    pub fn is_water(self) -> bool {
+        matches!(self, Terrain::Water | Terrain::River)
+    }
+}
+
+/// Climate zones assigned to tiles from their elevation and moisture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Biome {
+    Tundra,
+    Forest,
+    Desert,
+    Grassland,
+    Swamp,
+}
+
+impl Biome {
+    /// Every biome type
+    pub const ALL: [Biome; 5] = [
+        Biome::Tundra,
+        Biome::Forest,
+        Biome::Desert,
+        Biome::Grassland,
+        Biome::Swamp,
+    ];
+
+    /// Display name of the biome
+    pub fn name(self) -> &'static str {
+        match self {
+            Biome::Tundra => "Tundra",
+            Biome::Forest => "Forest",
+            Biome::Desert => "Desert",
+            Biome::Grassland => "Grassland",
+            Biome::Swamp => "Swamp",
+        }
+    }
+}
+
+/// A single tile of the generated world grid
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Tile {
+    /// Grid coordinates, with (0, 0) in the bottom-left corner of the world
+    pub coord: UVec2,
+    pub terrain: Terrain,
+    pub biome: Biome,
+}
+
+/// One mesh drawing every tile of a generation chunk, when tiles are batched
+///
+/// Each tile is a quad of four vertices, in the order the chunk's tiles were spawned.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileChunk {
+    /// Coordinates of the chunk's bottom-left tile
+    pub origin: UVec2,
+}
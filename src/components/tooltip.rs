@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+/// Text shown in a tooltip when the cursor rests on this UI element
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Tooltip(pub String);
+
+impl Tooltip {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self(text.into())
+    }
+}
+
+/// Marker for the floating tooltip box, spawned while something is hovered
+#[derive(Component)]
+pub struct TooltipBox;
@@ -1,4 +1,10 @@
+use std::path::PathBuf;
+
 use bevy::prelude::*;
+use crate::resources::{
+    InputAction, MapSize, Miracle, PlacementTool, Resolution, ResourceKind, SimulationSpeed,
+    Statistic, TerrainTool, ThemePreset,
+};
 
 /// Marker component for the main menu UI
 #[derive(Component)]
@@ -8,6 +14,17 @@ pub struct MainMenu;
 #[derive(Component)]
 pub struct MenuTitle;
 
+/// The pre-generated world panned behind the main menu while it sits idle
+#[derive(Component)]
+pub struct AttractView;
+
+/// Star drifting across the main menu background
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct MenuStar {
+    /// Drift speed in logical pixels per second
+    pub speed: f32,
+}
+
 /// Component for menu buttons with their associated actions
 #[derive(Component, Debug, Clone, PartialEq)]
 pub struct MenuButton {
@@ -17,10 +34,45 @@ pub struct MenuButton {
 /// Actions that can be triggered by menu buttons
 #[derive(Debug, Clone, PartialEq)]
 pub enum MenuAction {
+    /// Load the most recent save
+    Continue,
     NewWorldkeeper,
     StartNewGame,
+    LoadWorld,
+    /// Delete a save file, sent from the confirmation dialog on the saves screen
+    DeleteSave(PathBuf),
+    SaveGame,
+    /// Ask for a name, then save under it
+    SaveAs,
+    /// Save under a name, replacing the save already there, sent once overwriting is confirmed
+    SaveGameAs(String),
+    /// Copy the current world's seed to the clipboard
+    CopySeed,
+    /// Replace the world in play with one generated from this seed, sent once confirmed
+    RegenerateWorld(u64),
     Settings,
+    /// Restore every setting to its default, sent once the reset is confirmed
+    ResetSettings,
+    Controls,
+    Credits,
+    Back,
+    Resume,
+    /// Return to the main menu from the game, offering to save first if there are unsaved
+    /// changes
+    QuitToMenu,
+    /// Save, then return to the main menu once the save succeeded
+    SaveAndQuitToMenu,
+    /// Return to the main menu without asking, discarding any unsaved changes
+    ReturnToMenu,
+    /// Quit from the pause menu, offering to save first if there are unsaved changes
+    QuitToDesktop,
+    SaveAndQuit,
     Quit,
+    /// Ask the player to confirm before running the wrapped action
+    Confirm {
+        message: String,
+        action: Box<MenuAction>,
+    },
 }
 
 impl MenuButton {
@@ -29,21 +81,602 @@ impl MenuButton {
     }
 }
 
+impl MenuAction {
+    /// Key of the short description of what the action does, shown in the button's tooltip
+    pub fn description_key(&self) -> &'static str {
+        match self {
+            MenuAction::Continue => "tooltip.continue",
+            MenuAction::NewWorldkeeper => "tooltip.new_worldkeeper",
+            MenuAction::StartNewGame => "tooltip.start_new_game",
+            MenuAction::LoadWorld => "tooltip.load_world",
+            MenuAction::DeleteSave(_) => "tooltip.delete_save",
+            MenuAction::SaveGame => "tooltip.save_game",
+            MenuAction::SaveAs => "tooltip.save_as",
+            MenuAction::SaveGameAs(_) => "tooltip.save_game_as",
+            MenuAction::CopySeed => "tooltip.copy_seed",
+            MenuAction::RegenerateWorld(_) => "tooltip.regenerate_world",
+            MenuAction::Settings => "tooltip.settings",
+            MenuAction::ResetSettings => "tooltip.reset_settings",
+            MenuAction::Controls => "tooltip.controls",
+            MenuAction::Credits => "tooltip.credits",
+            MenuAction::Back => "tooltip.back",
+            MenuAction::Resume => "tooltip.resume",
+            MenuAction::QuitToMenu => "tooltip.quit_to_menu",
+            MenuAction::SaveAndQuitToMenu => "tooltip.save_and_quit_to_menu",
+            MenuAction::ReturnToMenu => "tooltip.return_to_menu",
+            MenuAction::QuitToDesktop => "tooltip.quit_to_desktop",
+            MenuAction::SaveAndQuit => "tooltip.save_and_quit",
+            MenuAction::Quit => "tooltip.quit",
+            MenuAction::Confirm { action, .. } => action.description_key(),
+        }
+    }
+
+    /// Wrap an action so it only runs once the player confirms the message
+    pub fn confirm(message: impl Into<String>, action: MenuAction) -> Self {
+        MenuAction::Confirm {
+            message: message.into(),
+            action: Box::new(action),
+        }
+    }
+}
+
+/// Scale a button eases toward, picked from its hover and press state
+///
+/// Only the scale of the button's `Transform` is animated. Layout owns the translation, so
+/// the button stays in place within its container.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ButtonScale {
+    pub target: f32,
+}
+
+impl ButtonScale {
+    pub const RESTING: f32 = 1.0;
+    pub const HOVERED: f32 = 1.05;
+    pub const PRESSED: f32 = 0.95;
+}
+
+impl Default for ButtonScale {
+    fn default() -> Self {
+        Self {
+            target: Self::RESTING,
+        }
+    }
+}
+
 /// Component for button text
 #[derive(Component)]
 pub struct ButtonText;
 
-/// UI style constants for consistent theming
-pub struct MenuTheme;
+/// Button that currently has keyboard focus, if any
+#[derive(Resource, Debug, Default)]
+pub struct FocusedButton(pub Option<Entity>);
+
+/// Marker component for the pause menu overlay
+#[derive(Component)]
+pub struct PauseMenu;
+
+/// Marker component for the game over screen UI
+#[derive(Component)]
+pub struct GameOverScreen;
+
+/// Marker component for the credits screen UI
+#[derive(Component)]
+pub struct CreditsScreen;
+
+/// Column of credits lines that scrolls up the credits screen
+#[derive(Component, Debug, Clone, Default)]
+pub struct CreditsRoll {
+    /// UI pixels scrolled since the screen opened
+    pub scrolled: f32,
+}
+
+/// Marker component for the New Worldkeeper screen UI
+#[derive(Component)]
+pub struct NewWorldkeeperScreen;
+
+/// Component for world setup buttons with their associated actions
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct WorldSetupButton {
+    pub action: WorldSetupAction,
+}
+
+/// Actions that can be triggered by world setup buttons
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorldSetupAction {
+    SelectMapSize(MapSize),
+    /// Copy the entered seed, or the current world's seed if none is entered
+    CopySeed,
+    /// Replace the entered seed with the one on the clipboard
+    PasteSeed,
+    ToggleSandbox,
+    CreateWorld,
+}
+
+impl WorldSetupButton {
+    pub fn new(action: WorldSetupAction) -> Self {
+        Self { action }
+    }
+}
+
+/// Marker for the seed `TextInput` on the New Worldkeeper screen
+#[derive(Component)]
+pub struct SeedField;
+
+impl SeedField {
+    /// Longest seed accepted, enough digits for any `u64`
+    pub const MAX_LENGTH: usize = 20;
+}
+
+/// Marker for the water level `Slider` on the New Worldkeeper screen
+#[derive(Component)]
+pub struct WaterLevelSlider;
+
+/// Marker for the day length `Slider` on the New Worldkeeper screen
+#[derive(Component)]
+pub struct DayLengthSlider;
+
+/// Text that displays the current value of a world setup option
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum WorldSetupValueText {
+    MapSize,
+    WaterLevel,
+    DayLength,
+    Sandbox,
+}
+
+/// Marker component for the loading screen UI
+#[derive(Component)]
+pub struct LoadingScreen;
+
+/// Marker for the loading bar node whose width tracks generation progress
+#[derive(Component)]
+pub struct LoadingBarFill;
+
+/// Marker for the loading screen's percentage text
+#[derive(Component)]
+pub struct LoadingText;
+
+/// Marker component for the in-game entity toolbar
+#[derive(Component)]
+pub struct EntityToolbar;
+
+/// Toolbar button that selects a placement tool
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ToolbarButton {
+    pub tool: PlacementTool,
+}
+
+/// Marker component for the in-game terrain tool bar
+#[derive(Component)]
+pub struct TerrainToolbar;
+
+/// Toolbar button that selects a terrain tool for the brush
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct TerrainToolButton {
+    pub tool: TerrainTool,
+}
+
+/// Filter for in-game UI that takes clicks and scrolls meant for the map underneath
+pub type MapBlockingUi = Or<(
+    With<ToolbarButton>,
+    With<MiracleButton>,
+    With<TerrainToolButton>,
+    With<Minimap>,
+    With<EventLogPanel>,
+    With<SpeedButton>,
+    With<StatisticsToggle>,
+)>;
+
+/// Collapsible in-game panel listing recent simulation events
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventLogPanel {
+    pub expanded: bool,
+}
+
+/// Header button that collapses and expands the event log
+#[derive(Component)]
+pub struct EventLogToggle;
+
+/// Scrollable list of event log entries, clipped by its parent
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct EventLogList {
+    /// Pixels scrolled down from the newest entry
+    pub offset: f32,
+}
+
+/// One entry in the event log list, recentering the camera on its location when clicked
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventLogEntry {
+    pub location: Option<UVec2>,
+}
+
+/// Marker component for the selected tile's inspector panel
+#[derive(Component)]
+pub struct InspectorPanel;
+
+/// Marker for the inspector panel's details text
+#[derive(Component)]
+pub struct InspectorText;
+
+/// Marker component for the full-screen day/night tint
+#[derive(Component)]
+pub struct DaylightOverlay;
+
+/// Marker component for the full-screen vignette that darkens the screen edges
+#[derive(Component)]
+pub struct VignetteOverlay;
+
+/// Marker component for the full-screen weather tint
+#[derive(Component)]
+pub struct WeatherOverlay;
+
+/// Marker component for the column that on-screen notifications stack in
+#[derive(Component)]
+pub struct NotificationStack;
+
+/// A single on-screen notification, removed when its time runs out
+#[derive(Component, Debug, Clone)]
+pub struct NotificationToast {
+    pub remaining_seconds: f32,
+}
+
+/// Marker component for the in-game minimap
+#[derive(Component)]
+pub struct Minimap;
+
+/// Outline on the minimap of the area the camera can see
+#[derive(Component)]
+pub struct MinimapViewport;
+
+/// Marker component for the in-game miracle bar
+#[derive(Component)]
+pub struct MiracleBar;
+
+/// Button on the miracle bar that casts a miracle
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct MiracleButton {
+    pub miracle: Miracle,
+}
+
+/// Bar showing the player's mana
+#[derive(Component, Debug, Clone, Default)]
+pub struct ManaBar {
+    /// Seconds left to show the bar in red after a rejected cast
+    pub flash_seconds: f32,
+}
+
+/// Marker for the child node that fills the mana bar up to the current mana
+#[derive(Component)]
+pub struct ManaBarFill;
+
+/// Marker component for the in-game top bar
+///
+/// Kept separate from gameplay markers so the HUD and the game world are torn down
+/// independently.
+#[derive(Component)]
+pub struct Hud;
+
+/// Text showing the stock of one resource
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ResourceText(pub ResourceKind);
+
+/// Marker component for the population text in the top bar
+#[derive(Component)]
+pub struct PopulationDisplay;
+
+/// Marker component for the clock text in the top bar
+#[derive(Component)]
+pub struct ClockDisplay;
+
+/// Marker component for the cursor position readout, shown with the debug overlay
+#[derive(Component)]
+pub struct CursorReadout;
+
+/// Top bar button that sets the simulation speed
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SpeedButton {
+    pub speed: SimulationSpeed,
+}
+
+/// Top bar button that shows or hides the statistics panel
+#[derive(Component)]
+pub struct StatisticsToggle;
+
+/// Marker component for the panel of statistics graphs, hidden until opened from the top bar
+#[derive(Component)]
+pub struct StatisticsPanel;
+
+/// Image showing the graph of one statistic
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct StatisticsGraph(pub Statistic);
+
+/// Text showing a statistic's latest value and the top of its graph
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct StatisticsLabel(pub Statistic);
+
+/// Marker component for the FPS and diagnostics overlay text
+#[derive(Component)]
+pub struct DebugOverlayText;
+
+/// Marker component for the settings screen UI
+#[derive(Component)]
+pub struct SettingsMenu;
+
+/// Component for settings screen buttons with their associated actions
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SettingsButton {
+    pub action: SettingsAction,
+}
+
+/// Actions that can be triggered by settings buttons
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingsAction {
+    CycleWindowMode,
+    CycleTheme,
+    CycleColorblindMode,
+    ToggleTerrainPatterns,
+    CycleLanguage,
+    ToggleEdgeScroll,
+    ToggleCameraInertia,
+    TogglePauseOnFocusLoss,
+    ToggleVignette,
+    ToggleVsync,
+    CycleFpsCap,
+    SelectResolution(Resolution),
+}
+
+impl SettingsButton {
+    pub fn new(action: SettingsAction) -> Self {
+        Self { action }
+    }
+}
+
+/// Marker for the master volume `Slider` on the settings screen
+#[derive(Component)]
+pub struct MasterVolumeSlider;
+
+/// Marker for the autosave interval `Slider` on the settings screen
+#[derive(Component)]
+pub struct AutosaveSlider;
+
+/// Marker for the text size `Slider` on the settings screen
+#[derive(Component)]
+pub struct FontScaleSlider;
+
+/// Marker for the mouse sensitivity `Slider` on the settings screen
+#[derive(Component)]
+pub struct MouseSensitivitySlider;
+
+/// Marker for the keyboard panning speed `Slider` on the settings screen
+#[derive(Component)]
+pub struct KeyPanSpeedSlider;
+
+/// Text that displays the current value of a setting
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum SettingValueText {
+    MasterVolume,
+    WindowMode,
+    Resolution,
+    Autosave,
+    EdgeScroll,
+    CameraInertia,
+    MouseSensitivity,
+    KeyPanSpeed,
+    PauseOnFocusLoss,
+    Vignette,
+    FrameRate,
+    Theme,
+    ColorblindMode,
+    TerrainPatterns,
+    Language,
+    FontScale,
+}
+
+/// Marker component for the controls (key rebinding) screen UI
+#[derive(Component)]
+pub struct ControlsMenu;
+
+/// Component for controls screen buttons with their associated actions
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ControlsButton {
+    pub action: ControlsAction,
+}
+
+/// Actions that can be triggered by controls screen buttons
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlsAction {
+    Rebind(InputAction),
+    ResetDefaults,
+}
+
+impl ControlsButton {
+    pub fn new(action: ControlsAction) -> Self {
+        Self { action }
+    }
+}
+
+/// Marker for the dialog asking for a name to save under
+#[derive(Component)]
+pub struct SaveAsDialog;
+
+/// Name field of the save-as dialog
+#[derive(Component)]
+pub struct SaveAsField;
+
+/// Button that saves under the name entered in the save-as dialog
+#[derive(Component)]
+pub struct SaveAsButton;
+
+/// Marker component for the saves screen UI
+#[derive(Component)]
+pub struct SavesScreen;
+
+/// Column the save slot rows are listed in, rebuilt whenever the saves change
+#[derive(Component)]
+pub struct SaveList;
+
+/// Component for saves screen buttons with their associated actions
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SavesButton {
+    pub action: SavesAction,
+}
+
+/// Actions that can be triggered by saves screen buttons, each for one save file
+#[derive(Debug, Clone, PartialEq)]
+pub enum SavesAction {
+    Load(PathBuf),
+    /// Ask for confirmation, then delete
+    Delete(PathBuf),
+    /// Rename to the name entered in the slot's name field
+    Rename(PathBuf),
+}
+
+impl SavesButton {
+    pub fn new(action: SavesAction) -> Self {
+        Self { action }
+    }
+}
+
+/// Name field of a save slot, holding the path of the save it renames
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SaveNameField {
+    pub path: PathBuf,
+}
+
+/// Text showing the key bound to an action
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct KeyBindingText(pub InputAction);
+
+/// Marker component for the keyboard shortcut help overlay
+#[derive(Component)]
+pub struct HelpOverlay;
+
+/// Text reporting the result of the last rebind attempt
+#[derive(Component)]
+pub struct ControlsStatusText;
+
+/// Colors and font sizes shared by every menu and in-game panel
+///
+/// Switched at runtime from the settings screen; nodes tagged with `ThemeColor` are recolored
+/// when it changes.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct MenuTheme {
+    pub background_color: Color,
+    pub title_color: Color,
+    pub button_normal: Color,
+    pub button_hovered: Color,
+    pub button_pressed: Color,
+    pub button_text: Color,
+    pub title_font_size: f32,
+    pub button_font_size: f32,
+    /// Multiplier already applied to the font sizes above, for text sized elsewhere
+    pub font_scale: f32,
+}
 
 impl MenuTheme {
-    pub const BACKGROUND_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
-    pub const TITLE_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
-    pub const BUTTON_NORMAL: Color = Color::srgb(0.15, 0.15, 0.15);
-    pub const BUTTON_HOVERED: Color = Color::srgb(0.25, 0.25, 0.25);
-    pub const BUTTON_PRESSED: Color = Color::srgb(0.35, 0.25, 0.35);
-    pub const BUTTON_TEXT: Color = Color::srgb(0.9, 0.9, 0.9);
-    
     pub const TITLE_FONT_SIZE: f32 = 64.0;
     pub const BUTTON_FONT_SIZE: f32 = 32.0;
-}
\ No newline at end of file
+
+    pub fn dark() -> Self {
+        Self {
+            background_color: Color::srgb(0.1, 0.1, 0.1),
+            title_color: Color::srgb(0.9, 0.9, 0.9),
+            button_normal: Color::srgb(0.15, 0.15, 0.15),
+            button_hovered: Color::srgb(0.25, 0.25, 0.25),
+            button_pressed: Color::srgb(0.35, 0.25, 0.35),
+            button_text: Color::srgb(0.9, 0.9, 0.9),
+            title_font_size: Self::TITLE_FONT_SIZE,
+            button_font_size: Self::BUTTON_FONT_SIZE,
+            font_scale: 1.0,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            background_color: Color::srgb(0.92, 0.9, 0.86),
+            title_color: Color::srgb(0.15, 0.15, 0.2),
+            button_normal: Color::srgb(0.8, 0.78, 0.74),
+            button_hovered: Color::srgb(0.68, 0.66, 0.62),
+            button_pressed: Color::srgb(0.6, 0.5, 0.6),
+            button_text: Color::srgb(0.1, 0.1, 0.12),
+            title_font_size: Self::TITLE_FONT_SIZE,
+            button_font_size: Self::BUTTON_FONT_SIZE,
+            font_scale: 1.0,
+        }
+    }
+
+    /// Pure black and white with a yellow highlight, for low-vision players
+    pub fn high_contrast() -> Self {
+        Self {
+            background_color: Color::BLACK,
+            title_color: Color::WHITE,
+            button_normal: Color::BLACK,
+            button_hovered: Color::srgb(1.0, 0.85, 0.0),
+            button_pressed: Color::srgb(1.0, 0.6, 0.0),
+            button_text: Color::WHITE,
+            title_font_size: Self::TITLE_FONT_SIZE,
+            button_font_size: Self::BUTTON_FONT_SIZE,
+            font_scale: 1.0,
+        }
+    }
+
+    /// This theme with every font size multiplied by `font_scale`
+    pub fn with_font_scale(self, font_scale: f32) -> Self {
+        Self {
+            title_font_size: Self::TITLE_FONT_SIZE * font_scale,
+            button_font_size: Self::BUTTON_FONT_SIZE * font_scale,
+            font_scale,
+            ..self
+        }
+    }
+
+    /// Scale a font size that isn't one of the theme's own by the font size multiplier
+    pub fn font_size(&self, base_size: f32) -> f32 {
+        base_size * self.font_scale
+    }
+
+    /// Color a themed node takes
+    pub fn color(&self, role: ThemeColor) -> Color {
+        match role {
+            ThemeColor::Background => self.background_color,
+            ThemeColor::Widget => self.button_normal,
+            ThemeColor::Accent | ThemeColor::Title => self.title_color,
+            ThemeColor::Text => self.button_text,
+        }
+    }
+}
+
+impl Default for MenuTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl From<ThemePreset> for MenuTheme {
+    fn from(preset: ThemePreset) -> Self {
+        match preset {
+            ThemePreset::Dark => Self::dark(),
+            ThemePreset::Light => Self::light(),
+            ThemePreset::HighContrast => Self::high_contrast(),
+        }
+    }
+}
+
+/// Part of the `MenuTheme` a node is colored with
+///
+/// Backgrounds, widgets and accents set the node's `BackgroundColor`, the rest set its text
+/// color. Nodes with a border have it drawn in the theme's highlight color. Buttons don't need
+/// this, `handle_button_interactions` recolors them every frame.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeColor {
+    /// Screen and panel backgrounds
+    Background,
+    /// Input fields and other controls that aren't buttons
+    Widget,
+    /// Bar and slider fills
+    Accent,
+    /// Headings and labels
+    Title,
+    /// Body text and values
+    Text,
+}
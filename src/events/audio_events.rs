@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+use crate::resources::SoundEffect;
+
+/// Request to play a one-shot UI sound effect
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlaySoundEffect(pub SoundEffect);
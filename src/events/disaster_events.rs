@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+use crate::resources::Disaster;
+
+/// Sent when a disaster should strike, whether rolled by the simulation or cast as a miracle
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DisasterTriggered(pub Disaster);
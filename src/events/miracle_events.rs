@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+use crate::resources::Miracle;
+
+/// Sent when a miracle has been paid for and should take effect
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MiracleCast(pub Miracle);
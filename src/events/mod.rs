@@ -0,0 +1,15 @@
+pub mod audio_events;
+pub mod disaster_events;
+pub mod miracle_events;
+pub mod notification_events;
+pub mod save_events;
+pub mod ui_events;
+pub mod world_events;
+
+pub use audio_events::*;
+pub use disaster_events::*;
+pub use miracle_events::*;
+pub use notification_events::*;
+pub use save_events::*;
+pub use ui_events::*;
+pub use world_events::*;
\ No newline at end of file
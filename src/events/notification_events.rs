@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+/// Request to show a short message on screen, which fades out by itself
+#[derive(Event, Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+}
+
+impl Notification {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
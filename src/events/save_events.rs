@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use crate::save::{save_path, DEFAULT_SAVE_NAME};
+
+/// Request to save the current game, to the default save file unless a name is given
+#[derive(Event, Debug, Clone, Default)]
+pub struct SaveGameRequested {
+    /// Where to go once the save has been written
+    pub after: AfterSave,
+    /// Name to save under, which also picks the file, replacing any save already there
+    pub name: Option<String>,
+}
+
+/// Where the player goes once a requested save has been written
+///
+/// Ordered by how far each leaves the game, so several requests in one frame follow the
+/// furthest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AfterSave {
+    /// Keep playing
+    #[default]
+    Stay,
+    /// Leave the game for the main menu
+    ReturnToMenu,
+    /// Exit WorldKeeper
+    Quit,
+}
+
+/// Request to load a save file and enter the game
+#[derive(Event, Debug, Clone)]
+pub struct LoadGameRequested {
+    pub path: PathBuf,
+}
+
+impl Default for LoadGameRequested {
+    /// Load the save written by the pause menu
+    fn default() -> Self {
+        Self {
+            path: save_path(DEFAULT_SAVE_NAME),
+        }
+    }
+}
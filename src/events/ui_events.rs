@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// Sent when a button is activated, either by a mouse click or by the keyboard
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ButtonActivated {
+    pub entity: Entity,
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// Request to replace the world in play with a newly generated one, without leaving the game
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RegenerateWorld {
+    pub seed: u64,
+}
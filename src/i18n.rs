@@ -0,0 +1,140 @@
+//! Translated UI strings, looked up by key from a JSON file per language.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Directory, relative to the working directory, that holds one strings file per language
+pub const I18N_DIRECTORY: &str = "assets/i18n";
+
+/// Languages the UI can be shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    /// Get the next language when cycling through the options
+    pub fn next(self) -> Self {
+        match self {
+            Language::English => Language::Spanish,
+            Language::Spanish => Language::English,
+        }
+    }
+
+    /// Name of the language in that language, shown on the settings screen
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    /// Language code naming the strings file
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    /// Path of the strings file for this language
+    pub fn strings_path(self) -> PathBuf {
+        Path::new(I18N_DIRECTORY).join(format!("{}.json", self.code()))
+    }
+}
+
+/// UI strings in the chosen language
+///
+/// Screens look their text up when they are set up, so a new language shows the next time
+/// each screen opens.
+#[derive(Resource, Debug, Default)]
+pub struct Locale {
+    pub language: Language,
+    strings: HashMap<String, String>,
+    /// Keys already warned about, so a missing string is only reported once
+    missing: Mutex<HashSet<String>>,
+}
+
+impl Locale {
+    /// Load the strings for a language, falling back to bare keys if the file can't be read
+    pub fn load(language: Language) -> Self {
+        let path = language.strings_path();
+        let strings = load_strings(&path).unwrap_or_else(|error| {
+            warn!("Failed to read strings from {}: {}", path.display(), error);
+            HashMap::new()
+        });
+
+        Self {
+            language,
+            strings,
+            missing: Mutex::default(),
+        }
+    }
+
+    /// Translated string for a key, or the key itself when the language has no such string
+    pub fn t(&self, key: &str) -> String {
+        if let Some(value) = self.strings.get(key) {
+            return value.clone();
+        }
+
+        if let Ok(mut missing) = self.missing.lock() {
+            if missing.insert(key.to_string()) {
+                warn!("Missing {} string for \"{}\"", self.language.label(), key);
+            }
+        }
+        key.to_string()
+    }
+}
+
+/// Read a flat JSON object of keys to translated strings
+pub fn load_strings(path: &Path) -> io::Result<HashMap<String, String>> {
+    let json = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_keys_fall_back_to_the_key() {
+        let locale = Locale {
+            language: Language::Spanish,
+            strings: HashMap::from([("menu.quit".to_string(), "Salir".to_string())]),
+            missing: Mutex::default(),
+        };
+
+        assert_eq!(locale.t("menu.quit"), "Salir");
+        assert_eq!(locale.t("menu.no_such_key"), "menu.no_such_key");
+        // Asking again still falls back, with the key only recorded once
+        assert_eq!(locale.t("menu.no_such_key"), "menu.no_such_key");
+        assert_eq!(locale.missing.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn every_language_has_the_same_keys() {
+        let english: HashSet<String> = load_strings(&Language::English.strings_path())
+            .unwrap()
+            .into_keys()
+            .collect();
+        assert!(!english.is_empty());
+
+        let mut language = Language::English.next();
+        while language != Language::English {
+            let keys: HashSet<String> = load_strings(&language.strings_path())
+                .unwrap()
+                .into_keys()
+                .collect();
+            assert_eq!(keys, english, "{} strings don't match English", language.label());
+            language = language.next();
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Worldkeeper's game modules, shared by the game binary and the benchmarks
+
+pub mod components;
+pub mod events;
+pub mod i18n;
+pub mod plugins;
+pub mod resources;
+pub mod save;
+pub mod systems;
+pub mod utils;
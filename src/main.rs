@@ -1,14 +1,20 @@
 use bevy::prelude::*;
+use bevy::sprite::Mesh2dHandle;
 
-// Module declarations
-mod components;
-mod resources;
-mod systems;
-mod plugins;
+// Game modules live in the library so benchmarks can use them too
+use worldkeeper::{components, plugins, resources, systems};
 
 // Use our modules
-use resources::GameState;
-use plugins::{MenuPlugin, NewWorldkeeperPlugin};
+use components::{no_modal_open, CameraController, CameraZoom, GameCamera, Tile};
+use resources::{GameState, InputAction, KeyBindings, Sandbox, Settings, SimulationClock, SystemClipboard, TileRendering, WorldBounds, WorldConfig};
+use plugins::{ActionHistoryPlugin, ControlsPlugin, CreditsPlugin, CursorPlugin, DebugOverlayPlugin, DisasterPlugin, EconomyPlugin, EventLogPlugin, GameAudioPlugin, GameOverPlugin, HealthPlugin, HelpPlugin, HudPlugin, LightingPlugin, LoadingPlugin, MenuPlugin, MinimapPlugin, MiraclePlugin, NewWorldkeeperPlugin, NotificationsPlugin, PausePlugin, PlacementPlugin, PopulationPlugin, RegenerationPlugin, SavePlugin, SavesPlugin, ScreenshotPlugin, SelectionPlugin, SettingsPlugin, SimulationPlugin, StatisticsPlugin, TerrainBrushPlugin, UiScalingPlugin, WeatherPlugin, WidgetsPlugin};
+use systems::camera::{
+    apply_camera_inertia, clamp_camera_to_bounds, cull_offscreen_tiles, drag_camera, ease_camera_focus, edge_scroll_camera,
+    focus_camera_hotkey, lock_camera_rotation, move_camera, reset_camera_hotkey, zoom_camera,
+};
+use systems::diagnostics::log_state_transitions;
+use systems::gamepad::{gamepad_just_pressed, log_gamepad_connections};
+use systems::save_load::apply_pending_load;
 
 fn main() {
     App::new()
@@ -20,50 +26,129 @@ fn main() {
             }),
             ..default()
         }))
-        // Add our custom plugins
+        // Add our custom plugins, grouped because plugin tuples are limited in size
         .add_plugins((
-            MenuPlugin,
-            NewWorldkeeperPlugin,
+            // Screens
+            (
+                MenuPlugin,
+                NewWorldkeeperPlugin,
+                SettingsPlugin,
+                ControlsPlugin,
+                SavesPlugin,
+                LoadingPlugin,
+                PausePlugin,
+                GameOverPlugin,
+                CreditsPlugin,
+            ),
+            // Simulation
+            (
+                SavePlugin,
+                SimulationPlugin,
+                HealthPlugin,
+                WeatherPlugin,
+                DisasterPlugin,
+                LightingPlugin,
+                PopulationPlugin,
+                EconomyPlugin,
+                EventLogPlugin,
+            ),
+            // Player tools and in-game UI
+            (
+                PlacementPlugin,
+                SelectionPlugin,
+                MiraclePlugin,
+                HudPlugin,
+                MinimapPlugin,
+                StatisticsPlugin,
+                TerrainBrushPlugin,
+                ActionHistoryPlugin,
+                RegenerationPlugin,
+            ),
+            // Shared infrastructure
+            (
+                WidgetsPlugin,
+                GameAudioPlugin,
+                DebugOverlayPlugin,
+                NotificationsPlugin,
+                UiScalingPlugin,
+                HelpPlugin,
+                ScreenshotPlugin,
+                CursorPlugin,
+            ),
         ))
-        // Setup systems that only run in InGame state
-        .add_systems(OnEnter(GameState::InGame), setup_game)
+        .init_resource::<WorldBounds>()
+        .init_resource::<WorldConfig>()
+        .init_resource::<Sandbox>()
+        .init_resource::<SystemClipboard>()
+        // Setup systems that only run in InGame state. The world itself is generated while Loading.
+        .add_systems(OnEnter(GameState::InGame), (setup_game, apply_pending_load).chain())
         .add_systems(
             Update,
             (
-                handle_input,
-                move_camera,
+                handle_input.run_if(no_modal_open),
+                // Camera systems all write the camera transform, so run them in a fixed order
+                (
+                    apply_camera_inertia.run_if(resource_changed::<Settings>),
+                    focus_camera_hotkey,
+                    reset_camera_hotkey,
+                    move_camera,
+                    edge_scroll_camera,
+                    drag_camera,
+                    zoom_camera,
+                    ease_camera_focus,
+                    lock_camera_rotation,
+                    clamp_camera_to_bounds,
+                    // Culls against where the camera ended up this frame. Batched tiles have
+                    // no visibility of their own, their chunk meshes are culled by the renderer.
+                    cull_offscreen_tiles.run_if(resource_equals(TileRendering::PerTile)),
+                ).chain(),
             ).run_if(in_state(GameState::InGame))
         )
-        .add_systems(OnExit(GameState::InGame), cleanup_game)
+        // The world survives pausing, so it is only torn down when the game ends
+        .add_systems(OnEnter(GameState::MainMenu), cleanup_game)
+        .add_systems(OnEnter(GameState::GameOver), cleanup_game)
+        .add_systems(Update, (log_gamepad_connections, log_state_transitions))
         .run();
 }
 
 /// Setup the game world when entering InGame state
 fn setup_game(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    settings: Res<Settings>,
+    existing_world: Query<(), With<GameCamera>>,
 ) {
+    // Resuming from pause re-enters InGame while the world still exists
+    if !existing_world.is_empty() {
+        return;
+    }
+    
     info!("Setting up game world");
     
-    // Spawn a 2D camera
-    commands.spawn(Camera2dBundle::default());
-
-    // Spawn a simple colored rectangle as a placeholder
-    commands.spawn(ColorMesh2dBundle {
-        mesh: meshes.add(Rectangle::new(100.0, 100.0)).into(),
-        material: materials.add(Color::srgb(0.8, 0.2, 0.3)),
-        transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
-        ..default()
-    });
+    // A fresh world starts its clock from the beginning
+    commands.insert_resource(SimulationClock::default());
+    
+    // Spawn a 2D camera that can be panned and zoomed. `apply_camera_inertia` only runs when the
+    // settings change, so the new camera takes the inertia setting from the start.
+    commands.spawn((
+        Camera2dBundle::default(),
+        GameCamera,
+        CameraZoom::default(),
+        CameraController {
+            inertia: settings.camera_inertia,
+            ..default()
+        },
+    ));
 
     info!("Game world initialized!");
 }
 
-/// Cleanup game world when exiting InGame state
+/// Filter for the gameplay camera and everything drawn in the game world
+type GameWorld = Or<(With<GameCamera>, With<Mesh2dHandle>, With<Tile>)>;
+
+/// Cleanup game world when returning to the main menu or reaching game over
 fn cleanup_game(
     mut commands: Commands,
-    query: Query<Entity, Or<(With<Camera>, With<Handle<Mesh>>)>>,
+    query: Query<Entity, GameWorld>,
 ) {
     for entity in &query {
         commands.entity(entity).despawn_recursive();
@@ -74,33 +159,146 @@ fn cleanup_game(
 /// Handle basic input during gameplay
 fn handle_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        info!("Space pressed - implement action here");
+    if key_bindings.just_pressed(&keyboard_input, InputAction::PrimaryAction)
+        || gamepad_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::South)
+    {
+        info!("Primary action pressed - implement action here");
     }
 }
 
-/// Simple camera movement with arrow keys during gameplay
-fn move_camera(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut camera_query: Query<&mut Transform, With<Camera>>,
-    time: Res<Time>,
-) {
-    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-        let movement_speed = 200.0;
-        let movement_delta = movement_speed * time.delta_seconds();
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
 
-        if keyboard_input.pressed(KeyCode::ArrowLeft) {
-            camera_transform.translation.x -= movement_delta;
-        }
-        if keyboard_input.pressed(KeyCode::ArrowRight) {
-            camera_transform.translation.x += movement_delta;
+    use super::*;
+    use worldkeeper::components::MenuTheme;
+    use worldkeeper::i18n::Locale;
+    use worldkeeper::resources::{Credits, GameOverReason, KeyBindings, PendingRebind, Settings};
+    use worldkeeper::systems::controls::{cleanup_controls_menu, setup_controls_menu};
+    use worldkeeper::systems::credits::{cleanup_credits_screen, setup_credits_screen};
+    use worldkeeper::systems::game_over::{cleanup_game_over_screen, setup_game_over_screen};
+    use worldkeeper::systems::loading::{cleanup_loading_screen, setup_loading_screen};
+    use worldkeeper::systems::menu::{cleanup_main_menu, setup_main_menu};
+    use worldkeeper::systems::new_worldkeeper::{cleanup_new_worldkeeper_screen, setup_new_worldkeeper_screen};
+    use worldkeeper::systems::pause::{cleanup_pause_menu, setup_pause_menu};
+    use worldkeeper::systems::saves::{cleanup_saves_screen, setup_saves_screen};
+    use worldkeeper::systems::settings::{cleanup_settings_menu, setup_settings_menu};
+
+    /// Assert that exactly one camera exists, so UI and gameplay never render ambiguously
+    fn assert_single_camera(world: &mut World) {
+        let count = world
+            .query_filtered::<(), With<Camera>>()
+            .iter(world)
+            .count();
+        assert_eq!(count, 1, "expected exactly one camera, found {}", count);
+    }
+
+    /// App with every screen's camera setup and cleanup, but no rendering or input
+    fn camera_test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_state::<GameState>()
+            .init_resource::<WorldConfig>()
+            .init_resource::<Sandbox>()
+            .init_resource::<Settings>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<PendingRebind>()
+            .init_resource::<GameOverReason>()
+            .init_resource::<Credits>()
+            .init_resource::<MenuTheme>()
+            .init_resource::<Locale>()
+            .add_systems(OnEnter(GameState::MainMenu), (setup_main_menu, cleanup_game))
+            .add_systems(OnExit(GameState::MainMenu), cleanup_main_menu)
+            .add_systems(OnEnter(GameState::NewWorldkeeper), setup_new_worldkeeper_screen)
+            .add_systems(OnExit(GameState::NewWorldkeeper), cleanup_new_worldkeeper_screen)
+            .add_systems(OnEnter(GameState::Settings), setup_settings_menu)
+            .add_systems(OnExit(GameState::Settings), cleanup_settings_menu)
+            .add_systems(OnEnter(GameState::Controls), setup_controls_menu)
+            .add_systems(OnExit(GameState::Controls), cleanup_controls_menu)
+            .add_systems(OnEnter(GameState::Saves), setup_saves_screen)
+            .add_systems(OnExit(GameState::Saves), cleanup_saves_screen)
+            .add_systems(OnEnter(GameState::Credits), setup_credits_screen)
+            .add_systems(OnExit(GameState::Credits), cleanup_credits_screen)
+            .add_systems(OnEnter(GameState::Loading), setup_loading_screen)
+            .add_systems(OnExit(GameState::Loading), cleanup_loading_screen)
+            .add_systems(OnEnter(GameState::InGame), setup_game)
+            .add_systems(OnEnter(GameState::Paused), setup_pause_menu)
+            .add_systems(OnExit(GameState::Paused), cleanup_pause_menu)
+            .add_systems(OnEnter(GameState::GameOver), (setup_game_over_screen, cleanup_game))
+            .add_systems(OnExit(GameState::GameOver), cleanup_game_over_screen);
+        app.update();
+        app
+    }
+
+    fn transition(app: &mut App, state: GameState) {
+        app.world_mut().resource_mut::<NextState<GameState>>().set(state.clone());
+        app.update();
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), state);
+    }
+
+    #[test]
+    fn main_menu_starts_with_one_camera() {
+        let mut app = camera_test_app();
+        assert_single_camera(app.world_mut());
+    }
+
+    #[test]
+    fn menu_screens_keep_one_camera() {
+        let mut app = camera_test_app();
+        for state in [
+            GameState::NewWorldkeeper,
+            GameState::MainMenu,
+            GameState::Settings,
+            GameState::Controls,
+            GameState::Settings,
+            GameState::MainMenu,
+            GameState::Saves,
+            GameState::MainMenu,
+            GameState::Credits,
+            GameState::MainMenu,
+        ] {
+            transition(&mut app, state);
+            assert_single_camera(app.world_mut());
         }
-        if keyboard_input.pressed(KeyCode::ArrowUp) {
-            camera_transform.translation.y += movement_delta;
+    }
+
+    #[test]
+    fn new_game_cameras_follow_the_inertia_setting() {
+        for inertia in [false, true] {
+            let mut app = camera_test_app();
+            app.world_mut().resource_mut::<Settings>().camera_inertia = inertia;
+            transition(&mut app, GameState::Loading);
+            transition(&mut app, GameState::InGame);
+
+            let world = app.world_mut();
+            let controller = world
+                .query_filtered::<&CameraController, With<GameCamera>>()
+                .single(world);
+            assert_eq!(controller.inertia, inertia);
         }
-        if keyboard_input.pressed(KeyCode::ArrowDown) {
-            camera_transform.translation.y -= movement_delta;
+    }
+
+    #[test]
+    fn gameplay_and_pause_screens_keep_one_camera() {
+        let mut app = camera_test_app();
+        for state in [
+            GameState::Loading,
+            GameState::InGame,
+            GameState::Paused,
+            GameState::Settings,
+            GameState::Controls,
+            GameState::Settings,
+            GameState::Paused,
+            GameState::InGame,
+            GameState::GameOver,
+            GameState::MainMenu,
+        ] {
+            transition(&mut app, state);
+            assert_single_camera(app.world_mut());
         }
     }
 }
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::no_modal_open;
+use crate::resources::{ActionHistory, GameState, WorldReset};
+use crate::systems::action_history::*;
+
+/// Plugin for undoing and redoing the player's edits to the world
+pub struct ActionHistoryPlugin;
+
+impl Plugin for ActionHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ActionHistory>()
+            .add_systems(Update, reset_action_history.in_set(WorldReset))
+            .add_systems(
+                Update,
+                (undo_world_edit, redo_world_edit)
+                    .chain()
+                    .run_if(in_state(GameState::InGame).and_then(no_modal_open))
+            );
+    }
+}
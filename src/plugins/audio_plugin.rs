@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use crate::events::PlaySoundEffect;
+use crate::resources::{GameState, Settings};
+use crate::systems::audio::*;
+
+/// Plugin for background music and UI sound effects
+///
+/// Named to avoid clashing with Bevy's own `AudioPlugin`.
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<PlaySoundEffect>()
+            .add_systems(Startup, load_audio_assets)
+            .add_systems(
+                Update,
+                (
+                    update_background_music.run_if(state_changed::<GameState>),
+                    update_music_volume.run_if(resource_changed::<Settings>),
+                    play_sound_effects,
+                )
+            );
+    }
+}
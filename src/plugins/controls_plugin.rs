@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, KeyBindings, PendingRebind};
+use crate::systems::controls::*;
+use crate::systems::menu::*;
+
+/// Plugin for the controls screen and the `KeyBindings` resource
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(load_startup_key_bindings())
+            .init_resource::<PendingRebind>()
+            
+            // Systems that run when entering Controls state
+            .add_systems(OnEnter(GameState::Controls), setup_controls_menu)
+            
+            // Systems that run while in Controls state
+            .add_systems(
+                Update,
+                (
+                    capture_rebind_key
+                        .before(handle_menu_keyboard_navigation)
+                        .before(handle_escape),
+                    handle_controls_actions.after(handle_menu_actions),
+                    update_controls_text
+                        .after(capture_rebind_key)
+                        .after(handle_controls_actions)
+                        .run_if(resource_changed::<KeyBindings>.or_else(resource_changed::<PendingRebind>)),
+                ).run_if(in_state(GameState::Controls))
+            )
+            
+            // Systems that run when exiting Controls state
+            .add_systems(OnExit(GameState::Controls), (cleanup_controls_menu, persist_key_bindings));
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use crate::resources::{Credits, GameState};
+use crate::systems::credits::*;
+
+/// Plugin for the scrolling credits screen
+pub struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Credits>()
+            .add_systems(OnEnter(GameState::Credits), setup_credits_screen)
+            .add_systems(
+                Update,
+                scroll_credits.run_if(in_state(GameState::Credits))
+            )
+            .add_systems(OnExit(GameState::Credits), cleanup_credits_screen);
+    }
+}
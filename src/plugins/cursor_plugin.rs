@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use crate::systems::camera::drag_camera;
+use crate::systems::cursor::*;
+
+/// Plugin that styles the OS cursor for menus, map tools and camera dragging
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        // Runs in every state, so the cursor returns to the arrow as soon as the game is left,
+        // and after a drag starts or ends so the hand shows on the same frame
+        app.add_systems(Update, update_cursor_icon.after(drag_camera));
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use crate::resources::DebugOverlay;
+use crate::systems::diagnostics::*;
+
+/// Plugin for the F3 FPS and diagnostics overlay
+///
+/// Named to avoid confusion with Bevy's own `DiagnosticsPlugin`, which stores the measurements.
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins((FrameTimeDiagnosticsPlugin, EntityCountDiagnosticsPlugin))
+            .init_resource::<DebugOverlay>()
+            .add_systems(Startup, setup_debug_overlay)
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    apply_debug_overlay_visibility.run_if(resource_changed::<DebugOverlay>),
+                    // Only pay for formatting while the overlay is on screen
+                    update_debug_overlay.run_if(|overlay: Res<DebugOverlay>| overlay.visible),
+                ).chain()
+            );
+    }
+}
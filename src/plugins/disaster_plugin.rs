@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use crate::events::DisasterTriggered;
+use crate::resources::{ActiveDisasters, DisasterConfig, DisasterTimer, GameState, WorldReset};
+use crate::systems::disasters::*;
+use crate::systems::miracles::apply_miracles;
+use crate::systems::simulation::advance_simulation_clock;
+
+/// Plugin for wildfires, floods and earthquakes that strike at random or are cast as miracles
+pub struct DisasterPlugin;
+
+impl Plugin for DisasterPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<DisasterTriggered>()
+            .init_resource::<DisasterConfig>()
+            .init_resource::<ActiveDisasters>()
+            .init_resource::<DisasterTimer>()
+            .add_systems(Update, reset_disasters.in_set(WorldReset))
+            .add_systems(
+                FixedUpdate,
+                (
+                    roll_disasters,
+                    advance_disasters,
+                ).chain().after(advance_simulation_clock).run_if(in_state(GameState::InGame))
+            )
+            .add_systems(
+                Update,
+                start_disasters.after(apply_miracles).run_if(in_state(GameState::InGame))
+            )
+            // Fires stay visible behind the pause menu, like the selection highlight
+            .add_systems(
+                Update,
+                draw_wildfires
+                    .run_if(in_state(GameState::InGame).or_else(in_state(GameState::Paused)))
+            );
+    }
+}
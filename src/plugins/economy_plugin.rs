@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, Resources, WorldReset};
+use crate::systems::economy::*;
+use crate::systems::population::update_population;
+
+/// Plugin for the daily food, water and wood economy
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Resources>()
+            .add_systems(Update, reset_resources.in_set(WorldReset))
+            
+            // Runs after the daily population update so shortages apply to the new population
+            .add_systems(
+                FixedUpdate,
+                update_economy
+                    .after(update_population)
+                    .run_if(in_state(GameState::InGame))
+            );
+    }
+}
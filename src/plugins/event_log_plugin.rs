@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use crate::resources::{EventLog, GameState, WorldReset};
+use crate::systems::event_log::*;
+
+/// Plugin for the in-game log of recent simulation events
+pub struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<EventLog>()
+            .add_systems(Update, reset_event_log.in_set(WorldReset))
+            .add_systems(OnEnter(GameState::InGame), setup_event_log_panel)
+            .add_systems(
+                Update,
+                (
+                    toggle_event_log,
+                    update_event_log_list,
+                    scroll_event_log,
+                    recenter_camera_on_entry,
+                ).chain().run_if(in_state(GameState::InGame))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_event_log_panel);
+    }
+}
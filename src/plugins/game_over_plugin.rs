@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use crate::resources::{GameOverReason, GameState};
+use crate::systems::game_over::*;
+
+/// Plugin for the game over screen
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GameOverReason>()
+            .add_systems(OnEnter(GameState::GameOver), setup_game_over_screen)
+            .add_systems(OnExit(GameState::GameOver), cleanup_game_over_screen);
+    }
+}
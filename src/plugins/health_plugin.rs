@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use crate::resources::{DecayConfig, DecayTimer, GameState, WorldReset};
+use crate::systems::health::*;
+use crate::systems::simulation::advance_simulation_clock;
+
+/// Plugin for the health of placed entities, which wears down on hostile terrain and in harsh
+/// weather until they die
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<DecayConfig>()
+            .init_resource::<DecayTimer>()
+            .add_systems(Update, reset_decay_timer.in_set(WorldReset))
+            .add_systems(
+                FixedUpdate,
+                decay_health
+                    .after(advance_simulation_clock)
+                    .run_if(in_state(GameState::InGame))
+            )
+            // The bars stay visible behind the pause menu, like the selection highlight
+            .add_systems(
+                Update,
+                draw_health_bars
+                    .run_if(in_state(GameState::InGame).or_else(in_state(GameState::Paused)))
+            );
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, KeyBindings};
+use crate::systems::help::*;
+use crate::systems::menu::handle_escape;
+use crate::systems::selection::clear_selection_on_escape;
+
+/// Plugin for the F1 keyboard shortcut overlay shown over the game
+pub struct HelpPlugin;
+
+impl Plugin for HelpPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (
+                    toggle_help_overlay
+                        .before(handle_escape)
+                        .before(clear_selection_on_escape),
+                    update_help_overlay.run_if(resource_changed::<KeyBindings>),
+                ).run_if(in_state(GameState::InGame).or_else(in_state(GameState::Paused)))
+            )
+            // The overlay is only for gameplay, so close it when leaving for another screen
+            .add_systems(OnEnter(GameState::Settings), cleanup_help_overlay)
+            .add_systems(OnEnter(GameState::MainMenu), cleanup_help_overlay)
+            .add_systems(OnEnter(GameState::GameOver), cleanup_help_overlay);
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, Population, Resources, SimulationClock};
+use crate::systems::hud::*;
+
+/// Plugin for the in-game top bar showing the time, speed controls, population and resources
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::InGame), setup_hud)
+            // The simulation ticks in `FixedUpdate`, so the HUD sees its changes the same frame
+            .add_systems(
+                Update,
+                (
+                    update_hud_clock.run_if(resource_changed::<SimulationClock>),
+                    update_hud_population.run_if(resource_changed::<Population>),
+                    update_hud_resources.run_if(resource_changed::<Resources>),
+                    (handle_speed_buttons, update_speed_button_colors).chain(),
+                    update_cursor_readout,
+                ).run_if(in_state(GameState::InGame))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_hud);
+    }
+}
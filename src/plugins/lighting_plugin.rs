@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, SimulationClock};
+use crate::systems::lighting::*;
+
+/// Plugin for the day/night cycle, which tints the world with the time of day, and for the
+/// vignette that darkens the screen edges as shortages threaten the world
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::InGame), (setup_daylight_overlay, setup_vignette))
+            .add_systems(
+                Update,
+                update_daylight_overlay
+                    .run_if(in_state(GameState::InGame).and_then(resource_changed::<SimulationClock>))
+            )
+            .add_systems(Update, update_vignette.run_if(in_state(GameState::InGame)))
+            .add_systems(OnExit(GameState::InGame), (cleanup_daylight_overlay, cleanup_vignette));
+    }
+}
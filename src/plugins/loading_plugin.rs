@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use crate::resources::{
+    BiomeTable, GameState, TerrainMaterials, TerrainPalette, TileEntities, TileRendering,
+    WorldGenBudget, WorldGenProgress, WorldReset,
+};
+use crate::systems::loading::*;
+use crate::systems::world_gen::*;
+
+/// Plugin for the loading screen shown while the world is generated
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<WorldGenProgress>()
+            .init_resource::<WorldGenBudget>()
+            .init_resource::<TileRendering>()
+            .init_resource::<BiomeTable>()
+            .init_resource::<TileEntities>()
+            
+            // Every feature's per-world resets run once a new world starts generating, here or
+            // when the world is regenerated in place
+            .configure_sets(Update, WorldReset.run_if(resource_added::<WorldGenerator>))
+            
+            // Systems that run when entering Loading state
+            .add_systems(OnEnter(GameState::Loading), (setup_loading_screen, start_world_generation))
+            
+            // The map is generated in the background, then a budget of tiles is spawned per frame
+            // and the game starts once it is done
+            .add_systems(
+                Update,
+                (
+                    generate_world_step,
+                    update_loading_screen.run_if(resource_changed::<WorldGenProgress>),
+                    finish_world_generation,
+                ).chain().run_if(in_state(GameState::Loading))
+            )
+            
+            // Batched tile meshes follow terrain changes for the rest of the game
+            .add_systems(Update, recolor_tile_chunks.run_if(in_state(GameState::InGame)))
+            
+            // The world is recolored in place when the theme changes the terrain palette
+            .add_systems(
+                Update,
+                repaint_terrain
+                    .run_if(resource_exists::<TerrainMaterials>.and_then(resource_changed::<TerrainPalette>))
+            )
+            
+            // Systems that run when exiting Loading state
+            .add_systems(OnExit(GameState::Loading), (cleanup_loading_screen, drop_world_generator));
+    }
+}
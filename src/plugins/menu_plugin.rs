@@ -1,6 +1,12 @@
 use bevy::prelude::*;
-use crate::resources::GameState;
+use crate::components::confirm_dialog::no_modal_open;
+use crate::components::slider::slider_interaction_system;
+use crate::components::ui::FocusedButton;
+use crate::events::ButtonActivated;
+use crate::resources::{GameState, Sandbox, SettingsOrigin, WorldConfig};
+use crate::systems::attract_mode::*;
 use crate::systems::menu::*;
+use crate::systems::new_worldkeeper::*;
 
 /// Plugin for managing the main menu and UI interactions
 pub struct MenuPlugin;
@@ -10,22 +16,44 @@ impl Plugin for MenuPlugin {
         app
             // Add state management
             .init_state::<GameState>()
+            .init_resource::<FocusedButton>()
+            .init_resource::<SettingsOrigin>()
+            .add_event::<ButtonActivated>()
             
             // Systems that run when entering MainMenu state
-            .add_systems(OnEnter(GameState::MainMenu), setup_main_menu)
+            .add_systems(OnEnter(GameState::MainMenu), (setup_main_menu, start_attract_mode))
             
-            // Systems that run while in MainMenu state
+            // Shared button systems for every screen that allows UI interaction, and for dialogs
+            // opened over the game
             .add_systems(
                 Update,
                 (
+                    detect_button_presses,
+                    handle_menu_keyboard_navigation,
                     handle_button_interactions,
                     handle_menu_actions,
-                    handle_escape_to_menu,
-                ).run_if(in_state(GameState::MainMenu))
+                ).chain().run_if(ui_interaction_allowed.or_else(not(no_modal_open)))
             )
+            .add_systems(Update, animate_button_scale.after(handle_button_interactions))
             
-            // Systems that run when exiting MainMenu state
-            .add_systems(OnExit(GameState::MainMenu), cleanup_main_menu);
+            // Escape follows `GameState::escape_target` in every state, unless a dialog takes it
+            .add_systems(Update, handle_escape.run_if(no_modal_open))
+            
+            .add_systems(Update, animate_menu_starfield.run_if(in_state(GameState::MainMenu)))
+            
+            // A world pans behind the menu once it sits idle, until the next input
+            .add_systems(
+                Update,
+                (
+                    spawn_attract_world,
+                    update_attract_mode,
+                    pan_attract_view,
+                ).chain().run_if(in_state(GameState::MainMenu))
+            )
+            
+            // Systems that run when exiting MainMenu state. The attract world goes with the menu,
+            // so it never carries into a game.
+            .add_systems(OnExit(GameState::MainMenu), (cleanup_main_menu, cleanup_attract_mode));
     }
 }
 
@@ -38,60 +66,13 @@ impl Plugin for NewWorldkeeperPlugin {
             .add_systems(OnEnter(GameState::NewWorldkeeper), setup_new_worldkeeper_screen)
             .add_systems(
                 Update,
-                handle_escape_to_menu.run_if(in_state(GameState::NewWorldkeeper))
+                (
+                    handle_world_setup_actions.after(handle_menu_actions),
+                    handle_world_setup_sliders.after(slider_interaction_system),
+                    update_world_setup_text
+                        .run_if(resource_changed::<WorldConfig>.or_else(resource_changed::<Sandbox>)),
+                ).run_if(in_state(GameState::NewWorldkeeper))
             )
             .add_systems(OnExit(GameState::NewWorldkeeper), cleanup_new_worldkeeper_screen);
     }
 }
-
-/// Temporary setup for the New Worldkeeper screen
-fn setup_new_worldkeeper_screen(mut commands: Commands) {
-    info!("Setting up New Worldkeeper screen");
-    
-    // Spawn a camera for UI rendering
-    commands.spawn(Camera2dBundle::default());
-    
-    commands
-        .spawn(NodeBundle {
-            style: Style {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                flex_direction: FlexDirection::Column,
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            background_color: Color::srgb(0.1, 0.2, 0.1).into(),
-            ..default()
-        })
-        .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                "New Worldkeeper Screen",
-                TextStyle {
-                    font_size: 48.0,
-                    color: Color::WHITE,
-                    ..default()
-                },
-            ));
-            
-            parent.spawn(TextBundle::from_section(
-                "Press ESC to return to main menu",
-                TextStyle {
-                    font_size: 24.0,
-                    color: Color::srgb(0.8, 0.8, 0.8),
-                    ..default()
-                },
-            ));
-        });
-}
-
-/// Cleanup New Worldkeeper screen
-fn cleanup_new_worldkeeper_screen(
-    mut commands: Commands,
-    query: Query<Entity, Or<(With<Node>, With<Camera>)>>,
-) {
-    for entity in &query {
-        commands.entity(entity).despawn_recursive();
-    }
-    info!("New Worldkeeper screen cleaned up");
-}
\ No newline at end of file
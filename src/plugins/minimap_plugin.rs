@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use crate::resources::GameState;
+use crate::systems::camera::clamp_camera_to_bounds;
+use crate::systems::minimap::*;
+
+/// Plugin for the minimap of the world and the camera view on it
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::InGame), setup_minimap)
+            .add_systems(
+                Update,
+                (
+                    recenter_camera_from_minimap.before(clamp_camera_to_bounds),
+                    update_minimap_viewport.after(clamp_camera_to_bounds),
+                    update_minimap_image,
+                ).run_if(in_state(GameState::InGame))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_minimap);
+    }
+}
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::no_modal_open;
+use crate::events::MiracleCast;
+use crate::resources::{DivinePower, GameState, WorldReset};
+use crate::systems::miracles::*;
+
+/// Plugin for miracles cast with the player's mana
+pub struct MiraclePlugin;
+
+impl Plugin for MiraclePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<MiracleCast>()
+            .init_resource::<DivinePower>()
+            .add_systems(Update, reset_divine_power.in_set(WorldReset))
+            .add_systems(OnEnter(GameState::InGame), setup_miracle_bar)
+            .add_systems(
+                Update,
+                (
+                    regenerate_mana,
+                    cast_miracles.run_if(no_modal_open),
+                    // Miracle weather overrides the regular weather roll, which already ran in
+                    // this frame's `FixedUpdate`
+                    apply_miracles,
+                    update_mana_bar,
+                    update_miracle_buttons,
+                ).chain().run_if(in_state(GameState::InGame))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_miracle_bar);
+    }
+}
@@ -1,3 +1,69 @@
+pub mod action_history_plugin;
+pub mod audio_plugin;
+pub mod controls_plugin;
+pub mod credits_plugin;
+pub mod cursor_plugin;
+pub mod diagnostics_plugin;
+pub mod disaster_plugin;
+pub mod economy_plugin;
+pub mod event_log_plugin;
+pub mod game_over_plugin;
+pub mod health_plugin;
+pub mod help_plugin;
+pub mod hud_plugin;
+pub mod lighting_plugin;
+pub mod loading_plugin;
 pub mod menu_plugin;
+pub mod minimap_plugin;
+pub mod miracle_plugin;
+pub mod notifications_plugin;
+pub mod pause_plugin;
+pub mod placement_plugin;
+pub mod population_plugin;
+pub mod regeneration_plugin;
+pub mod save_plugin;
+pub mod saves_plugin;
+pub mod screenshot_plugin;
+pub mod selection_plugin;
+pub mod settings_plugin;
+pub mod simulation_plugin;
+pub mod statistics_plugin;
+pub mod terrain_brush_plugin;
+pub mod ui_scale_plugin;
+pub mod weather_plugin;
+pub mod widgets_plugin;
 
-pub use menu_plugin::*;
\ No newline at end of file
+pub use action_history_plugin::*;
+pub use audio_plugin::*;
+pub use controls_plugin::*;
+pub use credits_plugin::*;
+pub use cursor_plugin::*;
+pub use diagnostics_plugin::*;
+pub use disaster_plugin::*;
+pub use economy_plugin::*;
+pub use event_log_plugin::*;
+pub use game_over_plugin::*;
+pub use health_plugin::*;
+pub use help_plugin::*;
+pub use hud_plugin::*;
+pub use lighting_plugin::*;
+pub use loading_plugin::*;
+pub use menu_plugin::*;
+pub use minimap_plugin::*;
+pub use miracle_plugin::*;
+pub use notifications_plugin::*;
+pub use pause_plugin::*;
+pub use placement_plugin::*;
+pub use population_plugin::*;
+pub use regeneration_plugin::*;
+pub use save_plugin::*;
+pub use saves_plugin::*;
+pub use screenshot_plugin::*;
+pub use selection_plugin::*;
+pub use settings_plugin::*;
+pub use simulation_plugin::*;
+pub use statistics_plugin::*;
+pub use terrain_brush_plugin::*;
+pub use ui_scale_plugin::*;
+pub use weather_plugin::*;
+pub use widgets_plugin::*;
\ No newline at end of file
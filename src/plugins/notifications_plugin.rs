@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use crate::events::Notification;
+use crate::systems::notifications::*;
+
+/// Plugin for short on-screen messages that any system can show by sending a `Notification`
+pub struct NotificationsPlugin;
+
+impl Plugin for NotificationsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<Notification>()
+            .add_systems(Startup, setup_notification_stack)
+            .add_systems(Update, (spawn_notifications, expire_notifications).chain());
+    }
+}
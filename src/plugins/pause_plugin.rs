@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::close_confirm_dialog_on_choice;
+use crate::resources::GameState;
+use crate::systems::menu::handle_escape;
+use crate::systems::pause::*;
+
+/// Plugin for pausing gameplay and showing the pause menu
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Runs after Escape handling so a pause key press and focus loss in one frame still pause
+            .add_systems(
+                Update,
+                pause_on_focus_loss.after(handle_escape).run_if(in_state(GameState::InGame))
+            )
+            
+            .add_systems(
+                Update,
+                handle_save_as
+                    .before(close_confirm_dialog_on_choice)
+                    .run_if(in_state(GameState::Paused))
+            )
+            
+            .add_systems(OnEnter(GameState::Paused), setup_pause_menu)
+            .add_systems(OnExit(GameState::Paused), cleanup_pause_menu);
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::no_modal_open;
+use crate::resources::{GameState, PlacedAssets, SelectedTool, ShowGrid, TerrainRules};
+use crate::systems::grid::*;
+use crate::systems::placement::*;
+
+/// Plugin for placing and deleting entities on the map from the in-game toolbar, and the
+/// tile grid and terrain patterns drawn over the map
+pub struct PlacementPlugin;
+
+impl Plugin for PlacementPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SelectedTool>()
+            .init_resource::<PlacedAssets>()
+            .init_resource::<ShowGrid>()
+            .init_resource::<TerrainRules>()
+            .add_systems(OnEnter(GameState::InGame), setup_entity_toolbar)
+            .add_systems(
+                Update,
+                (
+                    handle_toolbar_buttons,
+                    update_toolbar_colors,
+                    place_entity.run_if(no_modal_open),
+                    delete_entity.run_if(no_modal_open),
+                    draw_delete_cursor,
+                ).chain().run_if(in_state(GameState::InGame))
+            )
+            .add_systems(
+                Update,
+                (toggle_grid, draw_grid, draw_terrain_patterns).chain().run_if(in_state(GameState::InGame))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_entity_toolbar);
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, Population, WorldReset};
+use crate::systems::population::*;
+use crate::systems::simulation::advance_simulation_clock;
+
+/// Plugin for the world's population and its daily growth
+pub struct PopulationPlugin;
+
+impl Plugin for PopulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Population>()
+            .add_systems(Update, reset_population.in_set(WorldReset))
+            .add_systems(
+                FixedUpdate,
+                update_population
+                    .after(advance_simulation_clock)
+                    .run_if(in_state(GameState::InGame))
+            );
+    }
+}
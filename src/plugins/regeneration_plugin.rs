@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::{close_confirm_dialog_on_choice, no_modal_open};
+use crate::events::RegenerateWorld;
+use crate::resources::{GameState, WorldReset};
+use crate::systems::menu::handle_menu_actions;
+use crate::systems::regeneration::*;
+use crate::systems::world_gen::{generate_world_step, start_world_generation, WorldGenerator};
+
+/// Plugin for regenerating the world in place with Ctrl+R, for sandbox play and debugging
+pub struct RegenerationPlugin;
+
+impl Plugin for RegenerationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<RegenerateWorld>()
+            .add_systems(
+                Update,
+                (
+                    regenerate_world_hotkey.run_if(no_modal_open),
+                    handle_regeneration_actions
+                        .after(handle_menu_actions)
+                        .before(close_confirm_dialog_on_choice),
+                ).run_if(in_state(GameState::InGame))
+            )
+
+            // Generation starts as it does on the loading screen once the new seed is taken, and
+            // the same `WorldReset` systems run after it
+            .add_systems(
+                Update,
+                (begin_world_regeneration, start_world_generation)
+                    .chain()
+                    .after(regenerate_world_hotkey)
+                    .after(handle_regeneration_actions)
+                    .before(WorldReset)
+                    .run_if(in_state(GameState::InGame).and_then(on_event::<RegenerateWorld>()))
+            )
+
+            // Tiles are spawned a budget per frame while the game goes on, as on the loading screen
+            .add_systems(
+                Update,
+                (generate_world_step, finish_world_regeneration)
+                    .chain()
+                    .run_if(in_state(GameState::InGame).and_then(resource_exists::<WorldGenerator>))
+            );
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use crate::events::{LoadGameRequested, SaveGameRequested};
+use crate::resources::{GameState, WorldReset};
+use crate::save::UnsavedChanges;
+use crate::systems::autosave::*;
+use crate::systems::save_load::*;
+
+/// Plugin for saving and loading games from disk, including autosaves
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<SaveGameRequested>()
+            .add_event::<LoadGameRequested>()
+            .init_resource::<Autosave>()
+            .init_resource::<UnsavedChanges>()
+            .add_systems(Update, (handle_save_requests, handle_load_requests))
+            .add_systems(Update, reset_autosave.in_set(WorldReset))
+            // A world regenerated in place keeps its unsaved mark, see `begin_world_regeneration`
+            .add_systems(OnEnter(GameState::Loading), reset_unsaved_changes)
+            .add_systems(OnEnter(GameState::MainMenu), discard_pending_load)
+            // Autosaves are only written while the game is running, never from the pause menu
+            .add_systems(Update, tick_autosave.run_if(in_state(GameState::InGame)))
+            .add_systems(Update, poll_autosave);
+    }
+}
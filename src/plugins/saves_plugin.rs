@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::close_confirm_dialog_on_choice;
+use crate::resources::GameState;
+use crate::save::SaveSlots;
+use crate::systems::menu::*;
+use crate::systems::saves::*;
+
+/// Plugin for the saves screen, where save files are loaded, renamed and deleted
+pub struct SavesPlugin;
+
+impl Plugin for SavesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SaveSlots>()
+            .add_systems(OnEnter(GameState::Saves), (refresh_save_slots, setup_saves_screen))
+            .add_systems(
+                Update,
+                (
+                    (
+                        handle_saves_actions
+                            .after(handle_menu_actions)
+                            .before(close_confirm_dialog_on_choice),
+                        handle_save_name_submit,
+                    ),
+                    update_save_list.run_if(resource_changed::<SaveSlots>),
+                ).chain().run_if(in_state(GameState::Saves))
+            )
+            .add_systems(OnExit(GameState::Saves), cleanup_saves_screen);
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use crate::resources::Screenshots;
+use crate::systems::screenshot::*;
+
+/// Plugin for the F12 screenshot hotkey, available on every screen
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Screenshots>()
+            .add_systems(Update, (take_screenshot, report_screenshots).chain());
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::no_modal_open;
+use crate::resources::{GameState, HoveredTile, SelectedTile, Selection, SelectionDrag, WorldReset};
+use crate::systems::inspector::*;
+use crate::systems::menu::handle_escape;
+use crate::systems::selection::*;
+
+/// Plugin for selecting tiles and placed entities on the map, highlighting the selection and
+/// inspecting it
+pub struct SelectionPlugin;
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SelectedTile>()
+            .init_resource::<HoveredTile>()
+            .init_resource::<Selection>()
+            .init_resource::<SelectionDrag>()
+            .add_systems(Update, reset_selection.in_set(WorldReset))
+            .add_systems(OnEnter(GameState::InGame), setup_inspector_panel)
+            .add_systems(
+                Update,
+                (
+                    (hover_tile, draw_hover_highlight).chain(),
+                    (
+                        start_selection_drag.run_if(no_modal_open),
+                        select_tile.run_if(no_modal_open),
+                        draw_selection_box,
+                    ).chain(),
+                    // Escape closes an open dialog rather than clearing the selection
+                    clear_selection_on_escape.before(handle_escape).run_if(no_modal_open),
+                    update_inspector_panel
+                        .after(select_tile)
+                        .after(clear_selection_on_escape),
+                ).run_if(in_state(GameState::InGame))
+            )
+            // The highlight stays visible behind the pause menu
+            .add_systems(
+                Update,
+                draw_selection_highlight
+                    .run_if(in_state(GameState::InGame).or_else(in_state(GameState::Paused)))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_inspector_panel);
+    }
+}
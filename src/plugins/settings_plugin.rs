@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use crate::components::confirm_dialog::close_confirm_dialog_on_choice;
+use crate::components::slider::slider_interaction_system;
+use crate::components::ui::MenuTheme;
+use crate::i18n::Locale;
+use crate::resources::{GameState, Settings};
+use crate::systems::menu::*;
+use crate::systems::settings::*;
+
+/// Plugin for the settings screen and the `Settings` resource
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let settings = load_startup_settings();
+        app
+            // Screens spawned on the first frame already use the saved theme and language
+            .insert_resource(settings.menu_theme())
+            .insert_resource(settings.terrain_palette())
+            .insert_resource(Locale::load(settings.language))
+            .insert_resource(settings)
+            
+            // Applies on launch, since the resource counts as changed on the first frame
+            .add_systems(
+                Update,
+                (
+                    (apply_window_mode, apply_resolution, apply_present_mode, apply_theme, apply_language)
+                        .run_if(resource_changed::<Settings>),
+                    recolor_themed_ui.run_if(resource_changed::<MenuTheme>),
+                ).chain()
+            )
+            
+            // Runs last so the wait covers the whole frame's work
+            .add_systems(Last, limit_frame_rate)
+            
+            // Changes are written at most once a second, so slider drags don't write every frame
+            .add_systems(
+                Update,
+                persist_settings.run_if(on_timer(Duration::from_secs(1)).and_then(resource_changed::<Settings>))
+            )
+            
+            // Systems that run when entering Settings state
+            .add_systems(OnEnter(GameState::Settings), setup_settings_menu)
+            
+            // Systems that run while in Settings state
+            .add_systems(
+                Update,
+                (
+                    handle_settings_actions
+                        .after(handle_menu_actions)
+                        .before(close_confirm_dialog_on_choice),
+                    handle_settings_sliders.after(slider_interaction_system),
+                    update_settings_text.run_if(resource_changed::<Settings>),
+                ).run_if(in_state(GameState::Settings))
+            )
+            
+            // Systems that run when exiting Settings state
+            .add_systems(OnExit(GameState::Settings), (cleanup_settings_menu, persist_settings));
+    }
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, Rng, SimulationClock, WorldReset};
+use crate::systems::simulation::*;
+
+/// Plugin for the in-game simulation clock and gameplay RNG
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SimulationClock>()
+            .init_resource::<Rng>()
+            .insert_resource(Time::<Fixed>::from_hz(SimulationClock::TICKS_PER_SECOND))
+            .add_systems(Update, reset_rng.in_set(WorldReset))
+            .add_systems(
+                Update,
+                handle_simulation_speed_input.run_if(in_state(GameState::InGame))
+            )
+            // The clock only advances in InGame, so it stops while Paused. Simulation systems
+            // run after it in `FixedUpdate`, which runs before `Update` in each frame.
+            .add_systems(
+                FixedUpdate,
+                advance_simulation_clock.run_if(in_state(GameState::InGame))
+            );
+    }
+}
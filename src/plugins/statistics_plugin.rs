@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, StatisticsHistory, WorldReset};
+use crate::systems::economy::update_economy;
+use crate::systems::statistics::*;
+
+/// Plugin for the history of population, food and mana, and the panel graphing it
+pub struct StatisticsPlugin;
+
+impl Plugin for StatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<StatisticsHistory>()
+            .add_systems(Update, reset_statistics.in_set(WorldReset))
+            .add_systems(OnEnter(GameState::InGame), setup_statistics_panel)
+            // Samples after the daily economy update so each day's figures are settled
+            .add_systems(
+                FixedUpdate,
+                record_statistics
+                    .after(update_economy)
+                    .run_if(in_state(GameState::InGame))
+            )
+            .add_systems(
+                Update,
+                (
+                    (toggle_statistics_panel, update_statistics_toggle_colors).chain(),
+                    update_statistics_graphs.run_if(resource_changed::<StatisticsHistory>),
+                ).run_if(in_state(GameState::InGame))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_statistics_panel);
+    }
+}
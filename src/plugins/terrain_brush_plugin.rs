@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::no_modal_open;
+use crate::resources::{GameState, TerrainBrush, WorldReset};
+use crate::systems::camera::zoom_camera;
+use crate::systems::terrain_brush::*;
+
+/// Plugin for reshaping the land with a resizable terrain brush
+pub struct TerrainBrushPlugin;
+
+impl Plugin for TerrainBrushPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TerrainBrush>()
+            .add_systems(Update, reset_terrain_brush.in_set(WorldReset))
+            .add_systems(OnEnter(GameState::InGame), setup_terrain_toolbar)
+            .add_systems(
+                Update,
+                (
+                    handle_terrain_tool_buttons,
+                    update_terrain_tool_colors,
+                    // The wheel resizes the brush instead of zooming while a tool is selected
+                    resize_brush.before(zoom_camera),
+                    paint_terrain.run_if(no_modal_open),
+                    draw_brush_preview,
+                ).chain().run_if(in_state(GameState::InGame))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_terrain_toolbar);
+    }
+}
@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+use crate::systems::ui_scale::*;
+
+/// Plugin that scales the UI with the window size
+///
+/// Named to avoid confusion with Bevy's `UiScale` resource, which it drives.
+pub struct UiScalingPlugin;
+
+impl Plugin for UiScalingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_ui_scale);
+    }
+}
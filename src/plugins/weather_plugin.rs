@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use crate::resources::{GameState, Weather, WeatherConfig, WeatherTimer, WorldReset};
+use crate::systems::simulation::advance_simulation_clock;
+use crate::systems::weather::*;
+
+/// Plugin for weather that changes over in-game time and reshapes the coastline
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Weather>()
+            .init_resource::<WeatherConfig>()
+            .init_resource::<WeatherTimer>()
+            .add_systems(Update, reset_weather.in_set(WorldReset))
+            .add_systems(OnEnter(GameState::InGame), setup_weather_overlay)
+            .add_systems(
+                FixedUpdate,
+                (
+                    update_weather,
+                    apply_weather_to_terrain,
+                ).chain().after(advance_simulation_clock).run_if(in_state(GameState::InGame))
+            )
+            .add_systems(
+                Update,
+                update_weather_overlay
+                    .run_if(in_state(GameState::InGame).and_then(resource_changed::<Weather>))
+            )
+            .add_systems(OnExit(GameState::InGame), cleanup_weather_overlay);
+    }
+}
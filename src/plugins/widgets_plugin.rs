@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use crate::components::confirm_dialog::*;
+use crate::components::input_field::*;
+use crate::components::slider::*;
+use crate::systems::menu::{handle_escape, handle_menu_actions};
+use crate::systems::tooltip::tooltip_system;
+
+/// Plugin for reusable UI widgets shared by several screens
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<TextInputSubmitted>()
+            .add_event::<SliderChanged>()
+            .add_systems(
+                Update,
+                (
+                    (text_input_system, update_text_input_display).chain(),
+                    (slider_interaction_system, update_slider_display).chain(),
+                    close_confirm_dialog_on_choice.after(handle_menu_actions),
+                    close_confirm_dialog_on_escape.before(handle_escape),
+                    tooltip_system,
+                )
+            );
+    }
+}
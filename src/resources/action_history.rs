@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use crate::components::placeable::EntityKind;
+use crate::components::tile::Terrain;
+
+/// Terrain of one tile before and after an edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileChange {
+    pub coord: UVec2,
+    pub before: Terrain,
+    pub after: Terrain,
+}
+
+/// A reversible change the player made to the world
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorldEdit {
+    /// A terrain brush stroke, with the mana it cost
+    Terrain {
+        changes: Vec<TileChange>,
+        mana_spent: f32,
+    },
+    /// An entity placed on a tile that was empty before
+    Place { kind: EntityKind, coord: UVec2 },
+    /// A placed entity removed with the delete tool
+    Delete { kind: EntityKind, coord: UVec2 },
+}
+
+impl WorldEdit {
+    /// Mana refunded when the edit is undone and charged again when it is redone
+    pub fn mana_cost(&self) -> f32 {
+        match self {
+            WorldEdit::Terrain { mana_spent, .. } => *mana_spent,
+            WorldEdit::Place { .. } | WorldEdit::Delete { .. } => 0.0,
+        }
+    }
+}
+
+/// Recent world edits that can be undone, and undone edits that can be redone
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ActionHistory {
+    /// Edits in the order they were made, most recent last
+    done: VecDeque<WorldEdit>,
+    /// Undone edits, most recently undone last
+    undone: Vec<WorldEdit>,
+}
+
+impl ActionHistory {
+    /// Edits kept before the oldest is forgotten
+    pub const MAX_EDITS: usize = 50;
+
+    /// Record a new edit, which forgets anything that could have been redone
+    pub fn record(&mut self, edit: WorldEdit) {
+        self.undone.clear();
+        if self.done.len() == Self::MAX_EDITS {
+            self.done.pop_front();
+        }
+        self.done.push_back(edit);
+    }
+
+    /// Move the most recent edit to the redo stack, returning it so it can be reverted
+    pub fn undo(&mut self) -> Option<&WorldEdit> {
+        let edit = self.done.pop_back()?;
+        self.undone.push(edit);
+        self.undone.last()
+    }
+
+    /// The edit the next redo would reapply
+    pub fn next_redo(&self) -> Option<&WorldEdit> {
+        self.undone.last()
+    }
+
+    /// Move the most recently undone edit back to the history, returning it so it can be reapplied
+    pub fn redo(&mut self) -> Option<&WorldEdit> {
+        let edit = self.undone.pop()?;
+        self.done.push_back(edit);
+        self.done.back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(x: u32) -> WorldEdit {
+        WorldEdit::Place {
+            kind: EntityKind::Tree,
+            coord: UVec2::new(x, 0),
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let mut history = ActionHistory::default();
+        history.record(place(1));
+        history.record(place(2));
+
+        assert_eq!(history.undo(), Some(&place(2)));
+        assert_eq!(history.undo(), Some(&place(1)));
+        assert_eq!(history.undo(), None);
+
+        assert_eq!(history.next_redo(), Some(&place(1)));
+        assert_eq!(history.redo(), Some(&place(1)));
+        assert_eq!(history.redo(), Some(&place(2)));
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn recording_forgets_undone_edits() {
+        let mut history = ActionHistory::default();
+        history.record(place(1));
+        history.undo();
+        history.record(place(2));
+
+        assert_eq!(history.next_redo(), None);
+        assert_eq!(history.undo(), Some(&place(2)));
+        assert_eq!(history.undo(), None);
+    }
+
+    #[test]
+    fn oldest_edits_are_forgotten() {
+        let mut history = ActionHistory::default();
+        for x in 0..ActionHistory::MAX_EDITS as u32 + 1 {
+            history.record(place(x));
+        }
+
+        let mut undone = 0;
+        while history.undo().is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, ActionHistory::MAX_EDITS);
+        assert_eq!(history.next_redo(), Some(&place(1)));
+    }
+
+    #[test]
+    fn only_terrain_edits_cost_mana() {
+        let stroke = WorldEdit::Terrain {
+            changes: Vec::new(),
+            mana_spent: 3.0,
+        };
+        assert_eq!(stroke.mana_cost(), 3.0);
+        assert_eq!(place(0).mana_cost(), 0.0);
+    }
+}
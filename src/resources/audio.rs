@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use crate::resources::GameState;
+
+/// Background music tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MusicTrack {
+    Menu,
+    Ambient,
+}
+
+impl MusicTrack {
+    /// Track that should play in a game state, or `None` to keep whatever is playing
+    ///
+    /// Settings (and the controls screen under it) can be opened from both the main menu
+    /// and the pause menu, so they keep the current track instead of switching.
+    pub fn for_state(state: &GameState) -> Option<Self> {
+        match state {
+            GameState::MainMenu
+            | GameState::NewWorldkeeper
+            | GameState::Saves
+            | GameState::GameOver
+            | GameState::Credits => Some(MusicTrack::Menu),
+            GameState::InGame | GameState::Paused => Some(MusicTrack::Ambient),
+            GameState::Settings | GameState::Controls | GameState::Loading => None,
+        }
+    }
+}
+
+/// One-shot UI sound effects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEffect {
+    Click,
+    Hover,
+    /// An action the player tried isn't allowed
+    Reject,
+}
+
+/// Handles to the music and sound effect files, loaded once at startup
+#[derive(Resource, Debug, Clone)]
+pub struct AudioAssets {
+    pub menu_music: Handle<AudioSource>,
+    pub ambient_music: Handle<AudioSource>,
+    pub click: Handle<AudioSource>,
+    pub hover: Handle<AudioSource>,
+    pub reject: Handle<AudioSource>,
+}
+
+impl AudioAssets {
+    pub const MENU_MUSIC_PATH: &'static str = "audio/menu_music.ogg";
+    pub const AMBIENT_MUSIC_PATH: &'static str = "audio/ambient_music.ogg";
+    pub const CLICK_PATH: &'static str = "audio/click.ogg";
+    pub const HOVER_PATH: &'static str = "audio/hover.ogg";
+    pub const REJECT_PATH: &'static str = "audio/reject.ogg";
+
+    pub fn music(&self, track: MusicTrack) -> Handle<AudioSource> {
+        match track {
+            MusicTrack::Menu => self.menu_music.clone(),
+            MusicTrack::Ambient => self.ambient_music.clone(),
+        }
+    }
+
+    pub fn sound(&self, effect: SoundEffect) -> Handle<AudioSource> {
+        match effect {
+            SoundEffect::Click => self.click.clone(),
+            SoundEffect::Hover => self.hover.clone(),
+            SoundEffect::Reject => self.reject.clone(),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::{Mutex, PoisonError};
+
+use bevy::prelude::*;
+
+/// Connection to the system clipboard, kept open for the whole session
+///
+/// On Linux, copied text is served by the `arboard::Clipboard` that set it and disappears
+/// once that is dropped, so it can't be opened just for each copy. It is opened on first use
+/// since headless sessions have no clipboard at all.
+#[derive(Resource, Default)]
+pub struct SystemClipboard {
+    /// Only behind a `Mutex` so the resource is `Sync`; systems reach it through `ResMut`
+    clipboard: Mutex<Option<arboard::Clipboard>>,
+}
+
+impl SystemClipboard {
+    /// Write text to the system clipboard
+    ///
+    /// Fails when no clipboard is available, so callers should report the error rather than
+    /// unwrap it.
+    pub fn set_text(&mut self, text: &str) -> Result<(), arboard::Error> {
+        self.open()?.set_text(text)
+    }
+
+    /// Read text from the system clipboard
+    pub fn get_text(&mut self) -> Result<String, arboard::Error> {
+        self.open()?.get_text()
+    }
+
+    /// The open clipboard, opening it if this is the first use or opening failed before
+    fn open(&mut self) -> Result<&mut arboard::Clipboard, arboard::Error> {
+        let slot = self.clipboard.get_mut().unwrap_or_else(PoisonError::into_inner);
+        let clipboard = match slot.take() {
+            Some(clipboard) => clipboard,
+            None => arboard::Clipboard::new()?,
+        };
+        Ok(slot.insert(clipboard))
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+/// Lines shown on the credits screen, top to bottom
+///
+/// Empty strings leave a blank line between sections.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Credits(pub Vec<String>);
+
+impl Default for Credits {
+    fn default() -> Self {
+        Self(
+            [
+                "WorldKeeper",
+                "",
+                "Design and Programming",
+                "Paul-Michael-H",
+                "",
+                "Built with",
+                "Bevy Engine",
+                "noise-rs",
+                "",
+                "Thanks for playing!",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        )
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use crate::components::EntityCategory;
+use crate::resources::Weather;
+
+/// Tunable rates at which placed entities lose health, in health per in-game day
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DecayConfig {
+    /// In-game hours between health updates
+    pub interval_hours: f64,
+    /// Lost while standing on terrain `TerrainRules` wouldn't allow the entity on, such as
+    /// sand that has flooded or ground broken by an earthquake
+    pub hostile_terrain: f32,
+    /// Lost by every entity during a storm
+    pub storm: f32,
+    /// Lost by plants during a drought; wildlife loses half as much, and settlers draw on
+    /// the water stockpile instead
+    pub drought: f32,
+}
+
+impl DecayConfig {
+    /// Health an entity of a category loses per in-game day
+    pub fn damage_per_day(&self, category: EntityCategory, weather: Weather, hostile_terrain: bool) -> f32 {
+        let terrain_damage = if hostile_terrain { self.hostile_terrain } else { 0.0 };
+        let weather_damage = match (weather, category) {
+            (Weather::Storm, _) => self.storm,
+            (Weather::Drought, EntityCategory::Plant) => self.drought,
+            (Weather::Drought, EntityCategory::Wildlife) => self.drought * 0.5,
+            _ => 0.0,
+        };
+        terrain_damage + weather_damage
+    }
+}
+
+impl Default for DecayConfig {
+    fn default() -> Self {
+        Self {
+            interval_hours: 1.0,
+            hostile_terrain: 50.0,
+            storm: 15.0,
+            drought: 20.0,
+        }
+    }
+}
+
+/// In-game hour at which placed entities next lose health
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct DecayTimer {
+    pub next_decay_hour: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_decays_in_fair_weather_on_good_ground() {
+        let config = DecayConfig::default();
+        for category in [EntityCategory::Plant, EntityCategory::Wildlife, EntityCategory::Settler] {
+            assert_eq!(config.damage_per_day(category, Weather::Clear, false), 0.0);
+            assert_eq!(config.damage_per_day(category, Weather::Rain, false), 0.0);
+        }
+    }
+
+    #[test]
+    fn storms_hit_everything_and_droughts_hit_plants_hardest() {
+        let config = DecayConfig::default();
+
+        for category in [EntityCategory::Plant, EntityCategory::Wildlife, EntityCategory::Settler] {
+            assert_eq!(config.damage_per_day(category, Weather::Storm, false), config.storm);
+        }
+        assert_eq!(config.damage_per_day(EntityCategory::Plant, Weather::Drought, false), config.drought);
+        assert_eq!(
+            config.damage_per_day(EntityCategory::Wildlife, Weather::Drought, false),
+            config.drought * 0.5
+        );
+        assert_eq!(config.damage_per_day(EntityCategory::Settler, Weather::Drought, false), 0.0);
+    }
+
+    #[test]
+    fn hostile_terrain_adds_to_weather_damage() {
+        let config = DecayConfig::default();
+
+        assert_eq!(
+            config.damage_per_day(EntityCategory::Settler, Weather::Clear, true),
+            config.hostile_terrain
+        );
+        assert_eq!(
+            config.damage_per_day(EntityCategory::Plant, Weather::Storm, true),
+            config.hostile_terrain + config.storm
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+/// Whether the FPS and diagnostics overlay is shown
+///
+/// Kept as a resource so the choice survives state changes.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+impl DebugOverlay {
+    /// Key that shows and hides the overlay
+    pub const TOGGLE_KEY: KeyCode = KeyCode::F3;
+}
@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use crate::components::tile::{Biome, Terrain};
+use crate::resources::Weather;
+
+/// Disasters that strike the world, at random or cast by the player as miracles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Disaster {
+    Wildfire,
+    Flood,
+    Earthquake,
+}
+
+impl Disaster {
+    /// Every disaster, in the order they are rolled for
+    pub const ALL: [Disaster; 3] = [Disaster::Wildfire, Disaster::Flood, Disaster::Earthquake];
+
+    /// Display name of the disaster
+    pub fn name(self) -> &'static str {
+        match self {
+            Disaster::Wildfire => "Wildfire",
+            Disaster::Flood => "Flood",
+            Disaster::Earthquake => "Earthquake",
+        }
+    }
+}
+
+/// Tunable disaster behaviour
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DisasterConfig {
+    /// In-game hours between chances for a disaster to strike
+    pub check_interval_hours: f64,
+    /// Chance of a wildfire breaking out at each check in clear weather, tripled in a drought
+    /// and ruled out by rain
+    pub wildfire_chance: f64,
+    /// Chance of a flood at each check in the rain, tripled in a storm and ruled out in dry
+    /// weather
+    pub flood_chance: f64,
+    /// Chance of an earthquake at each check, whatever the weather
+    pub earthquake_chance: f64,
+    /// In-game hours between steps of a spreading wildfire
+    pub wildfire_step_hours: f64,
+    /// Chance for a burning tile to set each flammable neighbor alight per step
+    pub wildfire_spread_chance: f64,
+    /// In-game hours a tile burns before it goes out
+    pub wildfire_burn_hours: f64,
+    /// Health lost per step by entities standing in the fire
+    pub wildfire_damage: f32,
+    /// Tiles from the shore a flood can reach
+    pub flood_radius: i32,
+    /// In-game hours before flooded tiles dry out again
+    pub flood_hours: f64,
+    /// Tiles from the epicenter an earthquake can reach
+    pub earthquake_radius: i32,
+    /// Chance for each tile within reach to be changed by an earthquake
+    pub earthquake_tile_chance: f64,
+    /// Health lost by entities within reach of an earthquake
+    pub earthquake_damage: f32,
+}
+
+impl DisasterConfig {
+    /// Chance of a disaster striking at a check in the given weather
+    pub fn chance(&self, disaster: Disaster, weather: Weather) -> f64 {
+        match (disaster, weather) {
+            (Disaster::Wildfire, Weather::Clear) => self.wildfire_chance,
+            (Disaster::Wildfire, Weather::Drought) => self.wildfire_chance * 3.0,
+            (Disaster::Wildfire, _) => 0.0,
+            (Disaster::Flood, Weather::Rain) => self.flood_chance,
+            (Disaster::Flood, Weather::Storm) => self.flood_chance * 3.0,
+            (Disaster::Flood, _) => 0.0,
+            (Disaster::Earthquake, _) => self.earthquake_chance,
+        }
+    }
+}
+
+impl Default for DisasterConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_hours: 24.0,
+            wildfire_chance: 0.03,
+            flood_chance: 0.05,
+            earthquake_chance: 0.01,
+            wildfire_step_hours: 1.0,
+            wildfire_spread_chance: 0.35,
+            wildfire_burn_hours: 4.0,
+            wildfire_damage: 30.0,
+            flood_radius: 4,
+            flood_hours: 48.0,
+            earthquake_radius: 3,
+            earthquake_tile_chance: 0.5,
+            earthquake_damage: 60.0,
+        }
+    }
+}
+
+/// Whether fire can spread onto a tile
+///
+/// Only grass burns, and only where it grows thick enough: forests and grassland catch, while
+/// tundra, desert and swamp don't.
+pub fn is_flammable(terrain: Terrain, biome: Biome) -> bool {
+    terrain == Terrain::Grass && matches!(biome, Biome::Forest | Biome::Grassland)
+}
+
+/// A tile on fire, and the in-game hour it burns out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BurningTile {
+    pub coord: UVec2,
+    pub burns_out_hour: f64,
+}
+
+/// A tile under a flood, with the terrain it returns to when the water recedes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloodedTile {
+    pub coord: UVec2,
+    pub terrain: Terrain,
+    pub recedes_hour: f64,
+}
+
+/// Disasters still playing out across the world
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ActiveDisasters {
+    pub burning: Vec<BurningTile>,
+    /// Tiles that have burned in the current fires, which can't catch again until every fire
+    /// is out
+    pub burnt: HashSet<UVec2>,
+    pub flooded: Vec<FloodedTile>,
+}
+
+/// In-game hours at which disasters are next rolled for and next spread
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct DisasterTimer {
+    pub next_check_hour: f64,
+    pub next_step_hour: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weather_shifts_the_odds_of_fires_and_floods() {
+        let config = DisasterConfig::default();
+
+        assert_eq!(config.chance(Disaster::Wildfire, Weather::Clear), config.wildfire_chance);
+        assert_eq!(config.chance(Disaster::Wildfire, Weather::Drought), config.wildfire_chance * 3.0);
+        assert_eq!(config.chance(Disaster::Wildfire, Weather::Rain), 0.0);
+        assert_eq!(config.chance(Disaster::Wildfire, Weather::Storm), 0.0);
+
+        assert_eq!(config.chance(Disaster::Flood, Weather::Rain), config.flood_chance);
+        assert_eq!(config.chance(Disaster::Flood, Weather::Storm), config.flood_chance * 3.0);
+        assert_eq!(config.chance(Disaster::Flood, Weather::Clear), 0.0);
+        assert_eq!(config.chance(Disaster::Flood, Weather::Drought), 0.0);
+
+        for weather in Weather::ALL {
+            assert_eq!(config.chance(Disaster::Earthquake, weather), config.earthquake_chance);
+        }
+    }
+
+    #[test]
+    fn only_thick_grass_burns() {
+        for biome in Biome::ALL {
+            assert_eq!(
+                is_flammable(Terrain::Grass, biome),
+                matches!(biome, Biome::Forest | Biome::Grassland),
+                "{:?}",
+                biome
+            );
+        }
+        for terrain in [Terrain::Water, Terrain::River, Terrain::Sand, Terrain::Mountain] {
+            assert!(Biome::ALL.into_iter().all(|biome| !is_flammable(terrain, biome)));
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use crate::resources::{Disaster, InputAction};
+
+/// Powers the player can cast on the world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Miracle {
+    Rain,
+    Sunshine,
+    Earthquake,
+    Wildfire,
+    Flood,
+}
+
+impl Miracle {
+    /// Every miracle, in toolbar order
+    pub const ALL: [Miracle; 5] = [
+        Miracle::Rain,
+        Miracle::Sunshine,
+        Miracle::Earthquake,
+        Miracle::Wildfire,
+        Miracle::Flood,
+    ];
+
+    /// Display name shown on the toolbar
+    pub fn name(self) -> &'static str {
+        match self {
+            Miracle::Rain => "Rain",
+            Miracle::Sunshine => "Sunshine",
+            Miracle::Earthquake => "Earthquake",
+            Miracle::Wildfire => "Wildfire",
+            Miracle::Flood => "Flood",
+        }
+    }
+
+    /// Mana spent to cast the miracle
+    pub fn cost(self) -> f32 {
+        match self {
+            Miracle::Rain => 30.0,
+            Miracle::Sunshine => 30.0,
+            Miracle::Earthquake => 80.0,
+            Miracle::Wildfire => 60.0,
+            Miracle::Flood => 60.0,
+        }
+    }
+
+    /// In-game hours before the miracle can be cast again
+    pub fn cooldown_hours(self) -> f64 {
+        match self {
+            Miracle::Rain => 12.0,
+            Miracle::Sunshine => 12.0,
+            Miracle::Earthquake => 48.0,
+            Miracle::Wildfire => 36.0,
+            Miracle::Flood => 36.0,
+        }
+    }
+
+    /// Key binding that casts the miracle
+    pub fn input_action(self) -> InputAction {
+        match self {
+            Miracle::Rain => InputAction::CastRain,
+            Miracle::Sunshine => InputAction::CastSunshine,
+            Miracle::Earthquake => InputAction::CastEarthquake,
+            Miracle::Wildfire => InputAction::CastWildfire,
+            Miracle::Flood => InputAction::CastFlood,
+        }
+    }
+
+    /// Disaster the miracle brings down on the world, if it is one
+    pub fn disaster(self) -> Option<Disaster> {
+        match self {
+            Miracle::Rain | Miracle::Sunshine => None,
+            Miracle::Earthquake => Some(Disaster::Earthquake),
+            Miracle::Wildfire => Some(Disaster::Wildfire),
+            Miracle::Flood => Some(Disaster::Flood),
+        }
+    }
+}
+
+/// Why a miracle couldn't be cast
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastError {
+    NotEnoughMana,
+    /// The miracle is still recovering from its last cast
+    CoolingDown { remaining_hours: f64 },
+}
+
+/// The player's mana pool, regenerating over in-game time, and each miracle's cooldown
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DivinePower {
+    pub mana: f32,
+    /// Set in sandbox mode, where every power is free and miracles have no cooldown
+    pub unlimited: bool,
+    /// In-game hour the pool was last regenerated at
+    pub last_update_hour: f64,
+    /// In-game hour at which each miracle can next be cast
+    ready_at: HashMap<Miracle, f64>,
+}
+
+impl DivinePower {
+    pub const MAX_MANA: f32 = 100.0;
+    /// Mana regained per in-game hour
+    pub const REGEN_PER_HOUR: f32 = 2.0;
+
+    /// Regain mana for the in-game time passed since the last update
+    pub fn regenerate(&mut self, now_hours: f64) {
+        let hours = (now_hours - self.last_update_hour).max(0.0) as f32;
+        self.mana = (self.mana + hours * Self::REGEN_PER_HOUR).min(Self::MAX_MANA);
+        self.last_update_hour = now_hours;
+    }
+
+    /// Fraction of the pool that is full, in the range 0.0..=1.0
+    pub fn fraction(&self) -> f32 {
+        if self.unlimited {
+            return 1.0;
+        }
+        (self.mana / Self::MAX_MANA).clamp(0.0, 1.0)
+    }
+
+    /// In-game hours until a miracle is off cooldown, or zero when it is ready
+    pub fn cooldown_remaining(&self, miracle: Miracle, now_hours: f64) -> f64 {
+        self.ready_at
+            .get(&miracle)
+            .map_or(0.0, |ready_at| (ready_at - now_hours).max(0.0))
+    }
+
+    /// Whether a miracle can be cast right now
+    pub fn can_cast(&self, miracle: Miracle, now_hours: f64) -> bool {
+        self.unlimited
+            || (self.mana >= miracle.cost() && self.cooldown_remaining(miracle, now_hours) <= 0.0)
+    }
+
+    /// Spend the mana for a miracle and start its cooldown, if it can be cast
+    pub fn try_cast(&mut self, miracle: Miracle, now_hours: f64) -> Result<(), CastError> {
+        if self.unlimited {
+            return Ok(());
+        }
+        let remaining_hours = self.cooldown_remaining(miracle, now_hours);
+        if remaining_hours > 0.0 {
+            return Err(CastError::CoolingDown { remaining_hours });
+        }
+        if self.mana < miracle.cost() {
+            return Err(CastError::NotEnoughMana);
+        }
+
+        self.mana -= miracle.cost();
+        self.ready_at.insert(miracle, now_hours + miracle.cooldown_hours());
+        Ok(())
+    }
+
+    /// Spend mana on a power without a cooldown, if there is enough
+    pub fn try_spend(&mut self, amount: f32) -> Result<(), CastError> {
+        if self.unlimited {
+            return Ok(());
+        }
+        if self.mana < amount {
+            return Err(CastError::NotEnoughMana);
+        }
+        self.mana -= amount;
+        Ok(())
+    }
+
+    /// Give back mana from an undone power, up to the size of the pool
+    pub fn refund(&mut self, amount: f32) {
+        self.mana = (self.mana + amount).min(Self::MAX_MANA);
+    }
+}
+
+impl Default for DivinePower {
+    fn default() -> Self {
+        Self {
+            mana: Self::MAX_MANA,
+            unlimited: false,
+            last_update_hour: 0.0,
+            ready_at: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casting_spends_mana_and_starts_the_cooldown() {
+        let mut power = DivinePower::default();
+
+        assert_eq!(power.try_cast(Miracle::Rain, 0.0), Ok(()));
+        assert_eq!(power.mana, DivinePower::MAX_MANA - Miracle::Rain.cost());
+        assert!(matches!(
+            power.try_cast(Miracle::Rain, 0.0),
+            Err(CastError::CoolingDown { .. })
+        ));
+    }
+
+    #[test]
+    fn unlimited_power_casts_freely_without_cooldowns() {
+        let mut power = DivinePower {
+            mana: 0.0,
+            unlimited: true,
+            ..default()
+        };
+
+        for _ in 0..3 {
+            for miracle in Miracle::ALL {
+                assert!(power.can_cast(miracle, 0.0));
+                assert_eq!(power.try_cast(miracle, 0.0), Ok(()));
+            }
+            assert_eq!(power.try_spend(DivinePower::MAX_MANA * 10.0), Ok(()));
+        }
+        assert_eq!(power.mana, 0.0);
+        assert_eq!(power.fraction(), 1.0);
+        assert_eq!(power.cooldown_remaining(Miracle::Rain, 0.0), 0.0);
+    }
+
+    #[test]
+    fn limited_power_cannot_overspend() {
+        let mut power = DivinePower {
+            mana: 5.0,
+            ..default()
+        };
+
+        assert_eq!(power.try_spend(10.0), Err(CastError::NotEnoughMana));
+        assert_eq!(power.mana, 5.0);
+        assert_eq!(power.try_spend(5.0), Ok(()));
+        assert_eq!(power.mana, 0.0);
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::{Biome, EntityKind, Terrain};
+
+/// Goods the world's people produce and live on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Food,
+    Water,
+    Wood,
+}
+
+impl ResourceKind {
+    /// Every resource kind, in HUD order
+    pub const ALL: [ResourceKind; 3] = [ResourceKind::Food, ResourceKind::Water, ResourceKind::Wood];
+
+    fn index(self) -> usize {
+        match self {
+            ResourceKind::Food => 0,
+            ResourceKind::Water => 1,
+            ResourceKind::Wood => 2,
+        }
+    }
+
+    /// Display name of the resource
+    pub fn label(self) -> &'static str {
+        match self {
+            ResourceKind::Food => "Food",
+            ResourceKind::Water => "Water",
+            ResourceKind::Wood => "Wood",
+        }
+    }
+
+    /// Key of the resource's name in the UI strings
+    pub fn key(self) -> &'static str {
+        match self {
+            ResourceKind::Food => "resource.food",
+            ResourceKind::Water => "resource.water",
+            ResourceKind::Wood => "resource.wood",
+        }
+    }
+
+    /// Color of the resource's HUD icon
+    pub fn color(self) -> Color {
+        match self {
+            ResourceKind::Food => Color::srgb(0.85, 0.55, 0.2),
+            ResourceKind::Water => Color::srgb(0.3, 0.55, 0.95),
+            ResourceKind::Wood => Color::srgb(0.55, 0.35, 0.15),
+        }
+    }
+
+    /// Amount produced per day by a tile of the given terrain
+    pub fn terrain_yield(self, terrain: Terrain) -> f32 {
+        match (self, terrain) {
+            (ResourceKind::Food, Terrain::Grass) => 0.03,
+            (ResourceKind::Water, Terrain::Water | Terrain::River) => 0.05,
+            _ => 0.0,
+        }
+    }
+
+    /// Multiplier on this resource's terrain yield in a biome
+    pub fn biome_multiplier(self, biome: Biome) -> f32 {
+        match (self, biome) {
+            (ResourceKind::Food, Biome::Grassland) => 1.25,
+            (ResourceKind::Food, Biome::Swamp) => 0.75,
+            (ResourceKind::Food, Biome::Tundra) => 0.5,
+            (ResourceKind::Food, Biome::Desert) => 0.25,
+            (ResourceKind::Water, Biome::Swamp) => 1.5,
+            (ResourceKind::Water, Biome::Desert) => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Amount produced per day by a tile, accounting for its biome
+    pub fn tile_yield(self, terrain: Terrain, biome: Biome) -> f32 {
+        self.terrain_yield(terrain) * self.biome_multiplier(biome)
+    }
+
+    /// Amount produced per day by a placed entity
+    pub fn entity_yield(self, kind: EntityKind) -> f32 {
+        match (self, kind) {
+            (ResourceKind::Food, EntityKind::Animal) => 1.5,
+            (ResourceKind::Wood, EntityKind::Tree) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Amount each person uses per day
+    pub fn consumption_per_person(self) -> f32 {
+        match self {
+            ResourceKind::Food => 1.0,
+            ResourceKind::Water => 1.0,
+            ResourceKind::Wood => 0.2,
+        }
+    }
+
+    /// Stock a freshly generated world starts with
+    pub fn starting_amount(self) -> f32 {
+        match self {
+            ResourceKind::Food => 100.0,
+            ResourceKind::Water => 100.0,
+            ResourceKind::Wood => 50.0,
+        }
+    }
+}
+
+/// Stockpiled food, water and wood, updated once per in-game day
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Resources {
+    amounts: [f32; 3],
+    /// Consecutive days each resource has been empty
+    days_empty: [u32; 3],
+    /// Last completed in-game day the stockpile was updated for
+    #[serde(skip)]
+    pub last_update_day: u64,
+}
+
+impl Resources {
+    /// Days a resource may stay empty before the population starts to suffer
+    pub const SHORTAGE_GRACE_DAYS: u32 = 3;
+    /// Fraction of the population lost per day for each resource in a prolonged shortage
+    pub const SHORTAGE_PENALTY: f32 = 0.1;
+
+    pub fn amount(&self, kind: ResourceKind) -> f32 {
+        self.amounts[kind.index()]
+    }
+
+    pub fn days_empty(&self, kind: ResourceKind) -> u32 {
+        self.days_empty[kind.index()]
+    }
+
+    /// Add one day's production and remove one day's consumption, tracking empty days
+    pub fn apply_day(&mut self, kind: ResourceKind, produced: f32, consumed: f32) {
+        let index = kind.index();
+        self.amounts[index] = (self.amounts[index] + produced - consumed).max(0.0);
+        if self.amounts[index] <= 0.0 {
+            self.days_empty[index] += 1;
+        } else {
+            self.days_empty[index] = 0;
+        }
+    }
+
+    /// Resources that have been empty for longer than the grace period
+    pub fn shortages(&self) -> impl Iterator<Item = ResourceKind> + '_ {
+        ResourceKind::ALL
+            .into_iter()
+            .filter(|kind| self.days_empty(*kind) > Self::SHORTAGE_GRACE_DAYS)
+    }
+}
+
+impl Default for Resources {
+    fn default() -> Self {
+        Self {
+            amounts: ResourceKind::ALL.map(ResourceKind::starting_amount),
+            days_empty: [0; 3],
+            last_update_day: 0,
+        }
+    }
+}
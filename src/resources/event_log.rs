@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use crate::resources::SimulationClock;
+
+/// One line in the event log
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// In-game time the event happened, formatted as "Day N, HH:MM"
+    pub timestamp: String,
+    pub message: String,
+    /// Tile the event happened at, for events with a place on the map
+    pub location: Option<UVec2>,
+}
+
+/// Recent simulation events, such as shortages, population milestones and miracles
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct EventLog {
+    /// Entries in the order they happened, oldest first
+    entries: VecDeque<LogEntry>,
+}
+
+impl EventLog {
+    /// Entries kept before the oldest is dropped
+    pub const MAX_ENTRIES: usize = 100;
+
+    /// Record an event at the current in-game time
+    pub fn push(
+        &mut self,
+        clock: &SimulationClock,
+        message: impl Into<String>,
+        location: Option<UVec2>,
+    ) {
+        if self.entries.len() == Self::MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            timestamp: clock.time_label(),
+            message: message.into(),
+            location,
+        });
+    }
+
+    /// Entries from the most recent to the oldest
+    pub fn latest(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_drops_the_oldest_entries_past_its_cap() {
+        let mut log = EventLog::default();
+        let clock = SimulationClock::default();
+        let extra = 5;
+
+        for index in 0..EventLog::MAX_ENTRIES + extra {
+            log.push(&clock, format!("Event {}", index), None);
+        }
+
+        assert_eq!(log.latest().count(), EventLog::MAX_ENTRIES);
+        assert_eq!(
+            log.latest().next().map(|entry| entry.message.as_str()),
+            Some(format!("Event {}", EventLog::MAX_ENTRIES + extra - 1).as_str())
+        );
+        assert_eq!(
+            log.latest().last().map(|entry| entry.message.as_str()),
+            Some(format!("Event {}", extra).as_str())
+        );
+    }
+
+    #[test]
+    fn entries_keep_their_location() {
+        let mut log = EventLog::default();
+        log.push(&SimulationClock::default(), "Fire", Some(UVec2::new(3, 4)));
+
+        assert_eq!(log.latest().next().and_then(|entry| entry.location), Some(UVec2::new(3, 4)));
+    }
+}
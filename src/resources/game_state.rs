@@ -1,24 +1,161 @@
 use bevy::prelude::*;
 
+/// Systems that reset a feature's per-world state, such as its resources and timers
+///
+/// They run once a new world starts generating, whether from the loading screen or regenerated
+/// in place, so every new world starts from the same state.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WorldReset;
+
 /// Game states for managing different screens and game flow
 #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub enum GameState {
     #[default]
     MainMenu,
     NewWorldkeeper,
+    Settings,
+    /// Key rebinding screen, opened from the settings screen
+    Controls,
+    /// Save slot list, opened with "Load World" from the main menu
+    Saves,
+    /// Generating the world before entering the game
+    Loading,
     InGame,
     Paused,
     GameOver,
+    Credits,
 }
 
 impl GameState {
     /// Check if the current state allows UI interaction
     pub fn allows_ui_interaction(&self) -> bool {
-        matches!(self, GameState::MainMenu | GameState::NewWorldkeeper | GameState::Paused)
+        matches!(self, GameState::MainMenu | GameState::NewWorldkeeper | GameState::Settings | GameState::Controls | GameState::Saves | GameState::Paused | GameState::GameOver)
     }
     
     /// Check if the current state is in active gameplay
     pub fn is_gameplay(&self) -> bool {
         matches!(self, GameState::InGame)
     }
-}
\ No newline at end of file
+
+    /// State that Escape leads to from this one, if any
+    ///
+    /// Escape pauses and resumes the game, backs out of screens opened from the main menu, and
+    /// cancels world generation. Settings returns to wherever it was opened from.
+    pub fn escape_target(&self, settings_origin: &GameState) -> Option<GameState> {
+        match self {
+            GameState::MainMenu | GameState::GameOver => None,
+            GameState::NewWorldkeeper | GameState::Saves | GameState::Credits | GameState::Loading => {
+                Some(GameState::MainMenu)
+            }
+            GameState::Settings => Some(settings_origin.clone()),
+            GameState::Controls => Some(GameState::Settings),
+            GameState::InGame => Some(GameState::Paused),
+            GameState::Paused => Some(GameState::InGame),
+        }
+    }
+}
+
+/// State to return to when leaving the settings screen
+///
+/// Settings can be opened from both the main menu and the pause menu.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct SettingsOrigin(pub GameState);
+
+impl Default for SettingsOrigin {
+    fn default() -> Self {
+        Self(GameState::MainMenu)
+    }
+}
+
+/// Cause of the most recent game over, set by the simulation system that ended the game
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct GameOverReason(pub String);
+
+impl Default for GameOverReason {
+    fn default() -> Self {
+        Self("The world fell silent for reasons unknown.".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATES: [GameState; 10] = [
+        GameState::MainMenu,
+        GameState::NewWorldkeeper,
+        GameState::Settings,
+        GameState::Controls,
+        GameState::Saves,
+        GameState::Loading,
+        GameState::InGame,
+        GameState::Paused,
+        GameState::GameOver,
+        GameState::Credits,
+    ];
+
+    /// Expected `(allows_ui_interaction, is_gameplay)` for a state
+    ///
+    /// The match has no wildcard, so adding a state fails to compile until it is listed here.
+    fn expected(state: &GameState) -> (bool, bool) {
+        match state {
+            GameState::MainMenu => (true, false),
+            GameState::NewWorldkeeper => (true, false),
+            GameState::Settings => (true, false),
+            GameState::Controls => (true, false),
+            GameState::Saves => (true, false),
+            GameState::Loading => (false, false),
+            GameState::InGame => (false, true),
+            GameState::Paused => (true, false),
+            GameState::GameOver => (true, false),
+            GameState::Credits => (false, false),
+        }
+    }
+
+    #[test]
+    fn allows_ui_interaction_matches_every_state() {
+        for state in ALL_STATES {
+            assert_eq!(state.allows_ui_interaction(), expected(&state).0, "{:?}", state);
+        }
+    }
+
+    #[test]
+    fn is_gameplay_matches_every_state() {
+        for state in ALL_STATES {
+            assert_eq!(state.is_gameplay(), expected(&state).1, "{:?}", state);
+        }
+    }
+
+    #[test]
+    fn escape_targets_match_every_state() {
+        let origin = GameState::MainMenu;
+        for state in ALL_STATES {
+            let expected = match state {
+                GameState::MainMenu => None,
+                GameState::NewWorldkeeper => Some(GameState::MainMenu),
+                GameState::Settings => Some(GameState::MainMenu),
+                GameState::Controls => Some(GameState::Settings),
+                GameState::Saves => Some(GameState::MainMenu),
+                GameState::Loading => Some(GameState::MainMenu),
+                GameState::InGame => Some(GameState::Paused),
+                GameState::Paused => Some(GameState::InGame),
+                GameState::GameOver => None,
+                GameState::Credits => Some(GameState::MainMenu),
+            };
+            assert_eq!(state.escape_target(&origin), expected, "{:?}", state);
+        }
+    }
+
+    #[test]
+    fn escape_from_settings_returns_to_its_origin() {
+        assert_eq!(
+            GameState::Settings.escape_target(&GameState::Paused),
+            Some(GameState::Paused)
+        );
+    }
+
+    #[test]
+    fn default_state_is_main_menu() {
+        assert_eq!(GameState::default(), GameState::MainMenu);
+    }
+}
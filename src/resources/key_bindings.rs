@@ -0,0 +1,280 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Logical gameplay actions that can be bound to a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    FocusCamera,
+    ResetView,
+    PrimaryAction,
+    Pause,
+    Screenshot,
+    ToggleGrid,
+    /// Held with Ctrl, and with Shift too to keep the seed
+    RegenerateWorld,
+    CastRain,
+    CastSunshine,
+    CastEarthquake,
+    CastWildfire,
+    CastFlood,
+}
+
+impl InputAction {
+    /// Every action, in the order shown on the controls screen
+    pub const ALL: [InputAction; 16] = [
+        InputAction::PanUp,
+        InputAction::PanDown,
+        InputAction::PanLeft,
+        InputAction::PanRight,
+        InputAction::FocusCamera,
+        InputAction::ResetView,
+        InputAction::PrimaryAction,
+        InputAction::Pause,
+        InputAction::Screenshot,
+        InputAction::ToggleGrid,
+        InputAction::RegenerateWorld,
+        InputAction::CastRain,
+        InputAction::CastSunshine,
+        InputAction::CastEarthquake,
+        InputAction::CastWildfire,
+        InputAction::CastFlood,
+    ];
+
+    /// Display name shown on the controls screen
+    pub fn label(self) -> &'static str {
+        match self {
+            InputAction::PanUp => "Pan Up",
+            InputAction::PanDown => "Pan Down",
+            InputAction::PanLeft => "Pan Left",
+            InputAction::PanRight => "Pan Right",
+            InputAction::FocusCamera => "Center Camera",
+            InputAction::ResetView => "Reset View",
+            InputAction::PrimaryAction => "Action",
+            InputAction::Pause => "Pause",
+            InputAction::Screenshot => "Screenshot",
+            InputAction::ToggleGrid => "Toggle Grid",
+            InputAction::RegenerateWorld => "Regenerate World (Ctrl)",
+            InputAction::CastRain => "Cast Rain",
+            InputAction::CastSunshine => "Cast Sunshine",
+            InputAction::CastEarthquake => "Cast Earthquake",
+            InputAction::CastWildfire => "Cast Wildfire",
+            InputAction::CastFlood => "Cast Flood",
+        }
+    }
+
+    /// Group the action is listed under in the help overlay
+    pub fn category(self) -> InputCategory {
+        match self {
+            InputAction::PanUp
+            | InputAction::PanDown
+            | InputAction::PanLeft
+            | InputAction::PanRight
+            | InputAction::FocusCamera
+            | InputAction::ResetView => InputCategory::Camera,
+            InputAction::PrimaryAction
+            | InputAction::Pause
+            | InputAction::Screenshot
+            | InputAction::ToggleGrid
+            | InputAction::RegenerateWorld => InputCategory::General,
+            InputAction::CastRain
+            | InputAction::CastSunshine
+            | InputAction::CastEarthquake
+            | InputAction::CastWildfire
+            | InputAction::CastFlood => InputCategory::Miracles,
+        }
+    }
+}
+
+/// Groups of related actions in the help overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputCategory {
+    Camera,
+    General,
+    Miracles,
+}
+
+impl InputCategory {
+    /// Every category, in the order shown in the help overlay
+    pub const ALL: [InputCategory; 3] = [
+        InputCategory::Camera,
+        InputCategory::General,
+        InputCategory::Miracles,
+    ];
+
+    /// Heading shown in the help overlay
+    pub fn label(self) -> &'static str {
+        match self {
+            InputCategory::Camera => "Camera",
+            InputCategory::General => "General",
+            InputCategory::Miracles => "Miracles",
+        }
+    }
+
+    /// Actions in this category, in the order shown on the controls screen
+    pub fn actions(self) -> impl Iterator<Item = InputAction> {
+        InputAction::ALL
+            .into_iter()
+            .filter(move |action| action.category() == self)
+    }
+}
+
+/// Keyboard key bound to each `InputAction`
+///
+/// Persisted next to the settings file and read back on launch.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub pan_up: KeyCode,
+    pub pan_down: KeyCode,
+    pub pan_left: KeyCode,
+    pub pan_right: KeyCode,
+    pub focus_camera: KeyCode,
+    pub reset_view: KeyCode,
+    pub primary_action: KeyCode,
+    pub pause: KeyCode,
+    pub screenshot: KeyCode,
+    pub toggle_grid: KeyCode,
+    pub regenerate_world: KeyCode,
+    pub cast_rain: KeyCode,
+    pub cast_sunshine: KeyCode,
+    pub cast_earthquake: KeyCode,
+    pub cast_wildfire: KeyCode,
+    pub cast_flood: KeyCode,
+}
+
+impl KeyBindings {
+    /// Key currently bound to an action
+    pub fn key(&self, action: InputAction) -> KeyCode {
+        match action {
+            InputAction::PanUp => self.pan_up,
+            InputAction::PanDown => self.pan_down,
+            InputAction::PanLeft => self.pan_left,
+            InputAction::PanRight => self.pan_right,
+            InputAction::FocusCamera => self.focus_camera,
+            InputAction::ResetView => self.reset_view,
+            InputAction::PrimaryAction => self.primary_action,
+            InputAction::Pause => self.pause,
+            InputAction::Screenshot => self.screenshot,
+            InputAction::ToggleGrid => self.toggle_grid,
+            InputAction::RegenerateWorld => self.regenerate_world,
+            InputAction::CastRain => self.cast_rain,
+            InputAction::CastSunshine => self.cast_sunshine,
+            InputAction::CastEarthquake => self.cast_earthquake,
+            InputAction::CastWildfire => self.cast_wildfire,
+            InputAction::CastFlood => self.cast_flood,
+        }
+    }
+
+    fn key_mut(&mut self, action: InputAction) -> &mut KeyCode {
+        match action {
+            InputAction::PanUp => &mut self.pan_up,
+            InputAction::PanDown => &mut self.pan_down,
+            InputAction::PanLeft => &mut self.pan_left,
+            InputAction::PanRight => &mut self.pan_right,
+            InputAction::FocusCamera => &mut self.focus_camera,
+            InputAction::ResetView => &mut self.reset_view,
+            InputAction::PrimaryAction => &mut self.primary_action,
+            InputAction::Pause => &mut self.pause,
+            InputAction::Screenshot => &mut self.screenshot,
+            InputAction::ToggleGrid => &mut self.toggle_grid,
+            InputAction::RegenerateWorld => &mut self.regenerate_world,
+            InputAction::CastRain => &mut self.cast_rain,
+            InputAction::CastSunshine => &mut self.cast_sunshine,
+            InputAction::CastEarthquake => &mut self.cast_earthquake,
+            InputAction::CastWildfire => &mut self.cast_wildfire,
+            InputAction::CastFlood => &mut self.cast_flood,
+        }
+    }
+
+    /// Action bound to a key, if any
+    pub fn action_for(&self, key: KeyCode) -> Option<InputAction> {
+        InputAction::ALL.into_iter().find(|action| self.key(*action) == key)
+    }
+
+    /// Bind a key to an action
+    ///
+    /// A key can only drive one action, so binding a key that belongs to another action is
+    /// rejected and that action is returned as the error.
+    pub fn bind(&mut self, action: InputAction, key: KeyCode) -> Result<(), InputAction> {
+        match self.action_for(key) {
+            Some(existing) if existing != action => Err(existing),
+            _ => {
+                *self.key_mut(action) = key;
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether the key bound to an action is held
+    pub fn pressed(&self, keyboard_input: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        keyboard_input.pressed(self.key(action))
+    }
+
+    /// Whether the key bound to an action was pressed this frame
+    pub fn just_pressed(&self, keyboard_input: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        keyboard_input.just_pressed(self.key(action))
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            pan_up: KeyCode::ArrowUp,
+            pan_down: KeyCode::ArrowDown,
+            pan_left: KeyCode::ArrowLeft,
+            pan_right: KeyCode::ArrowRight,
+            focus_camera: KeyCode::Home,
+            reset_view: KeyCode::End,
+            primary_action: KeyCode::Space,
+            pause: KeyCode::Escape,
+            screenshot: KeyCode::F12,
+            toggle_grid: KeyCode::KeyG,
+            regenerate_world: KeyCode::KeyR,
+            cast_rain: KeyCode::KeyZ,
+            cast_sunshine: KeyCode::KeyX,
+            cast_earthquake: KeyCode::KeyC,
+            cast_wildfire: KeyCode::KeyV,
+            cast_flood: KeyCode::KeyB,
+        }
+    }
+}
+
+/// Action waiting for a key press on the controls screen, if any
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingRebind(pub Option<InputAction>);
+
+/// Short display name for a key, e.g. "W" instead of "KeyW"
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .map(str::to_string)
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_keys_each_drive_one_action() {
+        let bindings = KeyBindings::default();
+
+        for action in InputAction::ALL {
+            assert_eq!(bindings.action_for(bindings.key(action)), Some(action));
+        }
+    }
+
+    #[test]
+    fn screenshot_key_can_be_rebound() {
+        let mut bindings = KeyBindings::default();
+
+        assert_eq!(bindings.bind(InputAction::Screenshot, KeyCode::KeyZ), Err(InputAction::CastRain));
+        assert_eq!(bindings.bind(InputAction::Screenshot, KeyCode::F11), Ok(()));
+        assert_eq!(bindings.action_for(KeyCode::F11), Some(InputAction::Screenshot));
+    }
+}
@@ -1,3 +1,49 @@
+pub mod action_history;
+pub mod audio;
+pub mod clipboard;
+pub mod credits;
+pub mod decay;
+pub mod diagnostics;
+pub mod disaster;
+pub mod divine_power;
+pub mod economy;
+pub mod event_log;
 pub mod game_state;
+pub mod key_bindings;
+pub mod population;
+pub mod rng;
+pub mod sandbox;
+pub mod screenshot;
+pub mod settings;
+pub mod simulation;
+pub mod statistics;
+pub mod terrain_brush;
+pub mod terrain_palette;
+pub mod terrain_rules;
+pub mod weather;
+pub mod world;
 
-pub use game_state::*;
\ No newline at end of file
+pub use action_history::*;
+pub use audio::*;
+pub use clipboard::*;
+pub use credits::*;
+pub use decay::*;
+pub use diagnostics::*;
+pub use disaster::*;
+pub use divine_power::*;
+pub use economy::*;
+pub use event_log::*;
+pub use game_state::*;
+pub use key_bindings::*;
+pub use population::*;
+pub use rng::*;
+pub use sandbox::*;
+pub use screenshot::*;
+pub use settings::*;
+pub use simulation::*;
+pub use statistics::*;
+pub use terrain_brush::*;
+pub use terrain_palette::*;
+pub use terrain_rules::*;
+pub use weather::*;
+pub use world::*;
\ No newline at end of file
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use crate::components::{EntityKind, Terrain};
+
+/// Number of people living in the world
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Population {
+    pub count: u32,
+    /// Last completed in-game day the population was updated for
+    pub last_update_day: u64,
+}
+
+impl Population {
+    /// People living in a freshly generated world
+    pub const STARTING_COUNT: u32 = 20;
+    /// Fraction of the population added per day while well below carrying capacity
+    pub const GROWTH_RATE: f32 = 0.08;
+    /// Fraction of the population lost per day when the land can support nobody
+    pub const COLLAPSE_RATE: f32 = 0.25;
+    /// Population milestones are logged at each multiple of this size
+    pub const MILESTONE_STEP: u32 = 50;
+
+    /// Population the world can sustain on each terrain type, per tile
+    pub fn terrain_capacity(terrain: Terrain) -> f32 {
+        match terrain {
+            Terrain::Water => 0.05,
+            Terrain::River => 0.08,
+            Terrain::Sand => 0.02,
+            Terrain::Grass => 0.1,
+            Terrain::Mountain => 0.0,
+        }
+    }
+
+    /// Extra population each placed entity can feed
+    pub fn resource_capacity(kind: EntityKind) -> f32 {
+        match kind {
+            EntityKind::Tree => 2.0,
+            EntityKind::Animal => 5.0,
+            EntityKind::Villager => 0.0,
+        }
+    }
+
+    /// Population after one day of logistic growth toward the carrying capacity
+    ///
+    /// Any decline loses at least one person, so a world that can't support its people
+    /// eventually empties.
+    pub fn next_count(count: u32, capacity: f32) -> u32 {
+        if count == 0 {
+            return 0;
+        }
+
+        let current = count as f32;
+        let change = if capacity > 0.0 {
+            Self::GROWTH_RATE * current * (1.0 - current / capacity)
+        } else {
+            -Self::COLLAPSE_RATE * current
+        };
+        let change = if change < 0.0 {
+            change.floor()
+        } else {
+            change.round()
+        };
+
+        (current + change).max(0.0) as u32
+    }
+}
+
+impl Default for Population {
+    fn default() -> Self {
+        Self {
+            count: Self::STARTING_COUNT,
+            last_update_day: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn population_grows_toward_capacity_without_passing_it() {
+        let capacity = 100.0;
+        let mut count = Population::STARTING_COUNT;
+        for _ in 0..1000 {
+            let next = Population::next_count(count, capacity);
+            assert!(next >= count);
+            assert!(next as f32 <= capacity);
+            count = next;
+        }
+        assert!(count as f32 > capacity * 0.9);
+    }
+
+    #[test]
+    fn population_over_capacity_declines() {
+        let next = Population::next_count(200, 100.0);
+        assert!(next < 200);
+        assert!(next > 100);
+    }
+
+    #[test]
+    fn population_without_capacity_dies_out() {
+        let mut count = Population::STARTING_COUNT;
+        for _ in 0..100 {
+            let next = Population::next_count(count, 0.0);
+            assert!(next < count || count == 0);
+            count = next;
+        }
+        assert_eq!(count, 0);
+        assert_eq!(Population::next_count(0, 100.0), 0);
+    }
+}
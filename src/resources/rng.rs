@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use rand::distributions::uniform::SampleUniform;
+use rand::{Rng as _, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Seeded random number generator for gameplay
+///
+/// Reseeded from the `WorldConfig` seed whenever a world is created, and its position in the
+/// stream is saved, so a loaded game rolls the same numbers as the game that was saved. Gameplay
+/// systems should draw from this instead of `rand::thread_rng()`.
+#[derive(Resource, Debug, Clone)]
+pub struct Rng(ChaCha8Rng);
+
+impl Rng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    /// Random value in `min..max`
+    pub fn range<T: SampleUniform + PartialOrd>(&mut self, min: T, max: T) -> T {
+        self.0.gen_range(min..max)
+    }
+
+    /// `true` with probability `p`, clamped to 0.0..=1.0
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.0.gen_bool(p.clamp(0.0, 1.0))
+    }
+
+    /// Uniform random roll in 0.0..1.0
+    pub fn roll(&mut self) -> f32 {
+        self.0.gen()
+    }
+
+    /// Position in the random stream, stored in saves
+    pub fn word_pos(&self) -> u128 {
+        self.0.get_word_pos()
+    }
+
+    /// Resume the random stream from a saved position
+    pub fn set_word_pos(&mut self, word_pos: u128) {
+        self.0.set_word_pos(word_pos);
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::from_seed(0)
+    }
+}
+
+/// Lets the resource drive `rand` helpers such as `shuffle` and `choose`
+impl RngCore for Rng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+/// Whether the world is played in sandbox mode, chosen on the New Worldkeeper screen
+///
+/// Sandbox worlds have unlimited mana, no miracle cooldowns and no game over. Saves record the
+/// flag, so a loaded world keeps its mode.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sandbox(pub bool);
+
+impl Sandbox {
+    /// Sandbox state formatted for display
+    pub fn label(self) -> &'static str {
+        if self.0 {
+            "On"
+        } else {
+            "Off"
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+
+/// Outcome of a screenshot, sent back once the frame has been captured and written
+pub type ScreenshotResult = Result<PathBuf, String>;
+
+/// Screenshots waiting to be written, and UI hidden from them
+#[derive(Resource, Debug)]
+pub struct Screenshots {
+    /// Handed to each capture so it can report back from the render thread
+    pub sender: Sender<ScreenshotResult>,
+    pub receiver: Mutex<Receiver<ScreenshotResult>>,
+    /// Root UI nodes hidden for the pending shot, with the visibility to restore afterwards
+    pub hidden_ui: Vec<(Entity, Visibility)>,
+}
+
+impl Screenshots {
+    /// Held with the screenshot key to leave the UI out of the shot
+    pub const HIDE_UI_MODIFIERS: [KeyCode; 2] = [KeyCode::ShiftLeft, KeyCode::ShiftRight];
+    /// Directory, relative to the working directory, that screenshots are written to
+    pub const DIRECTORY: &'static str = "screenshots";
+}
+
+impl Default for Screenshots {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver: Mutex::new(receiver),
+            hidden_ui: Vec::new(),
+        }
+    }
+}
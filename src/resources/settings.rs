@@ -0,0 +1,458 @@
+use bevy::prelude::*;
+use bevy::window::{PresentMode, WindowMode};
+use serde::{Deserialize, Serialize};
+use crate::components::MenuTheme;
+use crate::i18n::Language;
+use crate::resources::TerrainPalette;
+
+/// Window display modes selectable from the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WindowModeSetting {
+    #[default]
+    Windowed,
+    BorderlessFullscreen,
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    /// Get the next mode when cycling through the options
+    pub fn next(self) -> Self {
+        match self {
+            WindowModeSetting::Windowed => WindowModeSetting::BorderlessFullscreen,
+            WindowModeSetting::BorderlessFullscreen => WindowModeSetting::Fullscreen,
+            WindowModeSetting::Fullscreen => WindowModeSetting::Windowed,
+        }
+    }
+
+    /// Display name shown on the settings screen
+    pub fn label(self) -> &'static str {
+        match self {
+            WindowModeSetting::Windowed => "Windowed",
+            WindowModeSetting::BorderlessFullscreen => "Borderless",
+            WindowModeSetting::Fullscreen => "Fullscreen",
+        }
+    }
+
+    /// Bevy window mode applied to the primary window
+    pub fn window_mode(self) -> WindowMode {
+        match self {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::BorderlessFullscreen => WindowMode::BorderlessFullscreen,
+            WindowModeSetting::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+/// Menu color schemes selectable from the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemePreset {
+    /// Get the next theme when cycling through the options
+    pub fn next(self) -> Self {
+        match self {
+            ThemePreset::Dark => ThemePreset::Light,
+            ThemePreset::Light => ThemePreset::HighContrast,
+            ThemePreset::HighContrast => ThemePreset::Dark,
+        }
+    }
+
+    /// Display name shown on the settings screen
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "Dark",
+            ThemePreset::Light => "Light",
+            ThemePreset::HighContrast => "High Contrast",
+        }
+    }
+}
+
+/// Terrain palettes for players with color vision deficiencies, replacing the theme's palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorblindMode {
+    #[default]
+    Off,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl ColorblindMode {
+    /// Get the next mode when cycling through the options
+    pub fn next(self) -> Self {
+        match self {
+            ColorblindMode::Off => ColorblindMode::Protanopia,
+            ColorblindMode::Protanopia => ColorblindMode::Deuteranopia,
+            ColorblindMode::Deuteranopia => ColorblindMode::Tritanopia,
+            ColorblindMode::Tritanopia => ColorblindMode::Off,
+        }
+    }
+
+    /// Display name shown on the settings screen
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorblindMode::Off => "Off",
+            ColorblindMode::Protanopia => "Protanopia",
+            ColorblindMode::Deuteranopia => "Deuteranopia",
+            ColorblindMode::Tritanopia => "Tritanopia",
+        }
+    }
+}
+
+/// Windowed resolution in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Resolution {
+    /// Resolutions offered on the settings screen, smallest first
+    pub const PRESETS: [Resolution; 5] = [
+        Resolution::new(1024, 768),
+        Resolution::new(1280, 720),
+        Resolution::new(1600, 900),
+        Resolution::new(1920, 1080),
+        Resolution::new(2560, 1440),
+    ];
+
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// Display name shown on the settings screen
+    pub fn label(self) -> String {
+        format!("{}x{}", self.width, self.height)
+    }
+
+    pub fn fits_within(self, available: UVec2) -> bool {
+        self.width <= available.x && self.height <= available.y
+    }
+
+    /// This resolution if it fits the available size, otherwise the largest preset that does
+    ///
+    /// Falls back to the smallest preset when nothing fits.
+    pub fn fit_to(self, available: UVec2) -> Self {
+        if self.fits_within(available) {
+            return self;
+        }
+
+        Self::PRESETS
+            .iter()
+            .rev()
+            .copied()
+            .find(|preset| preset.fits_within(available))
+            .unwrap_or(Self::PRESETS[0])
+    }
+}
+
+impl Default for Resolution {
+    fn default() -> Self {
+        Self::PRESETS[0]
+    }
+}
+
+/// Player-configurable settings edited from the settings screen
+///
+/// Persisted to the config directory whenever it changes and read back on launch.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Master volume in the range 0.0..=1.0
+    pub master_volume: f32,
+    pub window_mode: WindowModeSetting,
+    /// Window size used in windowed mode
+    pub resolution: Resolution,
+    /// In-game minutes between autosaves on the simulation clock, or 0 to turn autosaving off
+    pub autosave_game_minutes: u32,
+    /// Pan the camera when the cursor nears the window edge
+    pub edge_scroll: bool,
+    /// Let the camera glide to a stop after a drag-pan instead of stopping dead
+    pub camera_inertia: bool,
+    /// Multiplier for how far drag-panning and edge scrolling move the camera
+    pub mouse_sensitivity: f32,
+    /// Multiplier for the speed of panning with the keyboard or gamepad stick
+    pub key_pan_speed: f32,
+    /// Pause the game when the window loses focus
+    pub pause_on_focus_loss: bool,
+    /// Darken the screen edges in game, more so as shortages threaten the world
+    pub vignette: bool,
+    /// Wait for the monitor's refresh before presenting each frame
+    pub vsync: bool,
+    /// Highest frame rate to run at, or 0 for no cap
+    ///
+    /// With VSync on the monitor's refresh rate is also a limit, so caps above it have no
+    /// effect. The F3 diagnostics overlay shows the FPS actually reached.
+    pub fps_cap: u32,
+    pub theme: ThemePreset,
+    /// Terrain palette for a color vision deficiency, overriding the theme's palette
+    pub colorblind_mode: ColorblindMode,
+    /// Mark each terrain type with a pattern drawn over its tiles, so it doesn't rely on color
+    pub terrain_patterns: bool,
+    /// Language of the UI text
+    pub language: Language,
+    /// Multiplier for every font size, for players who need larger text
+    pub font_scale: f32,
+}
+
+impl Settings {
+    /// Increment of the volume slider
+    pub const VOLUME_STEP: f32 = 0.05;
+    pub const MAX_AUTOSAVE_GAME_MINUTES: u32 = 48 * 60;
+    /// Increment of the autosave slider, one in-game hour
+    pub const AUTOSAVE_STEP_GAME_MINUTES: u32 = 60;
+    pub const MIN_FONT_SCALE: f32 = 1.0;
+    pub const MAX_FONT_SCALE: f32 = 2.0;
+    /// Increment of the text size slider
+    pub const FONT_SCALE_STEP: f32 = 0.25;
+    pub const MIN_PAN_SPEED: f32 = 0.25;
+    pub const MAX_PAN_SPEED: f32 = 3.0;
+    /// Increment of the mouse sensitivity and key pan speed sliders
+    pub const PAN_SPEED_STEP: f32 = 0.25;
+    /// Frame rate caps offered on the settings screen, with 0 for no cap
+    pub const FPS_CAPS: [u32; 5] = [0, 30, 60, 120, 144];
+
+    /// Bring values read from a settings file back within the ranges the settings screen allows
+    ///
+    /// Hand-edited or corrupt files can hold anything. Values that aren't numbers at all, and
+    /// choices the settings screen doesn't offer, fall back to their defaults.
+    pub fn clamp_to_limits(&mut self) {
+        let defaults = Settings::default();
+        let finite_or = |value: f32, default: f32| if value.is_finite() { value } else { default };
+
+        self.set_volume(finite_or(self.master_volume, defaults.master_volume));
+        self.set_autosave_game_minutes(self.autosave_game_minutes);
+        self.set_font_scale(finite_or(self.font_scale, defaults.font_scale));
+        self.set_mouse_sensitivity(finite_or(self.mouse_sensitivity, defaults.mouse_sensitivity));
+        self.set_key_pan_speed(finite_or(self.key_pan_speed, defaults.key_pan_speed));
+        if !Self::FPS_CAPS.contains(&self.fps_cap) {
+            self.fps_cap = defaults.fps_cap;
+        }
+        if self.resolution.width == 0 || self.resolution.height == 0 {
+            self.resolution = defaults.resolution;
+        }
+    }
+
+    /// Set the master volume, keeping it within 0.0..=1.0
+    pub fn set_volume(&mut self, volume: f32) {
+        self.master_volume = volume.clamp(0.0, 1.0);
+    }
+
+    /// Master volume formatted as a percentage
+    pub fn volume_label(&self) -> String {
+        format!("{}%", (self.master_volume * 100.0).round() as u32)
+    }
+
+    /// Set the autosave interval in in-game minutes, keeping it within the allowed range
+    pub fn set_autosave_game_minutes(&mut self, minutes: u32) {
+        self.autosave_game_minutes = minutes.min(Self::MAX_AUTOSAVE_GAME_MINUTES);
+    }
+
+    /// Set the font size multiplier, keeping it within the allowed range
+    pub fn set_font_scale(&mut self, font_scale: f32) {
+        self.font_scale = font_scale.clamp(Self::MIN_FONT_SCALE, Self::MAX_FONT_SCALE);
+    }
+
+    /// Font size multiplier formatted as a percentage
+    pub fn font_scale_label(&self) -> String {
+        format!("{}%", (self.font_scale * 100.0).round() as u32)
+    }
+
+    /// Set the mouse sensitivity, keeping it within the allowed range
+    pub fn set_mouse_sensitivity(&mut self, sensitivity: f32) {
+        self.mouse_sensitivity = sensitivity.clamp(Self::MIN_PAN_SPEED, Self::MAX_PAN_SPEED);
+    }
+
+    /// Set the keyboard panning speed, keeping it within the allowed range
+    pub fn set_key_pan_speed(&mut self, speed: f32) {
+        self.key_pan_speed = speed.clamp(Self::MIN_PAN_SPEED, Self::MAX_PAN_SPEED);
+    }
+
+    /// Mouse sensitivity formatted as a percentage
+    pub fn mouse_sensitivity_label(&self) -> String {
+        format!("{}%", (self.mouse_sensitivity * 100.0).round() as u32)
+    }
+
+    /// Keyboard panning speed formatted as a percentage
+    pub fn key_pan_speed_label(&self) -> String {
+        format!("{}%", (self.key_pan_speed * 100.0).round() as u32)
+    }
+
+    /// Menu theme for the chosen palette and text size
+    pub fn menu_theme(&self) -> MenuTheme {
+        MenuTheme::from(self.theme).with_font_scale(self.font_scale)
+    }
+
+    /// Terrain palette for the chosen colorblind mode, or for the theme when it is off
+    pub fn terrain_palette(&self) -> TerrainPalette {
+        match self.colorblind_mode {
+            ColorblindMode::Off => TerrainPalette::from(self.theme),
+            mode => TerrainPalette::from(mode),
+        }
+    }
+
+    /// Terrain pattern state formatted for display
+    pub fn terrain_patterns_label(&self) -> &'static str {
+        on_off_label(self.terrain_patterns)
+    }
+
+    /// Edge scrolling state formatted for display
+    pub fn edge_scroll_label(&self) -> &'static str {
+        on_off_label(self.edge_scroll)
+    }
+
+    /// Camera inertia state formatted for display
+    pub fn camera_inertia_label(&self) -> &'static str {
+        on_off_label(self.camera_inertia)
+    }
+
+    /// Pause-on-focus-loss state formatted for display
+    pub fn pause_on_focus_loss_label(&self) -> &'static str {
+        on_off_label(self.pause_on_focus_loss)
+    }
+
+    /// Vignette state formatted for display
+    pub fn vignette_label(&self) -> &'static str {
+        on_off_label(self.vignette)
+    }
+
+    /// Cycle to the next frame rate cap, wrapping back to no cap
+    pub fn cycle_fps_cap(&mut self) {
+        let index = Self::FPS_CAPS.iter().position(|cap| *cap == self.fps_cap);
+        self.fps_cap = index.map_or(0, |index| Self::FPS_CAPS[(index + 1) % Self::FPS_CAPS.len()]);
+    }
+
+    /// Present mode of the primary window for the VSync setting
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    /// VSync and frame rate cap formatted for display
+    pub fn frame_rate_label(&self) -> String {
+        let cap = match self.fps_cap {
+            0 => "No Cap".to_string(),
+            cap => format!("{} FPS", cap),
+        };
+        format!("VSync {}, {}", on_off_label(self.vsync), cap)
+    }
+
+    /// Autosave interval formatted for display
+    pub fn autosave_label(&self) -> String {
+        match self.autosave_game_minutes {
+            0 => "Off".to_string(),
+            minutes if minutes % 60 == 0 => format!("Every {} in-game h", minutes / 60),
+            minutes => format!("Every {} in-game min", minutes),
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 0.8,
+            window_mode: WindowModeSetting::default(),
+            resolution: Resolution::default(),
+            autosave_game_minutes: 12 * 60,
+            edge_scroll: true,
+            camera_inertia: true,
+            mouse_sensitivity: 1.0,
+            key_pan_speed: 1.0,
+            pause_on_focus_loss: true,
+            vignette: true,
+            vsync: true,
+            fps_cap: 0,
+            theme: ThemePreset::default(),
+            colorblind_mode: ColorblindMode::default(),
+            terrain_patterns: false,
+            language: Language::default(),
+            font_scale: 1.0,
+        }
+    }
+}
+
+fn on_off_label(enabled: bool) -> &'static str {
+    if enabled {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loaded_values_are_clamped_to_their_limits() {
+        let mut settings = Settings {
+            master_volume: 3.0,
+            autosave_game_minutes: u32::MAX,
+            font_scale: 0.1,
+            fps_cap: 75,
+            resolution: Resolution::new(0, 720),
+            ..default()
+        };
+        settings.clamp_to_limits();
+
+        assert_eq!(settings.master_volume, 1.0);
+        assert_eq!(settings.autosave_game_minutes, Settings::MAX_AUTOSAVE_GAME_MINUTES);
+        assert_eq!(settings.font_scale, Settings::MIN_FONT_SCALE);
+        assert_eq!(settings.fps_cap, Settings::default().fps_cap);
+        assert_eq!(settings.resolution, Resolution::default());
+    }
+
+    #[test]
+    fn pan_speeds_are_clamped_to_their_limits() {
+        let mut settings = Settings::default();
+        settings.set_mouse_sensitivity(10.0);
+        settings.set_key_pan_speed(0.0);
+        assert_eq!(settings.mouse_sensitivity, Settings::MAX_PAN_SPEED);
+        assert_eq!(settings.key_pan_speed, Settings::MIN_PAN_SPEED);
+
+        let mut loaded = Settings {
+            mouse_sensitivity: -1.0,
+            key_pan_speed: f32::NAN,
+            ..default()
+        };
+        loaded.clamp_to_limits();
+        assert_eq!(loaded.mouse_sensitivity, Settings::MIN_PAN_SPEED);
+        assert_eq!(loaded.key_pan_speed, Settings::default().key_pan_speed);
+    }
+
+    #[test]
+    fn values_that_are_not_numbers_fall_back_to_defaults() {
+        let mut settings = Settings {
+            master_volume: f32::NAN,
+            font_scale: f32::INFINITY,
+            ..default()
+        };
+        settings.clamp_to_limits();
+
+        assert_eq!(settings.master_volume, Settings::default().master_volume);
+        assert_eq!(settings.font_scale, Settings::default().font_scale);
+    }
+
+    #[test]
+    fn values_in_range_are_kept() {
+        let mut settings = Settings {
+            master_volume: 0.35,
+            font_scale: 1.5,
+            fps_cap: 144,
+            resolution: Resolution::new(1366, 768),
+            ..default()
+        };
+        let expected = settings.clone();
+        settings.clamp_to_limits();
+
+        assert_eq!(settings, expected);
+    }
+}
@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+
+/// Speed settings for the simulation clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimulationSpeed {
+    Paused,
+    #[default]
+    Normal,
+    Fast,
+    Fastest,
+}
+
+impl SimulationSpeed {
+    /// Every speed, slowest first, in the order of the HUD speed buttons
+    pub const ALL: [SimulationSpeed; 4] = [
+        SimulationSpeed::Paused,
+        SimulationSpeed::Normal,
+        SimulationSpeed::Fast,
+        SimulationSpeed::Fastest,
+    ];
+
+    /// Multiplier applied to real time when advancing the clock
+    pub fn multiplier(self) -> f32 {
+        match self {
+            SimulationSpeed::Paused => 0.0,
+            SimulationSpeed::Normal => 1.0,
+            SimulationSpeed::Fast => 2.0,
+            SimulationSpeed::Fastest => 4.0,
+        }
+    }
+
+    /// Short label shown in the HUD
+    pub fn label(self) -> &'static str {
+        match self {
+            SimulationSpeed::Paused => "Paused",
+            SimulationSpeed::Normal => "1x",
+            SimulationSpeed::Fast => "2x",
+            SimulationSpeed::Fastest => "4x",
+        }
+    }
+
+    /// Text on the speed's HUD button
+    ///
+    /// The default font has no pause symbol, so two bars stand in for it.
+    pub fn button_label(self) -> &'static str {
+        match self {
+            SimulationSpeed::Paused => "||",
+            speed => speed.label(),
+        }
+    }
+
+    /// Key of the label in the UI strings
+    pub fn key(self) -> &'static str {
+        match self {
+            SimulationSpeed::Paused => "speed.paused",
+            SimulationSpeed::Normal => "speed.normal",
+            SimulationSpeed::Fast => "speed.fast",
+            SimulationSpeed::Fastest => "speed.fastest",
+        }
+    }
+}
+
+/// Broad part of the day, shown in the HUD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    Night,
+    Dawn,
+    Day,
+    Dusk,
+}
+
+impl TimeOfDay {
+    /// Part of the day a given hour (0.0..24.0) falls in
+    pub fn at_hour(hour: f64) -> Self {
+        match hour {
+            h if h < 5.0 => TimeOfDay::Night,
+            h if h < 8.0 => TimeOfDay::Dawn,
+            h if h < 18.0 => TimeOfDay::Day,
+            h if h < 21.0 => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    /// Display name of the time of day
+    pub fn label(self) -> &'static str {
+        match self {
+            TimeOfDay::Night => "Night",
+            TimeOfDay::Dawn => "Dawn",
+            TimeOfDay::Day => "Day",
+            TimeOfDay::Dusk => "Dusk",
+        }
+    }
+
+    /// Key of the name in the UI strings
+    pub fn key(self) -> &'static str {
+        match self {
+            TimeOfDay::Night => "time.night",
+            TimeOfDay::Dawn => "time.dawn",
+            TimeOfDay::Day => "time.day",
+            TimeOfDay::Dusk => "time.dusk",
+        }
+    }
+}
+
+/// In-game time, advanced while the game is running
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct SimulationClock {
+    /// Total in-game hours since the world was created
+    pub elapsed_hours: f64,
+    pub speed: SimulationSpeed,
+}
+
+impl SimulationClock {
+    pub const HOURS_PER_DAY: f64 = 24.0;
+    /// Fixed simulation steps per real second, whatever the frame rate
+    pub const TICKS_PER_SECOND: f64 = 20.0;
+
+    /// Advance the clock by a real-time delta scaled by the current speed, where a full
+    /// in-game day takes `day_length_seconds` of real time at 1x speed
+    pub fn advance(&mut self, real_seconds: f32, day_length_seconds: f32) {
+        let hours_per_second = Self::HOURS_PER_DAY / day_length_seconds.max(1.0) as f64;
+        self.elapsed_hours +=
+            real_seconds as f64 * self.speed.multiplier() as f64 * hours_per_second;
+    }
+
+    /// Number of completed in-game days
+    pub fn elapsed_days(&self) -> u64 {
+        (self.elapsed_hours / Self::HOURS_PER_DAY) as u64
+    }
+
+    /// Current day, starting from day 1
+    pub fn day(&self) -> u64 {
+        self.elapsed_days() + 1
+    }
+
+    /// Hour of the current day in the range 0.0..24.0
+    pub fn hour_of_day(&self) -> f64 {
+        self.elapsed_hours % Self::HOURS_PER_DAY
+    }
+
+    /// Part of the day the current hour falls in
+    pub fn time_of_day(&self) -> TimeOfDay {
+        TimeOfDay::at_hour(self.hour_of_day())
+    }
+
+    /// Current in-game time formatted as "Day N, HH:MM"
+    pub fn time_label(&self) -> String {
+        let hour_of_day = self.hour_of_day();
+        let hours = hour_of_day as u32;
+        let minutes = ((hour_of_day - hours as f64) * 60.0) as u32;
+        format!("Day {}, {:02}:{:02}", self.day(), hours, minutes)
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use crate::resources::SimulationClock;
+
+/// Figures tracked over time for the statistics graphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Statistic {
+    Population,
+    Food,
+    Mana,
+}
+
+impl Statistic {
+    /// Every statistic, in graph order
+    pub const ALL: [Statistic; 3] = [Statistic::Population, Statistic::Food, Statistic::Mana];
+
+    /// Display name of the statistic
+    pub fn label(self) -> &'static str {
+        match self {
+            Statistic::Population => "Population",
+            Statistic::Food => "Food",
+            Statistic::Mana => "Mana",
+        }
+    }
+
+    /// Color of the statistic's graph line
+    pub fn color(self) -> Color {
+        match self {
+            Statistic::Population => Color::srgb(0.95, 0.95, 0.95),
+            Statistic::Food => Color::srgb(0.9, 0.6, 0.2),
+            Statistic::Mana => Color::srgb(0.45, 0.6, 1.0),
+        }
+    }
+}
+
+/// Every statistic's value at one moment
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatisticsSample {
+    pub population: f32,
+    pub food: f32,
+    pub mana: f32,
+}
+
+impl StatisticsSample {
+    /// Value of one statistic
+    pub fn value(&self, statistic: Statistic) -> f32 {
+        match statistic {
+            Statistic::Population => self.population,
+            Statistic::Food => self.food,
+            Statistic::Mana => self.mana,
+        }
+    }
+}
+
+/// Recent samples of the world's statistics, taken at a fixed interval of in-game time
+///
+/// Only the last `MAX_SAMPLES` are kept, so the history stays the same size however long a
+/// world runs.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct StatisticsHistory {
+    /// Samples in the order they were taken, oldest first
+    samples: VecDeque<StatisticsSample>,
+    /// In-game hour the last sample was taken at
+    last_sample_hour: Option<f64>,
+}
+
+impl StatisticsHistory {
+    /// Samples taken per in-game day
+    pub const SAMPLES_PER_DAY: u32 = 4;
+    /// Samples kept before the oldest is dropped, covering 30 in-game days
+    pub const MAX_SAMPLES: usize = 120;
+
+    /// In-game hours between samples
+    pub fn sample_interval_hours() -> f64 {
+        SimulationClock::HOURS_PER_DAY / Self::SAMPLES_PER_DAY as f64
+    }
+
+    /// Record a sample if a full interval has passed since the last one, returning whether it
+    /// was recorded
+    pub fn record(&mut self, now_hours: f64, sample: StatisticsSample) -> bool {
+        if self
+            .last_sample_hour
+            .is_some_and(|last| now_hours - last < Self::sample_interval_hours())
+        {
+            return false;
+        }
+        if self.samples.len() == Self::MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.last_sample_hour = Some(now_hours);
+        true
+    }
+
+    /// Number of samples recorded
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether no samples have been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Values of one statistic, oldest first
+    pub fn values(&self, statistic: Statistic) -> impl Iterator<Item = f32> + '_ {
+        self.samples.iter().map(move |sample| sample.value(statistic))
+    }
+
+    /// Most recent value of one statistic
+    pub fn latest(&self, statistic: Statistic) -> Option<f32> {
+        self.samples.back().map(|sample| sample.value(statistic))
+    }
+
+    /// Top of a statistic's graph: its highest recorded value, rounded up to a whole step so
+    /// the scale doesn't change with every sample
+    pub fn scale(&self, statistic: Statistic) -> f32 {
+        const STEP: f32 = 10.0;
+        let highest = self.values(statistic).fold(0.0, f32::max);
+        ((highest / STEP).ceil() * STEP).max(STEP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(population: f32) -> StatisticsSample {
+        StatisticsSample {
+            population,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn samples_are_taken_once_per_interval() {
+        let mut history = StatisticsHistory::default();
+        let interval = StatisticsHistory::sample_interval_hours();
+
+        assert!(history.record(0.0, sample(1.0)));
+        assert!(!history.record(interval / 2.0, sample(2.0)));
+        assert!(history.record(interval, sample(3.0)));
+        assert_eq!(history.values(Statistic::Population).collect::<Vec<_>>(), vec![1.0, 3.0]);
+    }
+
+    #[test]
+    fn history_drops_the_oldest_samples() {
+        let mut history = StatisticsHistory::default();
+        let interval = StatisticsHistory::sample_interval_hours();
+        let extra = 5;
+
+        for index in 0..StatisticsHistory::MAX_SAMPLES + extra {
+            history.record(index as f64 * interval, sample(index as f32));
+        }
+
+        assert_eq!(history.len(), StatisticsHistory::MAX_SAMPLES);
+        assert_eq!(history.values(Statistic::Population).next(), Some(extra as f32));
+        assert_eq!(
+            history.latest(Statistic::Population),
+            Some((StatisticsHistory::MAX_SAMPLES + extra - 1) as f32)
+        );
+    }
+
+    #[test]
+    fn scale_rounds_the_highest_value_up() {
+        let mut history = StatisticsHistory::default();
+        assert_eq!(history.scale(Statistic::Population), 10.0);
+
+        history.record(0.0, sample(42.0));
+        history.record(StatisticsHistory::sample_interval_hours(), sample(17.0));
+        assert_eq!(history.scale(Statistic::Population), 50.0);
+        assert_eq!(history.scale(Statistic::Mana), 10.0);
+    }
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use crate::components::Terrain;
+use crate::resources::WorldConfig;
+
+/// Powers that reshape the land under the terrain brush
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TerrainTool {
+    Raise,
+    Lower,
+}
+
+impl TerrainTool {
+    /// Every terrain tool, in toolbar order
+    pub const ALL: [TerrainTool; 2] = [TerrainTool::Raise, TerrainTool::Lower];
+
+    /// Display name shown on the toolbar
+    pub fn name(self) -> &'static str {
+        match self {
+            TerrainTool::Raise => "Raise Land",
+            TerrainTool::Lower => "Lower Land",
+        }
+    }
+
+    /// Terrain a tile becomes when the tool is used on it
+    pub fn apply(self, terrain: Terrain) -> Terrain {
+        match (self, terrain) {
+            (TerrainTool::Raise, Terrain::Water) => Terrain::Sand,
+            (TerrainTool::Raise, Terrain::River | Terrain::Sand) => Terrain::Grass,
+            (TerrainTool::Raise, Terrain::Grass | Terrain::Mountain) => Terrain::Mountain,
+            (TerrainTool::Lower, Terrain::Mountain) => Terrain::Grass,
+            (TerrainTool::Lower, Terrain::Grass) => Terrain::Sand,
+            (TerrainTool::Lower, Terrain::Sand | Terrain::River | Terrain::Water) => Terrain::Water,
+        }
+    }
+}
+
+/// Selected terrain tool and the radius of its circular brush
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TerrainBrush {
+    pub tool: Option<TerrainTool>,
+    /// Tiles from the center to the edge of the brush, where 0 covers a single tile
+    pub radius: u32,
+}
+
+impl TerrainBrush {
+    pub const MAX_RADIUS: u32 = 5;
+    /// Mana spent for each tile the brush changes
+    pub const MANA_PER_TILE: f32 = 1.0;
+
+    /// Grow or shrink the brush, keeping it within the allowed radius
+    pub fn resize(&mut self, steps: i32) {
+        self.radius = self.radius.saturating_add_signed(steps).min(Self::MAX_RADIUS);
+    }
+
+    /// Tiles covered by the brush when centered on a tile, clipped to the map
+    pub fn tiles(&self, center: UVec2, config: &WorldConfig) -> Vec<UVec2> {
+        let radius = self.radius as i32;
+        let center = center.as_ivec2();
+        let size = IVec2::new(config.width as i32, config.height as i32);
+
+        (-radius..=radius)
+            .flat_map(|y| (-radius..=radius).map(move |x| IVec2::new(x, y)))
+            .filter(|offset| offset.length_squared() <= radius * radius)
+            .map(|offset| center + offset)
+            .filter(|coord| coord.cmpge(IVec2::ZERO).all() && coord.cmplt(size).all())
+            .map(|coord| coord.as_uvec2())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_stays_within_the_allowed_radius() {
+        let mut brush = TerrainBrush::default();
+        brush.resize(-1);
+        assert_eq!(brush.radius, 0);
+
+        brush.resize(2);
+        assert_eq!(brush.radius, 2);
+
+        brush.resize(100);
+        assert_eq!(brush.radius, TerrainBrush::MAX_RADIUS);
+    }
+
+    #[test]
+    fn brush_covers_a_circle_of_tiles() {
+        let config = WorldConfig::default();
+        let center = UVec2::new(20, 20);
+        let mut brush = TerrainBrush::default();
+        assert_eq!(brush.tiles(center, &config), vec![center]);
+
+        brush.radius = 2;
+        let tiles = brush.tiles(center, &config);
+        // A 5x5 square without its four corners and the tiles beside them
+        assert_eq!(tiles.len(), 13);
+        assert!(tiles.contains(&UVec2::new(22, 20)));
+        assert!(!tiles.contains(&UVec2::new(22, 22)));
+    }
+
+    #[test]
+    fn brush_is_clipped_to_the_map() {
+        let config = WorldConfig::default();
+        let brush = TerrainBrush {
+            tool: Some(TerrainTool::Raise),
+            radius: 1,
+        };
+
+        assert_eq!(brush.tiles(UVec2::ZERO, &config).len(), 3);
+        let corner = UVec2::new(config.width - 1, config.height - 1);
+        assert!(brush.tiles(corner, &config).iter().all(|tile| tile.x < config.width && tile.y < config.height));
+    }
+
+    #[test]
+    fn raising_and_lowering_step_through_the_terrain() {
+        let mut terrain = Terrain::Water;
+        for expected in [Terrain::Sand, Terrain::Grass, Terrain::Mountain, Terrain::Mountain] {
+            terrain = TerrainTool::Raise.apply(terrain);
+            assert_eq!(terrain, expected);
+        }
+        for expected in [Terrain::Grass, Terrain::Sand, Terrain::Water, Terrain::Water] {
+            terrain = TerrainTool::Lower.apply(terrain);
+            assert_eq!(terrain, expected);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use crate::components::tile::{Biome, Terrain};
+use crate::resources::{ColorblindMode, ThemePreset};
+
+/// Color of each terrain type in each biome, shared by the map tiles and the minimap
+///
+/// Follows the theme or colorblind mode chosen in the settings; changing it recolors the world
+/// in place.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct TerrainPalette(pub HashMap<(Terrain, Biome), Color>);
+
+impl TerrainPalette {
+    /// Palette with a color for every terrain and biome pair
+    fn from_fn(color: impl Fn(Terrain, Biome) -> Color) -> Self {
+        Self(
+            Terrain::ALL
+                .iter()
+                .flat_map(|terrain| Biome::ALL.iter().map(move |biome| (*terrain, *biome)))
+                .map(|(terrain, biome)| ((terrain, biome), color(terrain, biome)))
+                .collect(),
+        )
+    }
+
+    /// Natural colors, where grass takes on the color of its biome
+    pub fn standard() -> Self {
+        Self::from_fn(|terrain, biome| match (terrain, biome) {
+            (Terrain::Water, _) => Color::srgb(0.15, 0.35, 0.7),
+            (Terrain::River, _) => Color::srgb(0.3, 0.6, 0.9),
+            (Terrain::Sand, _) => Color::srgb(0.85, 0.8, 0.55),
+            (Terrain::Mountain, _) => Color::srgb(0.5, 0.45, 0.4),
+            (Terrain::Grass, Biome::Tundra) => Color::srgb(0.7, 0.75, 0.7),
+            (Terrain::Grass, Biome::Forest) => Color::srgb(0.15, 0.42, 0.18),
+            (Terrain::Grass, Biome::Desert) => Color::srgb(0.78, 0.68, 0.4),
+            (Terrain::Grass, Biome::Grassland) => Color::srgb(0.3, 0.6, 0.25),
+            (Terrain::Grass, Biome::Swamp) => Color::srgb(0.3, 0.4, 0.25),
+        })
+    }
+
+    /// Saturated colors far apart in brightness, for low-vision players
+    pub fn high_contrast() -> Self {
+        Self::from_fn(|terrain, biome| match (terrain, biome) {
+            (Terrain::Water, _) => Color::srgb(0.0, 0.1, 0.55),
+            (Terrain::River, _) => Color::srgb(0.0, 0.65, 1.0),
+            (Terrain::Sand, _) => Color::srgb(1.0, 0.9, 0.2),
+            (Terrain::Mountain, _) => Color::srgb(0.55, 0.55, 0.55),
+            (Terrain::Grass, Biome::Tundra) => Color::WHITE,
+            (Terrain::Grass, Biome::Forest) => Color::srgb(0.0, 0.35, 0.0),
+            (Terrain::Grass, Biome::Desert) => Color::srgb(0.95, 0.5, 0.0),
+            (Terrain::Grass, Biome::Grassland) => Color::srgb(0.3, 0.9, 0.1),
+            (Terrain::Grass, Biome::Swamp) => Color::srgb(0.5, 0.25, 0.6),
+        })
+    }
+
+    /// Colors told apart without red-sensitive cones, keeping water blue and grass in yellows
+    /// and browns set apart by brightness
+    pub fn protanopia() -> Self {
+        Self::from_fn(|terrain, biome| match (terrain, biome) {
+            (Terrain::Water, _) => Color::srgb(0.0, 0.3, 0.6),
+            (Terrain::River, _) => Color::srgb(0.35, 0.7, 0.95),
+            (Terrain::Sand, _) => Color::srgb(0.95, 0.9, 0.45),
+            (Terrain::Mountain, _) => Color::srgb(0.3, 0.28, 0.28),
+            (Terrain::Grass, Biome::Tundra) => Color::srgb(0.85, 0.85, 0.9),
+            (Terrain::Grass, Biome::Forest) => Color::srgb(0.5, 0.42, 0.1),
+            (Terrain::Grass, Biome::Desert) => Color::srgb(0.9, 0.6, 0.2),
+            (Terrain::Grass, Biome::Grassland) => Color::srgb(0.75, 0.65, 0.2),
+            (Terrain::Grass, Biome::Swamp) => Color::srgb(0.55, 0.55, 0.4),
+        })
+    }
+
+    /// Colors told apart without green-sensitive cones, along the same blue-yellow axis as
+    /// `protanopia`
+    pub fn deuteranopia() -> Self {
+        Self::from_fn(|terrain, biome| match (terrain, biome) {
+            (Terrain::Water, _) => Color::srgb(0.0, 0.3, 0.6),
+            (Terrain::River, _) => Color::srgb(0.35, 0.7, 0.95),
+            (Terrain::Sand, _) => Color::srgb(0.95, 0.9, 0.45),
+            (Terrain::Mountain, _) => Color::srgb(0.3, 0.28, 0.28),
+            (Terrain::Grass, Biome::Tundra) => Color::srgb(0.8, 0.82, 0.9),
+            (Terrain::Grass, Biome::Forest) => Color::srgb(0.5, 0.42, 0.1),
+            (Terrain::Grass, Biome::Desert) => Color::srgb(0.9, 0.55, 0.15),
+            (Terrain::Grass, Biome::Grassland) => Color::srgb(0.72, 0.62, 0.2),
+            (Terrain::Grass, Biome::Swamp) => Color::srgb(0.55, 0.55, 0.4),
+        })
+    }
+
+    /// Colors told apart without blue-sensitive cones, using reds and teals instead of the
+    /// blue-yellow axis
+    pub fn tritanopia() -> Self {
+        Self::from_fn(|terrain, biome| match (terrain, biome) {
+            (Terrain::Water, _) => Color::srgb(0.05, 0.2, 0.35),
+            (Terrain::River, _) => Color::srgb(0.4, 0.75, 0.8),
+            (Terrain::Sand, _) => Color::srgb(0.98, 0.75, 0.7),
+            (Terrain::Mountain, _) => Color::srgb(0.55, 0.5, 0.5),
+            (Terrain::Grass, Biome::Tundra) => Color::srgb(0.85, 0.9, 0.95),
+            (Terrain::Grass, Biome::Forest) => Color::srgb(0.55, 0.15, 0.15),
+            (Terrain::Grass, Biome::Desert) => Color::srgb(0.8, 0.42, 0.3),
+            (Terrain::Grass, Biome::Grassland) => Color::srgb(0.8, 0.3, 0.3),
+            (Terrain::Grass, Biome::Swamp) => Color::srgb(0.7, 0.2, 0.45),
+        })
+    }
+
+    /// Color of a terrain type in a biome
+    pub fn color(&self, terrain: Terrain, biome: Biome) -> Color {
+        self.0[&(terrain, biome)]
+    }
+}
+
+impl Default for TerrainPalette {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl From<ThemePreset> for TerrainPalette {
+    fn from(preset: ThemePreset) -> Self {
+        match preset {
+            ThemePreset::Dark | ThemePreset::Light => Self::standard(),
+            ThemePreset::HighContrast => Self::high_contrast(),
+        }
+    }
+}
+
+impl From<ColorblindMode> for TerrainPalette {
+    fn from(mode: ColorblindMode) -> Self {
+        match mode {
+            ColorblindMode::Off => Self::standard(),
+            ColorblindMode::Protanopia => Self::protanopia(),
+            ColorblindMode::Deuteranopia => Self::deuteranopia(),
+            ColorblindMode::Tritanopia => Self::tritanopia(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::Settings;
+
+    /// Smallest perceived difference, in Oklab units, between two terrain types in a biome
+    const MIN_DISTANCE: f32 = 0.1;
+
+    /// Color vision deficiency simulation in linear RGB, from Machado, Oliveira and Fernandes
+    /// (2009) at full severity
+    fn simulation(mode: ColorblindMode) -> Mat3 {
+        let rows = match mode {
+            ColorblindMode::Off => return Mat3::IDENTITY,
+            ColorblindMode::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorblindMode::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorblindMode::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        };
+        Mat3::from_cols_array_2d(&rows).transpose()
+    }
+
+    /// Color as seen with a deficiency, in Oklab
+    fn perceived(color: Color, mode: ColorblindMode) -> Vec3 {
+        let linear = color.to_linear();
+        let seen = (simulation(mode) * Vec3::new(linear.red, linear.green, linear.blue))
+            .clamp(Vec3::ZERO, Vec3::ONE);
+        let oklab = Oklaba::from(LinearRgba::rgb(seen.x, seen.y, seen.z));
+        Vec3::new(oklab.lightness, oklab.a, oklab.b)
+    }
+
+    fn smallest_distance(palette: &TerrainPalette, mode: ColorblindMode) -> f32 {
+        let mut smallest = f32::MAX;
+        for biome in Biome::ALL {
+            for (index, first) in Terrain::ALL.iter().enumerate() {
+                for second in &Terrain::ALL[index + 1..] {
+                    let distance = perceived(palette.color(*first, biome), mode)
+                        .distance(perceived(palette.color(*second, biome), mode));
+                    smallest = smallest.min(distance);
+                }
+            }
+        }
+        smallest
+    }
+
+    #[test]
+    fn every_pair_has_a_color() {
+        for palette in [
+            TerrainPalette::standard(),
+            TerrainPalette::high_contrast(),
+            TerrainPalette::protanopia(),
+            TerrainPalette::deuteranopia(),
+            TerrainPalette::tritanopia(),
+        ] {
+            assert_eq!(palette.0.len(), Terrain::ALL.len() * Biome::ALL.len());
+        }
+    }
+
+    #[test]
+    fn colorblind_presets_keep_terrain_types_apart() {
+        for mode in [
+            ColorblindMode::Protanopia,
+            ColorblindMode::Deuteranopia,
+            ColorblindMode::Tritanopia,
+        ] {
+            let distance = smallest_distance(&TerrainPalette::from(mode), mode);
+            assert!(distance >= MIN_DISTANCE, "{:?}: {}", mode, distance);
+        }
+    }
+
+    #[test]
+    fn standard_palette_blurs_together_for_colorblind_players() {
+        // The reason for the presets: without them some terrain types look nearly alike
+        for mode in [
+            ColorblindMode::Protanopia,
+            ColorblindMode::Deuteranopia,
+            ColorblindMode::Tritanopia,
+        ] {
+            let distance = smallest_distance(&TerrainPalette::standard(), mode);
+            assert!(distance < MIN_DISTANCE, "{:?}: {}", mode, distance);
+        }
+    }
+
+    #[test]
+    fn colorblind_mode_overrides_the_theme() {
+        let mut settings = Settings {
+            theme: ThemePreset::HighContrast,
+            ..default()
+        };
+        assert_eq!(settings.terrain_palette(), TerrainPalette::high_contrast());
+
+        settings.colorblind_mode = ColorblindMode::Tritanopia;
+        assert_eq!(settings.terrain_palette(), TerrainPalette::tritanopia());
+    }
+}
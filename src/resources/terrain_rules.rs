@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use crate::components::{EntityCategory, EntityKind, Terrain};
+
+/// What may stand on one terrain type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerrainRule {
+    /// Whether creatures can walk across the terrain
+    pub walkable: bool,
+    /// Entity categories that can be placed on the terrain
+    pub allowed: Vec<EntityCategory>,
+}
+
+impl TerrainRule {
+    pub fn new(walkable: bool, allowed: &[EntityCategory]) -> Self {
+        Self {
+            walkable,
+            allowed: allowed.to_vec(),
+        }
+    }
+}
+
+/// Placement and movement rules for each terrain type
+///
+/// Systems consult this table rather than hardcoding terrain checks, so the rules can be tuned
+/// here in one place. Terrain missing from the table allows nothing and can't be walked on.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct TerrainRules(pub HashMap<Terrain, TerrainRule>);
+
+impl TerrainRules {
+    /// Whether an entity kind can be placed on a terrain type
+    pub fn can_place(&self, kind: EntityKind, terrain: Terrain) -> bool {
+        self.0
+            .get(&terrain)
+            .is_some_and(|rule| rule.allowed.contains(&kind.category()))
+    }
+
+    /// Whether creatures can walk across a terrain type
+    pub fn is_walkable(&self, terrain: Terrain) -> bool {
+        self.0.get(&terrain).is_some_and(|rule| rule.walkable)
+    }
+}
+
+impl Default for TerrainRules {
+    fn default() -> Self {
+        use EntityCategory::*;
+
+        Self(HashMap::from([
+            (Terrain::Water, TerrainRule::new(false, &[])),
+            (Terrain::River, TerrainRule::new(false, &[])),
+            (Terrain::Sand, TerrainRule::new(true, &[Wildlife, Settler])),
+            (Terrain::Grass, TerrainRule::new(true, &[Plant, Wildlife, Settler])),
+            // Too steep to settle or grow trees on, but animals still roam the peaks
+            (Terrain::Mountain, TerrainRule::new(true, &[Wildlife])),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_terrain_has_a_rule() {
+        let rules = TerrainRules::default();
+        for terrain in Terrain::ALL {
+            assert!(rules.0.contains_key(&terrain), "{:?}", terrain);
+        }
+    }
+
+    #[test]
+    fn nothing_is_placed_on_water() {
+        let rules = TerrainRules::default();
+        for kind in EntityKind::ALL {
+            assert!(!rules.can_place(kind, Terrain::Water), "{:?}", kind);
+            assert!(!rules.can_place(kind, Terrain::River), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn kinds_follow_their_category() {
+        let rules = TerrainRules::default();
+
+        assert!(rules.can_place(EntityKind::Tree, Terrain::Grass));
+        assert!(!rules.can_place(EntityKind::Tree, Terrain::Sand));
+        assert!(rules.can_place(EntityKind::Animal, Terrain::Mountain));
+        assert!(!rules.can_place(EntityKind::Villager, Terrain::Mountain));
+        assert!(rules.can_place(EntityKind::Villager, Terrain::Sand));
+    }
+
+    #[test]
+    fn only_land_is_walkable() {
+        let rules = TerrainRules::default();
+        for terrain in Terrain::ALL {
+            assert_eq!(rules.is_walkable(terrain), !terrain.is_water(), "{:?}", terrain);
+        }
+    }
+
+    #[test]
+    fn terrain_missing_from_the_table_allows_nothing() {
+        let mut rules = TerrainRules::default();
+        rules.0.remove(&Terrain::Grass);
+
+        assert!(!rules.can_place(EntityKind::Tree, Terrain::Grass));
+        assert!(!rules.is_walkable(Terrain::Grass));
+    }
+}
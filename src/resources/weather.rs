@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+/// Current weather over the world
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Rain,
+    Storm,
+    Drought,
+}
+
+impl Weather {
+    /// Every weather type, in the order used by `WeatherConfig::transitions`
+    pub const ALL: [Weather; 4] = [Weather::Clear, Weather::Rain, Weather::Storm, Weather::Drought];
+
+    fn index(self) -> usize {
+        match self {
+            Weather::Clear => 0,
+            Weather::Rain => 1,
+            Weather::Storm => 2,
+            Weather::Drought => 3,
+        }
+    }
+
+    /// Display name of the weather
+    pub fn label(self) -> &'static str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::Rain => "Rain",
+            Weather::Storm => "Storm",
+            Weather::Drought => "Drought",
+        }
+    }
+
+    /// Screen tint drawn over the world for this weather
+    pub fn tint(self) -> Color {
+        match self {
+            Weather::Clear => Color::NONE,
+            Weather::Rain => Color::srgba(0.2, 0.3, 0.5, 0.2),
+            Weather::Storm => Color::srgba(0.1, 0.1, 0.2, 0.4),
+            Weather::Drought => Color::srgba(0.8, 0.5, 0.1, 0.15),
+        }
+    }
+
+    /// Tiles of shoreline that flood (positive) or dry out (negative) per terrain update
+    pub fn shoreline_change(self) -> i32 {
+        match self {
+            Weather::Clear => 0,
+            Weather::Rain => 2,
+            Weather::Storm => 6,
+            Weather::Drought => -4,
+        }
+    }
+}
+
+/// Tunable weather behaviour
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct WeatherConfig {
+    /// In-game hours between chances for the weather to change
+    pub change_interval_hours: f64,
+    /// Relative chance of moving from one weather to another, indexed as
+    /// `transitions[from][to]` in `Weather::ALL` order. Rows don't need to sum to 1.
+    pub transitions: [[f32; 4]; 4],
+    /// In-game hours between terrain updates caused by the weather
+    pub terrain_interval_hours: f64,
+}
+
+impl WeatherConfig {
+    /// Pick the next weather given a uniform random roll in 0.0..1.0
+    pub fn next_weather(&self, current: Weather, roll: f32) -> Weather {
+        let weights = &self.transitions[current.index()];
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            return current;
+        }
+
+        let mut remaining = roll * total;
+        for (weather, weight) in Weather::ALL.iter().zip(weights) {
+            if remaining < *weight {
+                return *weather;
+            }
+            remaining -= weight;
+        }
+        current
+    }
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        Self {
+            change_interval_hours: 6.0,
+            transitions: [
+                // Clear, Rain, Storm, Drought
+                [0.70, 0.20, 0.03, 0.07], // from Clear
+                [0.40, 0.45, 0.15, 0.00], // from Rain
+                [0.20, 0.50, 0.30, 0.00], // from Storm
+                [0.35, 0.05, 0.00, 0.60], // from Drought
+            ],
+            terrain_interval_hours: 3.0,
+        }
+    }
+}
+
+/// In-game hours at which the weather next gets a chance to change and next affects terrain
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct WeatherTimer {
+    pub next_change_hour: f64,
+    pub next_terrain_hour: f64,
+}
@@ -0,0 +1,468 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use crate::components::{Biome, EntityKind, Terrain};
+use serde::{Deserialize, Serialize};
+
+/// Size of a single tile in world units
+pub const TILE_SIZE: f32 = 32.0;
+
+/// Preset map sizes offered on the New Worldkeeper screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl MapSize {
+    /// Width and height of the map in tiles
+    pub fn dimensions(self) -> UVec2 {
+        match self {
+            MapSize::Small => UVec2::splat(48),
+            MapSize::Medium => UVec2::splat(64),
+            MapSize::Large => UVec2::splat(96),
+        }
+    }
+
+    /// Display name of the size
+    pub fn label(self) -> &'static str {
+        match self {
+            MapSize::Small => "Small",
+            MapSize::Medium => "Medium",
+            MapSize::Large => "Large",
+        }
+    }
+}
+
+/// Parameters used to generate and run a world
+///
+/// The same configuration always reproduces the same map.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldConfig {
+    pub seed: u64,
+    /// Width of the world in tiles
+    pub width: u32,
+    /// Height of the world in tiles
+    pub height: u32,
+    /// Normalized elevation below which tiles become water
+    pub water_level: f64,
+    /// Real seconds a full in-game day lasts at 1x speed
+    pub day_length_seconds: f32,
+}
+
+impl WorldConfig {
+    pub const MIN_WATER_LEVEL: f64 = 0.2;
+    pub const MAX_WATER_LEVEL: f64 = 0.6;
+    /// Increment of the water level slider
+    pub const WATER_LEVEL_STEP: f64 = 0.01;
+    pub const MIN_DAY_LENGTH: f32 = 24.0;
+    pub const MAX_DAY_LENGTH: f32 = 600.0;
+    /// Increment of the day length slider
+    pub const DAY_LENGTH_STEP: f32 = 12.0;
+
+    /// Resize the map to one of the preset sizes
+    pub fn set_map_size(&mut self, size: MapSize) {
+        let dimensions = size.dimensions();
+        self.width = dimensions.x;
+        self.height = dimensions.y;
+    }
+
+    /// Set the water level, keeping it within the allowed range
+    pub fn set_water_level(&mut self, water_level: f64) {
+        self.water_level = water_level.clamp(Self::MIN_WATER_LEVEL, Self::MAX_WATER_LEVEL);
+    }
+
+    /// Set the day length, keeping it within the allowed range
+    pub fn set_day_length(&mut self, day_length_seconds: f32) {
+        self.day_length_seconds = day_length_seconds.clamp(Self::MIN_DAY_LENGTH, Self::MAX_DAY_LENGTH);
+    }
+
+    /// Map dimensions formatted for display
+    pub fn size_label(&self) -> String {
+        format!("{} x {}", self.width, self.height)
+    }
+
+    /// Water level formatted as a percentage
+    pub fn water_level_label(&self) -> String {
+        format!("{}%", (self.water_level * 100.0).round() as u32)
+    }
+
+    /// Day length formatted as real minutes and seconds
+    pub fn day_length_label(&self) -> String {
+        let seconds = self.day_length_seconds.round() as u32;
+        format!("{}:{:02} per day", seconds / 60, seconds % 60)
+    }
+
+    /// Size of the whole world in world units
+    pub fn world_size(&self) -> Vec2 {
+        Vec2::new(self.width as f32, self.height as f32) * TILE_SIZE
+    }
+
+    /// World extents, with the map centered on the origin
+    pub fn bounds(&self) -> WorldBounds {
+        let half_size = self.world_size() / 2.0;
+        WorldBounds::new(-half_size, half_size)
+    }
+
+    /// World position of the center of a tile
+    pub fn tile_center(&self, coord: UVec2) -> Vec2 {
+        self.bounds().min + (coord.as_vec2() + Vec2::splat(0.5)) * TILE_SIZE
+    }
+
+    /// Grid coordinates of the tile containing a world position, if it is inside the map
+    pub fn tile_at(&self, position: Vec2) -> Option<UVec2> {
+        let local = (position - self.bounds().min) / TILE_SIZE;
+        if local.x < 0.0 || local.y < 0.0 {
+            return None;
+        }
+        let coord = local.floor().as_uvec2();
+        (coord.x < self.width && coord.y < self.height).then_some(coord)
+    }
+}
+
+/// Parse a player-entered seed, returning `None` for empty or non-numeric input
+pub fn parse_seed(input: &str) -> Option<u64> {
+    input.trim().parse().ok()
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        let dimensions = MapSize::Medium.dimensions();
+        Self {
+            seed: 0,
+            width: dimensions.x,
+            height: dimensions.y,
+            water_level: 0.42,
+            day_length_seconds: 120.0,
+        }
+    }
+}
+
+/// Tools on the entity toolbar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlacementTool {
+    /// Place an entity of a kind on the clicked tile
+    Place(EntityKind),
+    /// Remove the placed entity on the clicked tile
+    Delete,
+}
+
+impl PlacementTool {
+    /// Every tool, in toolbar order
+    pub const ALL: [PlacementTool; 4] = [
+        PlacementTool::Place(EntityKind::Tree),
+        PlacementTool::Place(EntityKind::Animal),
+        PlacementTool::Place(EntityKind::Villager),
+        PlacementTool::Delete,
+    ];
+
+    /// Display name shown on the toolbar
+    pub fn name(self) -> &'static str {
+        match self {
+            PlacementTool::Place(kind) => kind.name(),
+            PlacementTool::Delete => "Delete",
+        }
+    }
+}
+
+/// Toolbar tool applied by left-clicking the map, or `None` when no tool is selected
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectedTool(pub Option<PlacementTool>);
+
+/// Whether tile grid lines are drawn over the map, toggled with the grid key
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShowGrid(pub bool);
+
+/// Tile entity the player has clicked to inspect, if any
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectedTile(pub Option<Entity>);
+
+/// Grid coordinates of the tile under the cursor, or `None` when the cursor is off the map,
+/// outside the window or over the UI
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HoveredTile(pub Option<UVec2>);
+
+/// Placed entities the player has drag-selected on the map
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection(pub Vec<Entity>);
+
+/// Left-button press on the map that may turn into a drag-select
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct SelectionDrag {
+    /// Cursor position in the window where the press started, or `None` when not pressed
+    pub start_screen: Option<Vec2>,
+    /// The same position in the world
+    pub start_world: Vec2,
+}
+
+impl SelectionDrag {
+    /// Pixels the cursor must move before a press counts as a drag instead of a click
+    pub const THRESHOLD: f32 = 6.0;
+
+    /// Whether the cursor has moved far enough from the press to be dragging
+    pub fn is_dragging(&self, cursor: Vec2) -> bool {
+        self.start_screen
+            .is_some_and(|start| start.distance(cursor) >= Self::THRESHOLD)
+    }
+}
+
+/// Tile entity at each grid coordinate, so the tile at a point can be found without searching
+/// every tile
+///
+/// Filled in as world generation spawns the tiles, and emptied when it starts again.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TileEntities(pub HashMap<UVec2, Entity>);
+
+impl TileEntities {
+    pub fn get(&self, coord: UVec2) -> Option<Entity> {
+        self.0.get(&coord).copied()
+    }
+}
+
+/// Shared material for each terrain type in each biome, created when the world is generated
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TerrainMaterials(pub HashMap<(Terrain, Biome), Handle<ColorMaterial>>);
+
+impl TerrainMaterials {
+    pub fn get(&self, terrain: Terrain, biome: Biome) -> Handle<ColorMaterial> {
+        self.0[&(terrain, biome)].clone()
+    }
+}
+
+/// Mesh shared by every placed entity, and the material for each kind
+#[derive(Resource, Debug, Clone)]
+pub struct PlacedAssets {
+    pub mesh: Handle<Mesh>,
+    pub materials: HashMap<EntityKind, Handle<ColorMaterial>>,
+}
+
+impl FromWorld for PlacedAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Circle::new(TILE_SIZE * 0.35));
+        let mut color_materials = world.resource_mut::<Assets<ColorMaterial>>();
+        let materials = EntityKind::ALL
+            .iter()
+            .map(|kind| (*kind, color_materials.add(kind.color())))
+            .collect();
+        Self { mesh, materials }
+    }
+}
+
+/// Minimum elevation and moisture for a tile to belong to a biome
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiomeThreshold {
+    pub biome: Biome,
+    pub min_elevation: f64,
+    pub min_moisture: f64,
+}
+
+impl BiomeThreshold {
+    pub const fn new(biome: Biome, min_elevation: f64, min_moisture: f64) -> Self {
+        Self {
+            biome,
+            min_elevation,
+            min_moisture,
+        }
+    }
+}
+
+/// Table used by world generation to pick each tile's biome
+///
+/// Thresholds are checked in order and the first one the tile meets wins, so list the most
+/// specific biomes first. Tiles that meet none get the fallback biome.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct BiomeTable {
+    pub thresholds: Vec<BiomeThreshold>,
+    pub fallback: Biome,
+}
+
+impl BiomeTable {
+    /// Biome for a normalized elevation and moisture, both in the range 0.0..=1.0
+    pub fn classify(&self, elevation: f64, moisture: f64) -> Biome {
+        self.thresholds
+            .iter()
+            .find(|threshold| elevation >= threshold.min_elevation && moisture >= threshold.min_moisture)
+            .map_or(self.fallback, |threshold| threshold.biome)
+    }
+}
+
+impl Default for BiomeTable {
+    fn default() -> Self {
+        Self {
+            thresholds: vec![
+                // Cold highlands
+                BiomeThreshold::new(Biome::Tundra, 0.62, 0.0),
+                BiomeThreshold::new(Biome::Swamp, 0.0, 0.65),
+                BiomeThreshold::new(Biome::Forest, 0.0, 0.52),
+                BiomeThreshold::new(Biome::Grassland, 0.0, 0.35),
+            ],
+            fallback: Biome::Desert,
+        }
+    }
+}
+
+/// How far world generation has got, read by the loading screen
+///
+/// The map is built in passes on a background task, then its tiles are spawned a budget at a
+/// time. Each stage fills half of the loading bar.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorldGenProgress {
+    /// Map generation passes the background task has finished
+    pub passes: u32,
+    pub generated: u32,
+    pub total: u32,
+}
+
+impl WorldGenProgress {
+    /// Number of passes the background task makes over the map
+    pub const MAP_PASSES: u32 = 3;
+
+    pub fn new(total: u32) -> Self {
+        Self {
+            passes: 0,
+            generated: 0,
+            total,
+        }
+    }
+
+    /// Whether the background task has finished building the map
+    pub fn is_map_ready(&self) -> bool {
+        self.passes >= Self::MAP_PASSES
+    }
+
+    /// Share of world generation done, in the range 0.0..=1.0
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            let mapped = (self.passes as f32 / Self::MAP_PASSES as f32).min(1.0);
+            let spawned = (self.generated as f32 / self.total as f32).min(1.0);
+            (mapped + spawned) / 2.0
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.generated >= self.total
+    }
+}
+
+/// Maximum number of tiles world generation spawns per frame
+///
+/// Lower values load more slowly but keep each frame short on low-end machines.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldGenBudget {
+    pub tiles_per_frame: u32,
+}
+
+impl Default for WorldGenBudget {
+    fn default() -> Self {
+        Self {
+            tiles_per_frame: 1024,
+        }
+    }
+}
+
+/// How world generation draws the tiles
+///
+/// `PerTile` gives every tile its own mesh entity, one draw each. `Batched` merges the tiles of
+/// each generation chunk into a single vertex-colored mesh: a Medium 64x64 map draws 16 chunk
+/// meshes instead of 4,096 tile meshes, and a Large 96x96 map 36 instead of 9,216. Tiles stay
+/// as entities either way, but batched tiles carry no mesh, transform or visibility.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TileRendering {
+    PerTile,
+    #[default]
+    Batched,
+}
+
+/// Extents of the generated world in world units
+///
+/// World generation sets these after creating the map so the camera can't pan into empty space.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct WorldBounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl WorldBounds {
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+
+    /// Size of the world in world units
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// Center point of the world
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Clamp a camera position so a viewport of the given size stays inside the bounds
+    ///
+    /// On any axis where the world is smaller than the viewport the camera is centered instead.
+    pub fn clamp_camera_position(&self, position: Vec2, viewport_size: Vec2) -> Vec2 {
+        let half_viewport = viewport_size / 2.0;
+        let clamp_axis = |position: f32, min: f32, max: f32, half_viewport: f32| {
+            if max - min <= half_viewport * 2.0 {
+                (min + max) / 2.0
+            } else {
+                position.clamp(min + half_viewport, max - half_viewport)
+            }
+        };
+
+        Vec2::new(
+            clamp_axis(position.x, self.min.x, self.max.x, half_viewport.x),
+            clamp_axis(position.y, self.min.y, self.max.y, half_viewport.y),
+        )
+    }
+}
+
+impl Default for WorldBounds {
+    fn default() -> Self {
+        Self::new(Vec2::splat(-1000.0), Vec2::splat(1000.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ResourceKind;
+
+    #[test]
+    fn first_matching_biome_wins() {
+        let table = BiomeTable::default();
+
+        // High and wet land meets both the tundra and swamp thresholds, and tundra is listed first
+        assert_eq!(table.classify(0.9, 0.9), Biome::Tundra);
+        assert_eq!(table.classify(0.3, 0.7), Biome::Swamp);
+        assert_eq!(table.classify(0.3, 0.55), Biome::Forest);
+        assert_eq!(table.classify(0.3, 0.4), Biome::Grassland);
+    }
+
+    #[test]
+    fn tiles_meeting_no_threshold_get_the_fallback() {
+        let table = BiomeTable::default();
+        assert_eq!(table.classify(0.3, 0.1), table.fallback);
+
+        let empty = BiomeTable {
+            thresholds: Vec::new(),
+            fallback: Biome::Forest,
+        };
+        assert_eq!(empty.classify(1.0, 1.0), Biome::Forest);
+    }
+
+    #[test]
+    fn biomes_scale_terrain_yields() {
+        let grass = ResourceKind::Food.terrain_yield(Terrain::Grass);
+        assert!(ResourceKind::Food.tile_yield(Terrain::Grass, Biome::Grassland) > grass);
+        assert!(ResourceKind::Food.tile_yield(Terrain::Grass, Biome::Desert) < grass);
+        assert_eq!(ResourceKind::Food.tile_yield(Terrain::Grass, Biome::Forest), grass);
+        assert_eq!(ResourceKind::Food.tile_yield(Terrain::Mountain, Biome::Grassland), 0.0);
+    }
+}
@@ -0,0 +1,745 @@
+//! Saving and loading the game state as JSON save files in the saves directory, and the
+//! player's settings and key bindings as JSON files in the platform's config directory.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{EntityKind, Terrain};
+use crate::resources::{KeyBindings, Resources, Settings, WorldConfig};
+
+/// Directory, relative to the working directory, that holds save files
+pub const SAVE_DIRECTORY: &str = "saves";
+/// Name of the save written by the pause menu
+pub const DEFAULT_SAVE_NAME: &str = "world";
+/// Number of autosave files, overwritten in turn
+pub const AUTOSAVE_SLOTS: u32 = 3;
+/// Version of the save format written by this build
+///
+/// Bump it whenever the format changes in a way older builds can't read, and add a migration
+/// from the previous version to `MIGRATIONS`.
+pub const SAVE_VERSION: u32 = 2;
+/// File extension used for save files
+pub const SAVE_EXTENSION: &str = "save";
+/// File extension of saves written before saves had their own extension, which still load
+pub const LEGACY_SAVE_EXTENSION: &str = "json";
+/// Settings file name inside the config directory
+///
+/// Older builds wrote it to the working directory, where it is still read from as a fallback.
+pub const SETTINGS_FILE: &str = "settings.json";
+/// Key bindings file name inside the config directory, also read from the working directory
+/// as a fallback like `SETTINGS_FILE`
+pub const KEY_BINDINGS_FILE: &str = "key_bindings.json";
+
+/// Why a save couldn't be written or read
+#[derive(Debug)]
+pub enum SaveError {
+    /// The file couldn't be read or written
+    Io(io::Error),
+    /// The game state couldn't be encoded, or the file isn't a valid save
+    Serialization(serde_json::Error),
+    /// The save was written by a newer version of the game than this one
+    Version { found: u32, supported: u32 },
+    /// No save file exists at the path
+    NotFound(PathBuf),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "{}", error),
+            SaveError::Serialization(error) => write!(f, "invalid save data: {}", error),
+            SaveError::Version { found, supported } => write!(
+                f,
+                "save format v{} is newer than the supported v{}",
+                found, supported
+            ),
+            SaveError::NotFound(path) => write!(f, "no save at {}", path.display()),
+        }
+    }
+}
+
+impl Error for SaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SaveError::Io(error) => Some(error),
+            SaveError::Serialization(error) => Some(error),
+            SaveError::Version { .. } | SaveError::NotFound(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(error: serde_json::Error) -> Self {
+        SaveError::Serialization(error)
+    }
+}
+
+/// Camera position and zoom stored in a save
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraSave {
+    pub translation: [f32; 2],
+    pub scale: f32,
+}
+
+impl Default for CameraSave {
+    fn default() -> Self {
+        Self {
+            translation: [0.0, 0.0],
+            scale: 1.0,
+        }
+    }
+}
+
+/// An entity the player placed, respawned on its tile when the save is loaded
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlacedSave {
+    pub kind: EntityKind,
+    pub coord: [u32; 2],
+    pub health: f32,
+}
+
+/// A tile whose terrain no longer matches what its world config generates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TerrainSave {
+    pub coord: [u32; 2],
+    pub terrain: Terrain,
+}
+
+/// Summary written at the top of each save so the saves screen can list it cheaply
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveHeader {
+    /// Name the player gave the save, which may contain characters its file name can't
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Seconds since the Unix epoch when the save was written
+    pub saved_at: u64,
+    pub seed: u64,
+}
+
+impl SaveHeader {
+    /// Header for a named save of a world with the given seed, written right now
+    pub fn now(name: impl Into<String>, seed: u64) -> Self {
+        Self {
+            name: Some(name.into()),
+            saved_at: unix_timestamp(),
+            seed,
+        }
+    }
+}
+
+/// Snapshot of the game state written to disk
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SaveData {
+    /// Save format version, 0 for saves made before the format was versioned
+    #[serde(default)]
+    pub version: u32,
+    /// Missing from saves made before the saves screen existed
+    #[serde(default)]
+    pub header: Option<SaveHeader>,
+    /// Generation parameters, which reproduce the terrain when loaded
+    #[serde(default)]
+    pub world_config: WorldConfig,
+    #[serde(default)]
+    pub camera: CameraSave,
+    /// In-game hours elapsed on the simulation clock
+    #[serde(default)]
+    pub simulation_hours: f64,
+    /// Population count, missing from saves made before population was tracked
+    #[serde(default)]
+    pub population: Option<u32>,
+    /// Stockpiled resources, missing from saves made before the economy existed
+    #[serde(default)]
+    pub resources: Option<Resources>,
+    /// Position in the gameplay RNG stream, missing from saves made before it was seeded
+    #[serde(default)]
+    pub rng_word_pos: Option<u128>,
+    /// Whether the world is played in sandbox mode, off for saves made before it existed
+    #[serde(default)]
+    pub sandbox: bool,
+    /// Entities the player placed, missing from saves made before they were saved
+    #[serde(default)]
+    pub placed: Option<Vec<PlacedSave>>,
+    /// Tiles changed since the world was generated, by terrain edits, disasters or weather,
+    /// missing from saves made before terrain changes were saved
+    #[serde(default)]
+    pub terrain_changes: Option<Vec<TerrainSave>>,
+}
+
+/// Whether the world has changed since it was last saved
+///
+/// Set while the simulation runs and cleared by saves and autosaves.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnsavedChanges(pub bool);
+
+/// A save file as listed on the saves screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSlot {
+    /// Name from the header, or the file name without its extension for older saves
+    pub name: String,
+    pub path: PathBuf,
+    pub header: SaveHeader,
+}
+
+/// Only the parts of a save file needed to list it
+///
+/// Every other field is skipped over without being built.
+#[derive(Deserialize)]
+struct SaveSummary {
+    #[serde(default)]
+    header: Option<SaveHeader>,
+    /// Older saves have no header, but their seed can still be read from the world config
+    #[serde(default)]
+    world_config: Option<SeedOnly>,
+}
+
+#[derive(Deserialize)]
+struct SeedOnly {
+    seed: u64,
+}
+
+/// Only the format version of a save file, read before the rest of it
+#[derive(Deserialize)]
+struct VersionOnly {
+    #[serde(default)]
+    version: u32,
+}
+
+/// Upgrades from each save version to the next, indexed by the version they upgrade from
+const MIGRATIONS: [fn(&mut serde_json::Map<String, serde_json::Value>); SAVE_VERSION as usize] =
+    [migrate_v0_to_v1, migrate_v1_to_v2];
+
+/// Unversioned saves have every v1 field already, or load its default, so only the version
+/// number changes
+fn migrate_v0_to_v1(_save: &mut serde_json::Map<String, serde_json::Value>) {}
+
+/// Placed entities and terrain changes weren't written before v2, so they were lost and the
+/// world loads as generated
+fn migrate_v1_to_v2(_save: &mut serde_json::Map<String, serde_json::Value>) {}
+
+/// Saves listed on the saves screen, read when it opens and after each change
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveSlots(pub Vec<SaveSlot>);
+
+/// Save data waiting to be applied once the game world has been set up
+#[derive(Resource, Debug, Clone)]
+pub struct PendingLoad(pub SaveData);
+
+/// Platform config directory for the player's settings and key bindings
+///
+/// For example `~/.config/worldkeeper` on Linux or `%APPDATA%\WorldKeeper\config` on Windows.
+/// Falls back to the working directory on platforms without one.
+pub fn config_dir() -> PathBuf {
+    ProjectDirs::from("", "", "WorldKeeper")
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .unwrap_or_default()
+}
+
+/// Path of the settings file in the config directory
+pub fn settings_path() -> PathBuf {
+    config_dir().join(SETTINGS_FILE)
+}
+
+/// Path of the key bindings file in the config directory
+pub fn key_bindings_path() -> PathBuf {
+    config_dir().join(KEY_BINDINGS_FILE)
+}
+
+/// Path of the save file with the given name inside the saves directory
+pub fn save_path(name: &str) -> PathBuf {
+    Path::new(SAVE_DIRECTORY).join(name).with_extension(SAVE_EXTENSION)
+}
+
+/// Name of the save file for an autosave slot, counting slots from 0
+pub fn autosave_name(slot: u32) -> String {
+    format!("autosave_{}", slot % AUTOSAVE_SLOTS + 1)
+}
+
+/// Most recently modified save file in the saves directory, including autosaves
+pub fn latest_save() -> Option<PathBuf> {
+    fs::read_dir(SAVE_DIRECTORY)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| is_save_file(path))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Every readable save in the saves directory, most recently saved first
+pub fn list_saves() -> Vec<SaveSlot> {
+    list_saves_in(Path::new(SAVE_DIRECTORY))
+}
+
+/// Every readable save in a directory, most recently saved first
+///
+/// Only each file's header is read. Files that can't be read are skipped with a warning.
+pub fn list_saves_in(directory: &Path) -> Vec<SaveSlot> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut slots: Vec<SaveSlot> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| is_save_file(path))
+        .filter_map(|path| match read_save_slot(&path) {
+            Ok(slot) => Some(slot),
+            Err(error) => {
+                warn!("Skipping unreadable save {}: {}", path.display(), error);
+                None
+            }
+        })
+        .collect();
+    slots.sort_by(|a, b| b.header.saved_at.cmp(&a.header.saved_at).then_with(|| a.name.cmp(&b.name)));
+    slots
+}
+
+/// Read the listing details of one save file
+///
+/// Saves without a header fall back to the file's modification time.
+pub fn read_save_slot(path: &Path) -> io::Result<SaveSlot> {
+    let json = fs::read_to_string(path)?;
+    let summary: SaveSummary = serde_json::from_str(&json)?;
+    let header = match summary.header {
+        Some(header) => header,
+        None => {
+            let saved_at = fs::metadata(path)?
+                .modified()?
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default();
+            let seed = summary.world_config.map(|config| config.seed).unwrap_or_default();
+            SaveHeader {
+                name: None,
+                saved_at,
+                seed,
+            }
+        }
+    };
+
+    Ok(SaveSlot {
+        name: header.name.clone().unwrap_or_else(|| file_stem(path)),
+        path: path.to_path_buf(),
+        header,
+    })
+}
+
+/// Seconds since the Unix epoch, as stored in save headers
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Delete a save file
+pub fn delete_save(path: &Path) -> io::Result<()> {
+    fs::remove_file(path)
+}
+
+/// Give a save a new name, moving it to the matching file in the same directory and returning
+/// its new path
+///
+/// Names with nothing left once sanitized, or whose file belongs to another save, are rejected.
+pub fn rename_save(path: &Path, new_name: &str) -> Result<PathBuf, SaveError> {
+    let new_name = new_name.trim();
+    let file_name = sanitize_save_name(new_name);
+    if file_name.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid save name").into());
+    }
+    let new_path = path.with_file_name(file_name).with_extension(SAVE_EXTENSION);
+    if new_path != path && new_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "a save with that name already exists",
+        )
+        .into());
+    }
+
+    let mut save_data = load_game(path)?;
+    let slot = read_save_slot(path)?;
+    // Nothing to rewrite when the save already has this name
+    if new_path == path && slot.name == new_name {
+        return Ok(new_path);
+    }
+
+    let mut header = slot.header;
+    header.name = Some(new_name.to_string());
+    save_data.header = Some(header);
+    save_game(&save_data, &new_path)?;
+    if new_path != path {
+        fs::remove_file(path)?;
+    }
+    Ok(new_path)
+}
+
+/// File name, without the extension, for a save with the given name
+///
+/// Keeps letters, digits, spaces, dashes and underscores, so the result can never escape the
+/// saves directory or be rejected by the file system.
+pub fn sanitize_save_name(name: &str) -> String {
+    let kept: String = name
+        .chars()
+        .filter(|character| character.is_alphanumeric() || matches!(character, ' ' | '-' | '_'))
+        .collect();
+    kept.trim().to_string()
+}
+
+/// Whether a path has a save file extension, current or legacy
+fn is_save_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == SAVE_EXTENSION || extension == LEGACY_SAVE_EXTENSION)
+}
+
+/// File name without its extension
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Serialize the game state to a JSON file, creating the saves directory if needed
+pub fn save_game(world_state: &SaveData, path: &Path) -> Result<(), SaveError> {
+    let json = serde_json::to_string_pretty(world_state)?;
+    write_atomically(&json, path)?;
+    Ok(())
+}
+
+/// Read a game state from a JSON file
+///
+/// Missing files and corrupt contents are returned as errors rather than panicking.
+pub fn load_game(path: &Path) -> Result<SaveData, SaveError> {
+    let json = fs::read_to_string(path).map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => SaveError::NotFound(path.to_path_buf()),
+        _ => SaveError::Io(error),
+    })?;
+    parse_save(&json)
+}
+
+/// Parse a save, upgrading saves from older versions to `SAVE_VERSION`
+///
+/// Saves from a newer version of the game are rejected with `SaveError::Version` rather than
+/// loaded with their new fields silently dropped.
+pub fn parse_save(json: &str) -> Result<SaveData, SaveError> {
+    let VersionOnly { version } = serde_json::from_str(json)?;
+    if version > SAVE_VERSION {
+        return Err(SaveError::Version {
+            found: version,
+            supported: SAVE_VERSION,
+        });
+    }
+    if version == SAVE_VERSION {
+        return Ok(serde_json::from_str(json)?);
+    }
+
+    let mut save: serde_json::Map<String, serde_json::Value> = serde_json::from_str(json)?;
+    for migrate in &MIGRATIONS[version as usize..] {
+        migrate(&mut save);
+    }
+    save.insert("version".to_string(), SAVE_VERSION.into());
+    Ok(serde_json::from_value(save.into())?)
+}
+
+/// Serialize the player's settings to a JSON file
+pub fn save_settings(settings: &Settings, path: &Path) -> io::Result<()> {
+    write_json(settings, path)
+}
+
+/// Read the player's settings from a JSON file
+///
+/// Fields missing from the file keep their default values, and values out of range are
+/// clamped back into it.
+pub fn load_settings(path: &Path) -> io::Result<Settings> {
+    let json = fs::read_to_string(path)?;
+    let mut settings: Settings = serde_json::from_str(&json)?;
+    settings.clamp_to_limits();
+    Ok(settings)
+}
+
+/// Serialize the player's key bindings to a JSON file
+pub fn save_key_bindings(key_bindings: &KeyBindings, path: &Path) -> io::Result<()> {
+    write_json(key_bindings, path)
+}
+
+/// Read the player's key bindings from a JSON file
+///
+/// Actions missing from the file keep their default keys.
+pub fn load_key_bindings(path: &Path) -> io::Result<KeyBindings> {
+    let json = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Move a file aside with `.bak` appended to its name, replacing any earlier backup
+///
+/// Used for settings and key bindings files that can't be parsed, so the defaults written in
+/// their place don't destroy the player's hand edits.
+pub fn back_up_file(path: &Path) -> io::Result<PathBuf> {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+/// Write a value as pretty JSON, creating parent directories if needed
+fn write_json(value: &impl Serialize, path: &Path) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    write_atomically(&json, path)
+}
+
+/// Write a file's contents, creating parent directories if needed
+fn write_atomically(contents: &str, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Write to a temporary file first so a crash mid-write can't corrupt an existing file
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Save written by the first versioned build, kept on disk so the format can't drift
+    const V1_FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/save_v1.save");
+
+    #[test]
+    fn v1_fixture_still_loads() {
+        let save = load_game(Path::new(V1_FIXTURE)).expect("v1 fixture should load");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.header.and_then(|header| header.name).as_deref(), Some("Fixture world"));
+        assert_eq!(save.world_config.seed, 42);
+        assert_eq!(save.camera.translation, [128.0, -64.0]);
+        assert_eq!(save.simulation_hours, 36.5);
+        assert_eq!(save.population, Some(120));
+        assert!(save.resources.is_some());
+        assert_eq!(save.rng_word_pos, Some(4096));
+        // v1 saves didn't record placed entities or terrain changes
+        assert_eq!(save.placed, None);
+        assert_eq!(save.terrain_changes, None);
+    }
+
+    #[test]
+    fn unversioned_saves_are_upgraded() {
+        let save = parse_save(r#"{"world_config": {"seed": 7}, "population": 12}"#).unwrap();
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.world_config.seed, 7);
+        assert_eq!(save.population, Some(12));
+    }
+
+    #[test]
+    fn sandbox_flag_round_trips_and_defaults_to_off() {
+        let save = SaveData {
+            version: SAVE_VERSION,
+            sandbox: true,
+            ..default()
+        };
+        let json = serde_json::to_string(&save).unwrap();
+        assert!(parse_save(&json).unwrap().sandbox);
+
+        let older = format!(r#"{{"version": {}, "world_config": {{"seed": 3}}}}"#, SAVE_VERSION);
+        assert!(!parse_save(&older).unwrap().sandbox);
+        assert!(!load_game(Path::new(V1_FIXTURE)).unwrap().sandbox);
+    }
+
+    #[test]
+    fn placed_entities_and_terrain_changes_round_trip() {
+        let save = SaveData {
+            version: SAVE_VERSION,
+            placed: Some(vec![PlacedSave {
+                kind: EntityKind::Animal,
+                coord: [4, 9],
+                health: 62.5,
+            }]),
+            terrain_changes: Some(vec![TerrainSave {
+                coord: [2, 3],
+                terrain: Terrain::Mountain,
+            }]),
+            ..default()
+        };
+        let json = serde_json::to_string(&save).unwrap();
+
+        assert_eq!(parse_save(&json).unwrap(), save);
+    }
+
+    #[test]
+    fn saves_from_newer_versions_are_rejected() {
+        let json = format!(r#"{{"version": {}}}"#, SAVE_VERSION + 1);
+        let error = parse_save(&json).unwrap_err();
+
+        assert!(matches!(
+            error,
+            SaveError::Version { found, supported: SAVE_VERSION } if found == SAVE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn corrupt_saves_are_serialization_errors() {
+        for json in ["", "not a save", r#"{"version": 1, "world_config": "oops"}"#, r#"{"version": "1"}"#] {
+            let error = parse_save(json).unwrap_err();
+            assert!(matches!(error, SaveError::Serialization(_)), "{:?}: {:?}", json, error);
+        }
+    }
+
+    #[test]
+    fn missing_saves_are_not_found() {
+        let path = std::env::temp_dir().join("worldkeeper-test-missing.save");
+        let error = load_game(&path).unwrap_err();
+
+        assert!(matches!(&error, SaveError::NotFound(missing) if *missing == path));
+    }
+
+    #[test]
+    fn corrupt_settings_are_backed_up_before_defaults_replace_them() {
+        let directory = std::env::temp_dir().join("worldkeeper-test-backup");
+        let path = directory.join(SETTINGS_FILE);
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+
+        let error = load_settings(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let backup = back_up_file(&path).unwrap();
+        assert_eq!(backup, directory.join("settings.json.bak"));
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "{ not json");
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    /// Empty scratch directory for a test's save files
+    fn test_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("worldkeeper-test-{}", name));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn named_save(name: &str, saved_at: u64) -> SaveData {
+        SaveData {
+            version: SAVE_VERSION,
+            header: Some(SaveHeader {
+                name: Some(name.to_string()),
+                saved_at,
+                seed: 7,
+            }),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn saves_are_listed_newest_first_skipping_other_files() {
+        let directory = test_directory("list");
+        save_game(&named_save("Older", 100), &directory.join("older.save")).unwrap();
+        save_game(&named_save("Newer", 200), &directory.join("newer.save")).unwrap();
+        fs::write(directory.join("broken.save"), "not a save").unwrap();
+        fs::write(directory.join("notes.txt"), "not a save either").unwrap();
+
+        let names: Vec<String> = list_saves_in(&directory)
+            .into_iter()
+            .map(|slot| slot.name)
+            .collect();
+        assert_eq!(names, vec!["Newer", "Older"]);
+        assert!(list_saves_in(&directory.join("missing")).is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn renamed_saves_move_to_their_new_file() {
+        let directory = test_directory("rename");
+        let path = directory.join("old.save");
+        save_game(&named_save("Old", 100), &path).unwrap();
+
+        let new_path = rename_save(&path, "  New world!  ").unwrap();
+
+        assert_eq!(new_path, directory.join("New world.save"));
+        assert!(!path.exists());
+        let slot = read_save_slot(&new_path).unwrap();
+        assert_eq!(slot.name, "New world!");
+        assert_eq!(slot.header.saved_at, 100);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn save_names_are_sanitized_for_the_file_system() {
+        assert_eq!(sanitize_save_name("My World"), "My World");
+        assert_eq!(sanitize_save_name("  padded  "), "padded");
+        assert_eq!(sanitize_save_name("../../etc/passwd"), "etcpasswd");
+        assert_eq!(sanitize_save_name("a:b*c?d<e>f|g\\h"), "abcdefgh");
+        assert_eq!(sanitize_save_name("día_1-α"), "día_1-α");
+        assert_eq!(sanitize_save_name("?!*"), "");
+    }
+
+    #[test]
+    fn renaming_a_save_to_its_own_name_leaves_it_untouched() {
+        let directory = test_directory("rename-unchanged");
+        let path = directory.join("Same.save");
+        save_game(&named_save("Same", 100), &path).unwrap();
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+
+        assert_eq!(rename_save(&path, " Same ").unwrap(), path);
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), modified);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn failed_renames_report_why() {
+        let directory = test_directory("rename-errors");
+        let path = directory.join("first.save");
+        save_game(&named_save("First", 100), &path).unwrap();
+        save_game(&named_save("Second", 200), &directory.join("Second.save")).unwrap();
+        let corrupt = directory.join("corrupt.save");
+        fs::write(&corrupt, "not a save").unwrap();
+        let missing = directory.join("missing.save");
+
+        let error = rename_save(&path, " ?! ").unwrap_err();
+        assert!(matches!(&error, SaveError::Io(error) if error.kind() == io::ErrorKind::InvalidInput));
+        let error = rename_save(&path, "Second").unwrap_err();
+        assert!(matches!(&error, SaveError::Io(error) if error.kind() == io::ErrorKind::AlreadyExists));
+        let error = rename_save(&missing, "Third").unwrap_err();
+        assert!(matches!(&error, SaveError::NotFound(not_found) if *not_found == missing));
+        let error = rename_save(&corrupt, "Fourth").unwrap_err();
+        assert!(matches!(error, SaveError::Serialization(_)), "{:?}", error);
+
+        // Nothing was moved by the failed renames
+        assert!(path.exists() && corrupt.exists());
+        assert_eq!(read_save_slot(&path).unwrap().name, "First");
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn unreadable_saves_are_io_errors() {
+        // A directory exists but can't be read as a file
+        let error = load_game(&std::env::temp_dir()).unwrap_err();
+
+        assert!(matches!(error, SaveError::Io(_)), "{:?}", error);
+        assert!(error.source().is_some());
+    }
+}
@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+use crate::components::placeable::{EntityKind, Placed};
+use crate::components::tile::{Terrain, Tile};
+use crate::events::{Notification, PlaySoundEffect};
+use crate::resources::{
+    ActionHistory, DivinePower, PlacedAssets, SoundEffect, TerrainMaterials, WorldConfig,
+    WorldEdit,
+};
+use crate::systems::placement::spawn_placed;
+
+const UNDO_KEY: KeyCode = KeyCode::KeyZ;
+const REDO_KEY: KeyCode = KeyCode::KeyY;
+
+/// Start each new world with nothing to undo or redo
+pub fn reset_action_history(mut history: ResMut<ActionHistory>) {
+    *history = ActionHistory::default();
+}
+
+/// Undo the most recent world edit with Ctrl+Z, refunding any mana it cost
+#[allow(clippy::too_many_arguments)]
+pub fn undo_world_edit(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<WorldConfig>,
+    terrain_materials: Res<TerrainMaterials>,
+    placed_assets: Res<PlacedAssets>,
+    mut history: ResMut<ActionHistory>,
+    mut divine_power: ResMut<DivinePower>,
+    mut tile_query: Query<(&mut Tile, &mut Handle<ColorMaterial>)>,
+    placed_query: Query<(Entity, &Placed)>,
+) {
+    if !ctrl_just_pressed(&keyboard_input, UNDO_KEY) {
+        return;
+    }
+    let Some(edit) = history.undo() else {
+        return;
+    };
+
+    match edit {
+        WorldEdit::Terrain { changes, .. } => {
+            for change in changes {
+                set_terrain(&mut tile_query, &terrain_materials, change.coord, change.before);
+            }
+        }
+        WorldEdit::Place { kind, coord } => {
+            despawn_placed(&mut commands, &placed_query, *kind, *coord);
+        }
+        WorldEdit::Delete { kind, coord } => {
+            spawn_placed(&mut commands, &placed_assets, &config, *kind, *coord);
+        }
+    }
+    divine_power.refund(edit.mana_cost());
+}
+
+/// Redo the most recently undone world edit with Ctrl+Y, charging its mana again
+///
+/// A redo that can't be paid for is rejected with a sound and stays available.
+#[allow(clippy::too_many_arguments)]
+pub fn redo_world_edit(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<WorldConfig>,
+    terrain_materials: Res<TerrainMaterials>,
+    placed_assets: Res<PlacedAssets>,
+    mut history: ResMut<ActionHistory>,
+    mut divine_power: ResMut<DivinePower>,
+    mut tile_query: Query<(&mut Tile, &mut Handle<ColorMaterial>)>,
+    placed_query: Query<(Entity, &Placed)>,
+    mut sounds: EventWriter<PlaySoundEffect>,
+    mut notifications: EventWriter<Notification>,
+) {
+    if !ctrl_just_pressed(&keyboard_input, REDO_KEY) {
+        return;
+    }
+    let Some(cost) = history.next_redo().map(WorldEdit::mana_cost) else {
+        return;
+    };
+    if divine_power.try_spend(cost).is_err() {
+        notifications.send(Notification::new("Not enough mana"));
+        sounds.send(PlaySoundEffect(SoundEffect::Reject));
+        return;
+    }
+    let Some(edit) = history.redo() else {
+        return;
+    };
+
+    match edit {
+        WorldEdit::Terrain { changes, .. } => {
+            for change in changes {
+                set_terrain(&mut tile_query, &terrain_materials, change.coord, change.after);
+            }
+        }
+        WorldEdit::Place { kind, coord } => {
+            spawn_placed(&mut commands, &placed_assets, &config, *kind, *coord);
+        }
+        WorldEdit::Delete { kind, coord } => {
+            despawn_placed(&mut commands, &placed_query, *kind, *coord);
+        }
+    }
+}
+
+fn ctrl_just_pressed(keyboard_input: &ButtonInput<KeyCode>, key: KeyCode) -> bool {
+    keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+        && keyboard_input.just_pressed(key)
+}
+
+/// Despawn the placed entity of a kind standing on a tile, if it is still there
+fn despawn_placed(
+    commands: &mut Commands,
+    placed_query: &Query<(Entity, &Placed)>,
+    kind: EntityKind,
+    coord: UVec2,
+) {
+    if let Some((entity, _)) = placed_query
+        .iter()
+        .find(|(_, placed)| placed.kind == kind && placed.coord == coord)
+    {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Change a tile's terrain and the material it is drawn with
+fn set_terrain(
+    tile_query: &mut Query<(&mut Tile, &mut Handle<ColorMaterial>)>,
+    terrain_materials: &TerrainMaterials,
+    coord: UVec2,
+    terrain: Terrain,
+) {
+    if let Some((mut tile, mut material)) =
+        tile_query.iter_mut().find(|(tile, _)| tile.coord == coord)
+    {
+        tile.terrain = terrain;
+        *material = terrain_materials.get(terrain, tile.biome);
+    }
+}
@@ -0,0 +1,303 @@
+use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use bevy::window::PrimaryWindow;
+use crate::components::camera::UiCamera;
+use crate::components::ui::{AttractView, MainMenu, MenuStar};
+use crate::resources::{BiomeTable, TerrainPalette, WorldConfig};
+use crate::systems::minimap::tile_image;
+use crate::systems::world_gen::WorldMap;
+
+/// Real seconds the main menu sits without input before the attract view starts
+const ATTRACT_IDLE_SECONDS: f32 = 30.0;
+/// Seed of the world shown in the attract view, so it is the same world every time
+const ATTRACT_SEED: u64 = 20_240_601;
+/// Size of the attract world in tiles, large enough to pan across at any window size
+const ATTRACT_WORLD_SIZE: UVec2 = UVec2::new(128, 96);
+/// Opacity of the attract world, low enough for the menu to stay readable over it
+const ATTRACT_WORLD_ALPHA: f32 = 0.55;
+/// Angular speed of the pan along each axis, in radians per second; different speeds make
+/// the camera wander rather than trace a line
+const ATTRACT_PAN_SPEED: Vec2 = Vec2::new(0.05, 0.035);
+
+/// Idle tracking for the main menu, and the attract world while it is generated
+///
+/// Present only while the main menu is open.
+#[derive(Resource)]
+pub struct AttractMode {
+    config: WorldConfig,
+    /// Builds the attract world off the main thread, until it is spawned
+    task: Option<Task<WorldMap>>,
+    /// Real seconds since the last input
+    idle_seconds: f32,
+    /// Seconds the attract view has been panning, or `None` while the normal menu shows
+    active_seconds: Option<f32>,
+}
+
+/// Start generating the attract world when the main menu opens
+pub fn start_attract_mode(mut commands: Commands, biome_table: Res<BiomeTable>) {
+    let config = WorldConfig {
+        seed: ATTRACT_SEED,
+        width: ATTRACT_WORLD_SIZE.x,
+        height: ATTRACT_WORLD_SIZE.y,
+        ..default()
+    };
+    let task = {
+        let config = config.clone();
+        let biome_table = biome_table.clone();
+        AsyncComputeTaskPool::get().spawn(async move { WorldMap::generate(&config, &biome_table) })
+    };
+
+    commands.insert_resource(AttractMode {
+        config,
+        task: Some(task),
+        idle_seconds: 0.0,
+        active_seconds: None,
+    });
+}
+
+/// Spawn the attract world, hidden, once it has been generated
+///
+/// It is drawn as one sprite with a pixel per tile, at the size the tiles have in game.
+pub fn spawn_attract_world(
+    mut commands: Commands,
+    palette: Res<TerrainPalette>,
+    mut attract: ResMut<AttractMode>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Some(task) = attract.task.as_mut() else {
+        return;
+    };
+    let Some(map) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    attract.task = None;
+
+    let config = &attract.config;
+    let image = tile_image(config, &palette, map.tiles.iter());
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::WHITE.with_alpha(ATTRACT_WORLD_ALPHA),
+                custom_size: Some(config.world_size()),
+                ..default()
+            },
+            texture: images.add(image),
+            // Beneath the stars, though only one of them shows at a time
+            transform: Transform::from_translation(config.bounds().center().extend(-1.0)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        AttractView,
+        MainMenu,
+    ));
+}
+
+/// Start the attract view after the menu has sat idle for a while, and return to the normal
+/// menu on any input
+#[allow(clippy::too_many_arguments)]
+pub fn update_attract_mode(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut wheel_events: EventReader<MouseWheel>,
+    mut attract: ResMut<AttractMode>,
+    mut view_query: Query<&mut Visibility, (With<AttractView>, Without<MenuStar>)>,
+    mut star_query: Query<&mut Visibility, (With<MenuStar>, Without<AttractView>)>,
+    mut camera_query: Query<&mut Transform, (With<UiCamera>, With<MainMenu>)>,
+) {
+    let moved = motion_events.read().count() > 0;
+    let scrolled = wheel_events.read().count() > 0;
+    let input = moved
+        || scrolled
+        || keyboard_input.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || gamepad_buttons.get_just_pressed().next().is_some();
+
+    let (show_world, show_stars) = if input {
+        attract.idle_seconds = 0.0;
+        if attract.active_seconds.take().is_none() {
+            return;
+        }
+        info!("Leaving the attract view");
+        // The stars are laid out around the origin
+        for mut camera_transform in &mut camera_query {
+            camera_transform.translation.x = 0.0;
+            camera_transform.translation.y = 0.0;
+        }
+        (Visibility::Hidden, Visibility::Inherited)
+    } else {
+        attract.idle_seconds += time.delta_seconds();
+        if attract.active_seconds.is_some()
+            || attract.idle_seconds < ATTRACT_IDLE_SECONDS
+            || view_query.is_empty()
+        {
+            return;
+        }
+        info!("Starting the attract view");
+        attract.active_seconds = Some(0.0);
+        (Visibility::Inherited, Visibility::Hidden)
+    };
+
+    for mut visibility in &mut view_query {
+        *visibility = show_world;
+    }
+    for mut visibility in &mut star_query {
+        *visibility = show_stars;
+    }
+}
+
+/// Filter for the main menu's camera, which shows the attract world
+type MenuCamera = (With<UiCamera>, With<MainMenu>);
+
+/// Slowly pan the menu camera across the attract world while the attract view shows
+///
+/// The camera wanders over the whole world, clamped like the gameplay camera so the edges of
+/// the world never come into view.
+pub fn pan_attract_view(
+    time: Res<Time>,
+    mut attract: ResMut<AttractMode>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), MenuCamera>,
+) {
+    let Some(active_seconds) = attract.active_seconds.as_mut() else {
+        return;
+    };
+    *active_seconds += time.delta_seconds();
+    let phase = ATTRACT_PAN_SPEED * *active_seconds;
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((mut camera_transform, projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let bounds = attract.config.bounds();
+    let viewport_size = window.size() * projection.scale;
+    let reach = ((bounds.size() - viewport_size) / 2.0).max(Vec2::ZERO);
+    let target = bounds.center() + reach * Vec2::new(phase.x.sin(), phase.y.sin());
+    let position = bounds.clamp_camera_position(target, viewport_size);
+    camera_transform.translation.x = position.x;
+    camera_transform.translation.y = position.y;
+}
+
+/// Drop the attract mode when leaving the main menu; its world is despawned with the menu
+pub fn cleanup_attract_mode(mut commands: Commands) {
+    commands.remove_resource::<AttractMode>();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::state::app::StatesPlugin;
+
+    use super::*;
+    use crate::resources::GameState;
+    use crate::systems::menu::cleanup_main_menu;
+
+    /// App with an idle main menu: a hidden attract world over visible stars
+    fn idle_menu_app(idle_seconds: f32) -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<GamepadButton>>()
+            .add_event::<MouseMotion>()
+            .add_event::<MouseWheel>()
+            .insert_resource(AttractMode {
+                config: WorldConfig::default(),
+                task: None,
+                idle_seconds,
+                active_seconds: None,
+            })
+            .add_systems(Update, update_attract_mode);
+
+        let world = app.world_mut();
+        world.spawn((Visibility::Hidden, AttractView, MainMenu));
+        world.spawn((Visibility::Inherited, MenuStar { speed: 10.0 }));
+        world.spawn((Transform::from_xyz(0.0, 0.0, 0.0), UiCamera, MainMenu));
+        app
+    }
+
+    fn view_visibility(app: &mut App) -> Visibility {
+        let world = app.world_mut();
+        *world.query_filtered::<&Visibility, With<AttractView>>().single(world)
+    }
+
+    #[test]
+    fn attract_view_waits_for_the_menu_to_sit_idle() {
+        let mut app = idle_menu_app(ATTRACT_IDLE_SECONDS / 2.0);
+
+        app.update();
+
+        assert_eq!(view_visibility(&mut app), Visibility::Hidden);
+        assert!(app.world().resource::<AttractMode>().active_seconds.is_none());
+    }
+
+    #[test]
+    fn attract_view_starts_when_idle_and_stops_on_input() {
+        let mut app = idle_menu_app(ATTRACT_IDLE_SECONDS);
+
+        app.update();
+        assert_eq!(view_visibility(&mut app), Visibility::Inherited);
+        assert!(app.world().resource::<AttractMode>().active_seconds.is_some());
+
+        // Panned away, then any key brings the menu back to the origin
+        let world = app.world_mut();
+        let mut camera = world.query_filtered::<&mut Transform, With<UiCamera>>().single_mut(world);
+        camera.translation = Vec3::new(300.0, -200.0, 0.0);
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::Space);
+        app.update();
+
+        assert_eq!(view_visibility(&mut app), Visibility::Hidden);
+        let world = app.world_mut();
+        assert_eq!(
+            *world.query_filtered::<&Visibility, With<MenuStar>>().single(world),
+            Visibility::Inherited
+        );
+        let camera = world.query_filtered::<&Transform, With<UiCamera>>().single(world);
+        assert_eq!(camera.translation.truncate(), Vec2::ZERO);
+        let attract = world.resource::<AttractMode>();
+        assert_eq!(attract.idle_seconds, 0.0);
+        assert!(attract.active_seconds.is_none());
+    }
+
+    #[test]
+    fn attract_world_is_generated_with_the_menu_and_dropped_with_it() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_state::<GameState>()
+            .init_resource::<BiomeTable>()
+            .init_resource::<TerrainPalette>()
+            .init_resource::<Assets<Image>>()
+            .add_systems(OnEnter(GameState::MainMenu), start_attract_mode)
+            .add_systems(Update, spawn_attract_world.run_if(in_state(GameState::MainMenu)))
+            .add_systems(OnExit(GameState::MainMenu), (cleanup_main_menu, cleanup_attract_mode));
+
+        let attract_views = |app: &mut App| {
+            let world = app.world_mut();
+            world.query_filtered::<(), With<AttractView>>().iter(world).count()
+        };
+        for _ in 0..500 {
+            app.update();
+            if attract_views(&mut app) > 0 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(attract_views(&mut app), 1);
+        assert!(app.world().resource::<AttractMode>().task.is_none());
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Settings);
+        app.update();
+
+        assert!(!app.world().contains_resource::<AttractMode>());
+        assert_eq!(attract_views(&mut app), 0);
+    }
+}
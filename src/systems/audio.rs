@@ -0,0 +1,84 @@
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use crate::components::BackgroundMusic;
+use crate::events::PlaySoundEffect;
+use crate::resources::{AudioAssets, GameState, MusicTrack, Settings};
+
+/// Music plays quieter than sound effects at the same master volume
+const MUSIC_VOLUME: f32 = 0.5;
+
+/// Load all music and sound effect handles
+pub fn load_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AudioAssets {
+        menu_music: asset_server.load(AudioAssets::MENU_MUSIC_PATH),
+        ambient_music: asset_server.load(AudioAssets::AMBIENT_MUSIC_PATH),
+        click: asset_server.load(AudioAssets::CLICK_PATH),
+        hover: asset_server.load(AudioAssets::HOVER_PATH),
+        reject: asset_server.load(AudioAssets::REJECT_PATH),
+    });
+}
+
+/// Switch the background music to match the current game state
+///
+/// The previous track is despawned before the new one starts so tracks never overlap, and
+/// a track that is already playing is left alone so moving between menus doesn't restart it.
+pub fn update_background_music(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    audio_assets: Res<AudioAssets>,
+    settings: Res<Settings>,
+    music_query: Query<(Entity, &BackgroundMusic)>,
+) {
+    let Some(track) = MusicTrack::for_state(state.get()) else {
+        return;
+    };
+
+    let mut already_playing = false;
+    for (entity, music) in &music_query {
+        if music.track == track {
+            already_playing = true;
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    if !already_playing {
+        commands.spawn((
+            AudioBundle {
+                source: audio_assets.music(track),
+                settings: PlaybackSettings::LOOP
+                    .with_volume(Volume::new(settings.master_volume * MUSIC_VOLUME)),
+            },
+            BackgroundMusic { track },
+        ));
+    }
+}
+
+/// Apply master volume changes to the music that is already playing
+pub fn update_music_volume(
+    settings: Res<Settings>,
+    music_query: Query<&AudioSink, With<BackgroundMusic>>,
+) {
+    for sink in &music_query {
+        sink.set_volume(settings.master_volume * MUSIC_VOLUME);
+    }
+}
+
+/// Play requested sound effects, each as an entity that despawns when it finishes
+pub fn play_sound_effects(
+    mut commands: Commands,
+    mut requests: EventReader<PlaySoundEffect>,
+    audio_assets: Res<AudioAssets>,
+    settings: Res<Settings>,
+) {
+    for PlaySoundEffect(effect) in requests.read() {
+        if settings.master_volume <= 0.0 {
+            continue;
+        }
+
+        commands.spawn(AudioBundle {
+            source: audio_assets.sound(*effect),
+            settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(settings.master_volume)),
+        });
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{block_on, IoTaskPool, Task};
+use crate::events::Notification;
+use crate::resources::{Settings, SimulationClock};
+use crate::save::{autosave_name, save_game, save_path, SaveError, UnsavedChanges, AUTOSAVE_SLOTS};
+use crate::systems::save_load::SaveSnapshot;
+
+/// Progress toward the next autosave, and the write currently in flight
+#[derive(Resource, Default)]
+pub struct Autosave {
+    /// In-game hour the count toward the next autosave started at, taken from the simulation
+    /// clock on the first tick in each world
+    pub since_hours: Option<f64>,
+    /// Slot the next autosave is written to
+    pub next_slot: u32,
+    task: Option<Task<Result<PathBuf, SaveError>>>,
+}
+
+impl Autosave {
+    /// Whether an autosave is due at the clock's current hour, starting the next count if so
+    pub fn is_due(&mut self, now_hours: f64, interval_game_minutes: u32) -> bool {
+        let since = *self.since_hours.get_or_insert(now_hours);
+        if (now_hours - since) * 60.0 < interval_game_minutes as f64 {
+            return false;
+        }
+        self.since_hours = Some(now_hours);
+        true
+    }
+}
+
+/// Start counting toward the first autosave when a world is loaded
+pub fn reset_autosave(mut autosave: ResMut<Autosave>) {
+    autosave.since_hours = None;
+}
+
+/// Autosave to the next slot once the interval from the settings has passed on the simulation
+/// clock
+///
+/// Only the snapshot is taken on the main thread. Serializing and writing the file happen
+/// on the IO task pool so large worlds don't stall the frame.
+pub fn tick_autosave(
+    clock: Res<SimulationClock>,
+    settings: Res<Settings>,
+    snapshot: SaveSnapshot,
+    mut autosave: ResMut<Autosave>,
+) {
+    // Skip while the previous autosave is still being written
+    if settings.autosave_game_minutes == 0 || autosave.task.is_some() {
+        return;
+    }
+    if !autosave.is_due(clock.elapsed_hours, settings.autosave_game_minutes) {
+        return;
+    }
+
+    let name = autosave_name(autosave.next_slot);
+    let Some(save_data) = snapshot.save_data(&name) else {
+        return;
+    };
+    let path = save_path(&name);
+    autosave.next_slot = (autosave.next_slot + 1) % AUTOSAVE_SLOTS;
+
+    autosave.task = Some(IoTaskPool::get().spawn(async move {
+        save_game(&save_data, &path).map(|()| path)
+    }));
+}
+
+/// Report finished autosaves
+pub fn poll_autosave(
+    mut autosave: ResMut<Autosave>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let Some(task) = autosave.task.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    autosave.task = None;
+
+    match result {
+        Ok(path) => {
+            info!("Autosaved to {}", path.display());
+            notifications.send(Notification::new("Autosaved"));
+            unsaved_changes.0 = false;
+        }
+        Err(error) => {
+            error!("Autosave failed: {}", error);
+            notifications.send(Notification::new("Autosave failed"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn autosaves_follow_in_game_minutes() {
+        let mut autosave = Autosave::default();
+
+        // A loaded world starts counting from its saved time, not from zero
+        assert!(!autosave.is_due(30.0, 90));
+        assert!(!autosave.is_due(31.0, 90));
+        assert!(autosave.is_due(31.5, 90));
+        assert!(!autosave.is_due(32.0, 90));
+        assert!(autosave.is_due(33.0, 90));
+    }
+}
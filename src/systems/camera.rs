@@ -0,0 +1,725 @@
+use bevy::ecs::query::QuerySingleError;
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::components::{CameraController, CameraZoom, EventLogPanel, GameCamera, MapBlockingUi, Tile};
+use crate::resources::{
+    InputAction, KeyBindings, SelectedTile, Settings, TerrainBrush, WorldBounds, WorldConfig,
+    TILE_SIZE,
+};
+use crate::systems::gamepad::active_gamepad;
+use crate::utils::{cursor_to_world, visible_world_rect};
+
+/// Pan the gameplay camera with the bound pan keys or the gamepad left stick
+///
+/// The camera accelerates toward the input direction and coasts to a stop when input is
+/// released. Diagonal key presses are normalized so they are no faster than cardinal ones.
+/// Holding Shift speeds panning up and holding Ctrl slows it down, on top of the key pan speed
+/// setting.
+#[allow(clippy::too_many_arguments)]
+pub fn move_camera(
+    settings: Res<Settings>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut camera_query: Query<(&mut Transform, &mut CameraController), With<GameCamera>>,
+    time: Res<Time>,
+    mut warned: Local<bool>,
+) {
+    let camera = camera_query.get_single_mut();
+    warn_on_camera_lookup_error(&camera, &mut warned);
+    let Ok((mut camera_transform, mut controller)) = camera else {
+        return;
+    };
+
+    let mut key_direction = Vec2::ZERO;
+    if key_bindings.pressed(&keyboard_input, InputAction::PanLeft) {
+        key_direction.x -= 1.0;
+    }
+    if key_bindings.pressed(&keyboard_input, InputAction::PanRight) {
+        key_direction.x += 1.0;
+    }
+    if key_bindings.pressed(&keyboard_input, InputAction::PanUp) {
+        key_direction.y += 1.0;
+    }
+    if key_bindings.pressed(&keyboard_input, InputAction::PanDown) {
+        key_direction.y -= 1.0;
+    }
+
+    // Left stick pans proportionally to how far it is pushed
+    let stick = active_gamepad(&gamepads)
+        .map(|gamepad| {
+            Vec2::new(
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+                    .unwrap_or(0.0),
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+                    .unwrap_or(0.0),
+            )
+        })
+        .unwrap_or(Vec2::ZERO);
+
+    let direction = pan_direction(key_direction, stick);
+    let multiplier = CameraController::speed_multiplier(&keyboard_input) * settings.key_pan_speed;
+    let delta_seconds = time.delta_seconds();
+
+    if direction != Vec2::ZERO {
+        // Accelerate toward the target velocity without overshooting it, scaling acceleration
+        // too so the faster speed is reached as quickly as the normal one
+        let target_velocity = controller.target_velocity(direction, multiplier);
+        let max_change = controller.acceleration * multiplier * delta_seconds;
+        let change = (target_velocity - controller.velocity).clamp_length_max(max_change);
+        controller.velocity += change;
+    } else if controller.velocity != Vec2::ZERO {
+        // Frame-rate independent exponential damping
+        let decay = (-controller.damping * delta_seconds).exp();
+        controller.velocity *= decay;
+        if controller.velocity.length() < CameraController::STOP_SPEED {
+            controller.velocity = Vec2::ZERO;
+        }
+    }
+
+    if controller.velocity != Vec2::ZERO {
+        camera_transform.translation += (controller.velocity * delta_seconds).extend(0.0);
+    }
+}
+
+/// Pan the gameplay camera while the cursor is near the edge of the window
+///
+/// Panning speeds up the closer the cursor gets to the edge. Nothing happens while the window
+/// is unfocused or the cursor is over the in-game UI.
+pub fn edge_scroll_camera(
+    settings: Res<Settings>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    ui_query: Query<&Interaction, MapBlockingUi>,
+    mut camera_query: Query<
+        (&mut Transform, &OrthographicProjection, &CameraController),
+        With<GameCamera>,
+    >,
+    time: Res<Time>,
+) {
+    if !settings.edge_scroll {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    // A cursor left at the edge of an unfocused window would otherwise scroll forever
+    if !window.focused {
+        return;
+    }
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    if ui_query.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+    let Ok((mut camera_transform, projection, controller)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let direction = edge_scroll_direction(cursor, window.size(), controller.edge_scroll_margin);
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    // Scaled by the zoom so the map moves across the screen at the same rate at any zoom level
+    let multiplier = CameraController::speed_multiplier(&keyboard_input) * settings.mouse_sensitivity;
+    let velocity = controller.target_velocity(direction, multiplier) * projection.scale;
+    camera_transform.translation += (velocity * time.delta_seconds()).extend(0.0);
+}
+
+/// Pan direction for a cursor position in window coordinates, growing from zero at the inner
+/// edge of the margin to one at the window edge
+fn edge_scroll_direction(cursor: Vec2, window_size: Vec2, margin: f32) -> Vec2 {
+    if margin <= 0.0 {
+        return Vec2::ZERO;
+    }
+    let strength = |distance: f32| (1.0 - distance / margin).clamp(0.0, 1.0);
+
+    // Window y grows downward while world y grows upward
+    let direction = Vec2::new(
+        strength(window_size.x - cursor.x) - strength(cursor.x),
+        strength(cursor.y) - strength(window_size.y - cursor.y),
+    );
+    direction.clamp_length_max(1.0)
+}
+
+/// Combine key and stick input into a pan direction no longer than one
+fn pan_direction(key_direction: Vec2, stick: Vec2) -> Vec2 {
+    (key_direction.normalize_or_zero() + stick).clamp_length_max(1.0)
+}
+
+/// Log a warning when the gameplay camera is missing or duplicated, so camera input doesn't
+/// just go dead without explanation
+///
+/// Each problem is only reported once; `warned` is cleared again once exactly one camera is
+/// found. Returns whether a warning was logged.
+pub fn warn_on_camera_lookup_error<T>(
+    lookup: &Result<T, QuerySingleError>,
+    warned: &mut bool,
+) -> bool {
+    let problem = match lookup {
+        Ok(_) => {
+            *warned = false;
+            return false;
+        }
+        Err(_) if *warned => return false,
+        Err(QuerySingleError::NoEntities(_)) => "no gameplay camera exists",
+        Err(QuerySingleError::MultipleEntities(_)) => "more than one gameplay camera exists",
+    };
+
+    warn!("Camera input ignored: {}", problem);
+    *warned = true;
+    true
+}
+
+/// Zoom the gameplay camera with the mouse wheel, easing toward the cursor position
+///
+/// The wheel resizes the brush instead while a terrain tool is selected, and scrolls the event
+/// log while the cursor is over it.
+pub fn zoom_camera(
+    mut scroll_events: EventReader<MouseWheel>,
+    brush: Res<TerrainBrush>,
+    event_log_query: Query<&Interaction, With<EventLogPanel>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection, &mut CameraZoom)>,
+    time: Res<Time>,
+) {
+    let scroll: f32 = scroll_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / CameraZoom::PIXELS_PER_LINE,
+        })
+        .sum();
+
+    let Ok((mut camera_transform, mut projection, mut zoom)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    // Scrolling up zooms in, which means a smaller projection scale
+    let over_event_log = event_log_query.iter().any(|interaction| *interaction != Interaction::None);
+    if scroll != 0.0 && brush.tool.is_none() && !over_event_log {
+        zoom.zoom_by(CameraZoom::ZOOM_FACTOR_PER_LINE.powf(-scroll));
+    }
+
+    let previous_scale = projection.scale;
+    if previous_scale == zoom.target_scale {
+        return;
+    }
+
+    // Frame-rate independent exponential ease toward the target scale
+    let blend = 1.0 - (-zoom.smoothing * time.delta_seconds()).exp();
+    let mut new_scale = previous_scale + (zoom.target_scale - previous_scale) * blend;
+    if (new_scale - zoom.target_scale).abs() < 0.0001 {
+        new_scale = zoom.target_scale;
+    }
+
+    // Keep the world point under the cursor fixed while the scale changes
+    let window = window_query.get_single().ok();
+    let anchor = window.and_then(|window| cursor_to_world(window, &projection, &camera_transform));
+    projection.scale = new_scale;
+    if let (Some(window), Some(anchor)) = (window, anchor) {
+        if let Some(moved) = cursor_to_world(window, &projection, &camera_transform) {
+            camera_transform.translation += (anchor - moved).extend(0.0);
+        }
+    }
+}
+
+/// Pan the gameplay camera by dragging with the middle mouse button held
+///
+/// Mouse motion is scaled by the current zoom so the world follows the cursor 1:1 at the
+/// default mouse sensitivity. A drag only starts with the cursor over the map, not the in-game
+/// UI. Grabbing the map stops any glide, and with inertia on, releasing a drag sets the camera
+/// gliding.
+pub fn drag_camera(
+    settings: Res<Settings>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut motion_events: EventReader<MouseMotion>,
+    ui_query: Query<&Interaction, MapBlockingUi>,
+    mut camera_query: Query<
+        (&mut Transform, &OrthographicProjection, &mut CameraController),
+        With<GameCamera>,
+    >,
+    time: Res<Time>,
+) {
+    // Always drain motion so stale deltas don't apply when the button is next pressed
+    let delta: Vec2 = motion_events.read().map(|event| event.delta).sum();
+
+    let Ok((mut camera_transform, projection, mut controller)) = camera_query.get_single_mut() else {
+        return;
+    };
+    if mouse_buttons.just_released(MouseButton::Middle) && controller.dragging {
+        controller.release_drag();
+        return;
+    }
+    if mouse_buttons.just_pressed(MouseButton::Middle)
+        && ui_query.iter().all(|interaction| *interaction == Interaction::None)
+    {
+        controller.start_drag();
+    }
+    if !mouse_buttons.pressed(MouseButton::Middle) || !controller.dragging {
+        return;
+    }
+
+    // Screen space y points down while world space y points up
+    let world_delta = Vec2::new(-delta.x, delta.y) * projection.scale * settings.mouse_sensitivity;
+    // Still frames count too, so holding the cursor before letting go ends without a glide
+    controller.track_drag(world_delta, time.delta_seconds());
+    if world_delta != Vec2::ZERO {
+        camera_transform.translation += world_delta.extend(0.0);
+    }
+}
+
+/// Turn camera inertia on or off to match the settings
+pub fn apply_camera_inertia(
+    settings: Res<Settings>,
+    mut camera_query: Query<&mut CameraController, With<GameCamera>>,
+) {
+    for mut controller in &mut camera_query {
+        controller.inertia = settings.camera_inertia;
+    }
+}
+
+/// Recenter the gameplay camera on the selected tile, or on the world origin when no tile is
+/// selected, when the focus key is pressed
+pub fn focus_camera_hotkey(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    selected_tile: Res<SelectedTile>,
+    config: Res<WorldConfig>,
+    tile_query: Query<&Tile>,
+    mut camera_query: Query<&mut CameraController, With<GameCamera>>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, InputAction::FocusCamera) {
+        return;
+    }
+    let Ok(mut controller) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let target = selected_tile
+        .0
+        .and_then(|entity| tile_query.get(entity).ok())
+        .map(|tile| config.tile_center(tile.coord))
+        .unwrap_or(Vec2::ZERO);
+    controller.focus_camera_on(target);
+}
+
+/// Ease the gameplay camera back to its default zoom when the reset view key is pressed
+pub fn reset_camera_hotkey(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut camera_query: Query<(&CameraController, &mut CameraZoom), With<GameCamera>>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, InputAction::ResetView) {
+        return;
+    }
+    if let Ok((controller, mut zoom)) = camera_query.get_single_mut() {
+        controller.reset_view(&mut zoom);
+    }
+}
+
+/// Hold the gameplay camera at its default rotation, easing back any rotation it picked up
+pub fn lock_camera_rotation(
+    mut camera_query: Query<(&mut Transform, &CameraController), With<GameCamera>>,
+    time: Res<Time>,
+) {
+    let Ok((mut camera_transform, controller)) = camera_query.get_single_mut() else {
+        return;
+    };
+    // Avoid triggering change detection while the rotation is already locked
+    if camera_transform.rotation != controller.default_rotation {
+        camera_transform.rotation =
+            controller.rotation_step(camera_transform.rotation, time.delta_seconds());
+    }
+}
+
+/// Ease the gameplay camera toward its focus target, if it has one
+///
+/// The destination is clamped to the world bounds for the visible area so the camera comes to
+/// rest where `clamp_camera_to_bounds` would leave it. Panning by key or drag cancels the focus.
+pub fn ease_camera_focus(
+    bounds: Res<WorldBounds>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<
+        (&mut Transform, &OrthographicProjection, &mut CameraController),
+        With<GameCamera>,
+    >,
+    time: Res<Time>,
+) {
+    let Ok((mut camera_transform, projection, mut controller)) = camera_query.get_single_mut() else {
+        return;
+    };
+    let Some(target) = controller.focus_target else {
+        return;
+    };
+    if controller.velocity != Vec2::ZERO || mouse_buttons.pressed(MouseButton::Middle) {
+        controller.focus_target = None;
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    let viewport_size = window.size() * projection.scale;
+    let destination = bounds.clamp_camera_position(target, viewport_size);
+    let position = controller.focus_step(
+        camera_transform.translation.truncate(),
+        destination,
+        time.delta_seconds(),
+    );
+    camera_transform.translation.x = position.x;
+    camera_transform.translation.y = position.y;
+    if position == destination {
+        controller.focus_target = None;
+    }
+}
+
+/// Keep the gameplay camera inside the world bounds, accounting for the visible area at the current zoom
+///
+/// Panning velocity into an edge is dropped, so a glide stops at the edge instead of pressing
+/// against it.
+pub fn clamp_camera_to_bounds(
+    bounds: Res<WorldBounds>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<
+        (&mut Transform, &OrthographicProjection, Option<&mut CameraController>),
+        With<CameraZoom>,
+    >,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((mut camera_transform, projection, controller)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let viewport_size = Vec2::new(window.width(), window.height()) * projection.scale;
+    let clamped = bounds.clamp_camera_position(camera_transform.translation.truncate(), viewport_size);
+    
+    // Avoid triggering change detection when the camera is already in bounds
+    let position = camera_transform.translation.truncate();
+    if clamped != position {
+        if let Some(mut controller) = controller {
+            if clamped.x != position.x {
+                controller.velocity.x = 0.0;
+            }
+            if clamped.y != position.y {
+                controller.velocity.y = 0.0;
+            }
+        }
+        camera_transform.translation.x = clamped.x;
+        camera_transform.translation.y = clamped.y;
+    }
+}
+
+/// Hide tiles outside the camera's view and show them again as they scroll back in
+///
+/// Only needed with `TileRendering::PerTile`, since batched tiles aren't drawn themselves.
+///
+/// Only checks the tiles when the visible area has changed or tiles were spawned, and only
+/// writes the tiles whose visibility actually changes.
+pub fn cull_offscreen_tiles(
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    new_tiles: Query<(), Added<Tile>>,
+    mut tile_query: Query<(&Tile, &mut Visibility)>,
+    mut last_visible: Local<Option<Rect>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    // Grown by half a tile so tiles only partly on screen count as visible
+    let visible = visible_world_rect(window.size(), projection, camera_transform)
+        .inflate(TILE_SIZE / 2.0);
+    if *last_visible == Some(visible) && new_tiles.is_empty() {
+        return;
+    }
+    *last_visible = Some(visible);
+
+    for (tile, mut visibility) in &mut tile_query {
+        let wanted = if visible.contains(config.tile_center(tile.coord)) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::ui::SpeedButton;
+    use crate::resources::SimulationSpeed;
+
+    fn spawn_game_camera(world: &mut World) -> Entity {
+        world
+            .spawn((Transform::default(), CameraController::default(), GameCamera))
+            .id()
+    }
+
+    fn lookup_warns(world: &mut World, warned: &mut bool) -> bool {
+        let mut query = world.query_filtered::<&Transform, With<GameCamera>>();
+        let lookup = query.get_single(world);
+        warn_on_camera_lookup_error(&lookup, warned)
+    }
+
+    #[test]
+    fn duplicate_cameras_warn_once() {
+        let mut world = World::new();
+        spawn_game_camera(&mut world);
+        spawn_game_camera(&mut world);
+
+        let mut warned = false;
+        assert!(lookup_warns(&mut world, &mut warned));
+        assert!(warned);
+        assert!(!lookup_warns(&mut world, &mut warned));
+    }
+
+    #[test]
+    fn missing_camera_warns() {
+        let mut world = World::new();
+        let mut warned = false;
+        assert!(lookup_warns(&mut world, &mut warned));
+    }
+
+    #[test]
+    fn single_camera_clears_warning() {
+        let mut world = World::new();
+        let camera = spawn_game_camera(&mut world);
+        spawn_game_camera(&mut world);
+
+        let mut warned = false;
+        assert!(lookup_warns(&mut world, &mut warned));
+
+        world.despawn(camera);
+        assert!(!lookup_warns(&mut world, &mut warned));
+        assert!(!warned);
+    }
+
+    #[test]
+    fn diagonal_pan_is_no_faster_than_cardinal_with_multiplier() {
+        let controller = CameraController::default();
+        for multiplier in [1.0, CameraController::FAST_MULTIPLIER, CameraController::SLOW_MULTIPLIER] {
+            let cardinal = controller.target_velocity(pan_direction(Vec2::X, Vec2::ZERO), multiplier);
+            let diagonal = controller.target_velocity(pan_direction(Vec2::ONE, Vec2::ZERO), multiplier);
+            assert!((diagonal.length() - cardinal.length()).abs() < 1e-3);
+            assert!((cardinal.length() - controller.max_speed * multiplier).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn edge_scroll_ramps_up_toward_the_edge() {
+        let window_size = Vec2::new(800.0, 600.0);
+        let margin = 20.0;
+
+        assert_eq!(edge_scroll_direction(Vec2::new(400.0, 300.0), window_size, margin), Vec2::ZERO);
+        assert_eq!(edge_scroll_direction(Vec2::new(0.0, 300.0), window_size, margin), Vec2::NEG_X);
+        assert_eq!(edge_scroll_direction(Vec2::new(400.0, 0.0), window_size, margin), Vec2::Y);
+
+        let halfway = edge_scroll_direction(Vec2::new(790.0, 300.0), window_size, margin);
+        assert!((halfway.x - 0.5).abs() < 1e-5);
+
+        let corner = edge_scroll_direction(Vec2::new(800.0, 600.0), window_size, margin);
+        assert!((corner.length() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn modifier_keys_scale_pan_speed() {
+        let mut keyboard_input = ButtonInput::<KeyCode>::default();
+        assert_eq!(CameraController::speed_multiplier(&keyboard_input), 1.0);
+
+        keyboard_input.press(KeyCode::ShiftLeft);
+        assert_eq!(
+            CameraController::speed_multiplier(&keyboard_input),
+            CameraController::FAST_MULTIPLIER
+        );
+
+        keyboard_input.release(KeyCode::ShiftLeft);
+        keyboard_input.press(KeyCode::ControlRight);
+        assert_eq!(
+            CameraController::speed_multiplier(&keyboard_input),
+            CameraController::SLOW_MULTIPLIER
+        );
+    }
+
+    #[test]
+    fn focus_eases_toward_target_and_settles() {
+        let mut controller = CameraController {
+            velocity: Vec2::new(50.0, 0.0),
+            ..default()
+        };
+        let target = Vec2::new(400.0, -200.0);
+        controller.focus_camera_on(target);
+        assert_eq!(controller.focus_target, Some(target));
+        assert_eq!(controller.velocity, Vec2::ZERO);
+
+        // Eases rather than snapping, closing part of the gap each frame
+        let first = controller.focus_step(Vec2::ZERO, target, 1.0 / 60.0);
+        assert!(first.length() > 0.0);
+        assert!(first.distance(target) < target.length());
+        assert!(first.distance(target) > target.length() / 2.0);
+
+        let mut position = first;
+        for _ in 0..600 {
+            position = controller.focus_step(position, target, 1.0 / 60.0);
+        }
+        assert_eq!(position, target);
+    }
+
+    #[test]
+    fn reset_view_restores_the_default_zoom() {
+        let controller = CameraController::default();
+        let mut zoom = CameraZoom::default();
+        zoom.zoom_by(3.0);
+        assert_ne!(zoom.target_scale, controller.default_scale);
+
+        controller.reset_view(&mut zoom);
+        assert_eq!(zoom.target_scale, controller.default_scale);
+    }
+
+    #[test]
+    fn rotation_eases_back_to_default_and_settles() {
+        let controller = CameraController::default();
+        let tilted = Quat::from_rotation_z(0.5);
+
+        // Eases rather than snapping
+        let first = controller.rotation_step(tilted, 1.0 / 60.0);
+        assert!(first.angle_between(Quat::IDENTITY) < 0.5);
+        assert!(first.angle_between(Quat::IDENTITY) > 0.25);
+
+        let mut rotation = first;
+        for _ in 0..600 {
+            rotation = controller.rotation_step(rotation, 1.0 / 60.0);
+        }
+        assert_eq!(rotation, controller.default_rotation);
+    }
+
+    #[test]
+    fn releasing_a_drag_glides_only_with_inertia() {
+        let mut controller = CameraController::default();
+        for _ in 0..60 {
+            controller.track_drag(Vec2::new(5.0, 0.0), 1.0 / 60.0);
+        }
+        controller.release_drag();
+        assert!((controller.velocity.x - 300.0).abs() < 1.0);
+        assert_eq!(controller.drag_velocity, Vec2::ZERO);
+
+        let mut precise = CameraController {
+            inertia: false,
+            ..default()
+        };
+        precise.track_drag(Vec2::new(5.0, 0.0), 1.0 / 60.0);
+        precise.release_drag();
+        assert_eq!(precise.velocity, Vec2::ZERO);
+    }
+
+    #[test]
+    fn flicked_drag_glide_is_capped() {
+        let mut controller = CameraController::default();
+        for _ in 0..60 {
+            controller.track_drag(Vec2::new(0.0, -500.0), 1.0 / 60.0);
+        }
+        controller.release_drag();
+        let cap = controller.max_speed * CameraController::FAST_MULTIPLIER;
+        assert!((controller.velocity.length() - cap).abs() < 1e-3);
+        assert!(controller.velocity.y < 0.0);
+    }
+
+    #[test]
+    fn drags_only_start_over_the_map() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Settings>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .add_event::<MouseMotion>()
+            .add_systems(Update, drag_camera);
+        let world = app.world_mut();
+        let camera = world
+            .spawn((
+                Transform::default(),
+                OrthographicProjection::default(),
+                CameraController::default(),
+                GameCamera,
+            ))
+            .id();
+        let button = world
+            .spawn((
+                Interaction::Hovered,
+                SpeedButton {
+                    speed: SimulationSpeed::Fast,
+                },
+            ))
+            .id();
+
+        let drag = |app: &mut App| {
+            let mut mouse_buttons = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+            mouse_buttons.clear();
+            mouse_buttons.press(MouseButton::Middle);
+            app.world_mut().send_event(MouseMotion {
+                delta: Vec2::new(10.0, 0.0),
+            });
+            app.update();
+        };
+        let release = |app: &mut App| {
+            let mut mouse_buttons = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+            mouse_buttons.clear();
+            mouse_buttons.release(MouseButton::Middle);
+            app.update();
+        };
+
+        // Grabbing over the UI leaves the camera where it is
+        drag(&mut app);
+        assert!(!app.world().get::<CameraController>(camera).unwrap().dragging);
+        assert_eq!(app.world().get::<Transform>(camera).unwrap().translation, Vec3::ZERO);
+        release(&mut app);
+
+        *app.world_mut().get_mut::<Interaction>(button).unwrap() = Interaction::None;
+        drag(&mut app);
+        assert!(app.world().get::<CameraController>(camera).unwrap().dragging);
+        assert!(app.world().get::<Transform>(camera).unwrap().translation.x < 0.0);
+
+        release(&mut app);
+        assert!(!app.world().get::<CameraController>(camera).unwrap().dragging);
+    }
+
+    #[test]
+    fn move_camera_leaves_duplicate_cameras_alone() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Settings>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<Gamepads>()
+            .init_resource::<Axis<GamepadAxis>>()
+            .add_systems(Update, move_camera);
+        let cameras = [
+            spawn_game_camera(app.world_mut()),
+            spawn_game_camera(app.world_mut()),
+        ];
+
+        let pan_right = app.world().resource::<KeyBindings>().key(InputAction::PanRight);
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(pan_right);
+        app.update();
+        app.update();
+
+        for camera in cameras {
+            let transform = app.world().get::<Transform>(camera).unwrap();
+            assert_eq!(transform.translation, Vec3::ZERO);
+        }
+    }
+}
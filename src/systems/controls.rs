@@ -0,0 +1,268 @@
+use std::io;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use crate::components::camera::{GameCamera, UiCamera};
+use crate::components::ui::*;
+use crate::events::ButtonActivated;
+use crate::i18n::Locale;
+use crate::resources::{key_label, InputAction, KeyBindings, PendingRebind};
+use crate::save::{back_up_file, key_bindings_path, load_key_bindings, save_key_bindings, KEY_BINDINGS_FILE};
+use crate::systems::menu::{create_labeled_row, create_menu_button, create_sized_button};
+
+/// Text shown on a binding button while it waits for a key
+const WAITING_FOR_KEY: &str = "Press a key...";
+
+/// Setup the controls screen with one rebind button per action
+pub fn setup_controls_menu(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    key_bindings: Res<KeyBindings>,
+    game_camera: Query<(), With<GameCamera>>,
+) {
+    info!("Setting up controls menu");
+
+    // Opened from the pause menu, the screen renders through the gameplay camera that is
+    // still alive, so a second camera is only needed when there is no game world
+    if game_camera.is_empty() {
+        commands.spawn((Camera2dBundle::default(), UiCamera, ControlsMenu));
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                background_color: theme.background_color.into(),
+                ..default()
+            },
+            ControlsMenu,
+            ThemeColor::Background,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                TextBundle::from_section(
+                    "Controls",
+                    TextStyle {
+                        font_size: theme.title_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Title,
+            ));
+
+            for action in InputAction::ALL {
+                create_labeled_row(parent, &theme, action.label(), |row| {
+                    create_sized_button(
+                        row,
+                        &theme,
+                        &key_label(key_bindings.key(action)),
+                        Val::Px(260.0),
+                        Val::Px(44.0),
+                        ControlsButton::new(ControlsAction::Rebind(action)),
+                    );
+                });
+            }
+
+            parent.spawn((
+                TextBundle::from_section(
+                    "Click an action, then press the key to bind",
+                    TextStyle {
+                        font_size: theme.font_size(24.0),
+                        color: Color::srgb(0.8, 0.8, 0.8),
+                        ..default()
+                    },
+                ),
+                ControlsStatusText,
+            ));
+
+            // Button container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(15.0),
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    create_sized_button(
+                        parent,
+                        &theme,
+                        "Reset to Defaults",
+                        Val::Px(300.0),
+                        Val::Px(60.0),
+                        ControlsButton::new(ControlsAction::ResetDefaults),
+                    );
+                    create_menu_button(parent, &theme, &locale, "Back", MenuAction::Back);
+                });
+        });
+}
+
+/// Handle activated controls buttons
+pub fn handle_controls_actions(
+    mut activated: EventReader<ButtonActivated>,
+    button_query: Query<&ControlsButton>,
+    mut key_bindings: ResMut<KeyBindings>,
+    mut pending: ResMut<PendingRebind>,
+    mut status_query: Query<&mut Text, With<ControlsStatusText>>,
+) {
+    for event in activated.read() {
+        let Ok(controls_button) = button_query.get(event.entity) else {
+            continue;
+        };
+
+        let status = match controls_button.action {
+            ControlsAction::Rebind(action) => {
+                pending.0 = Some(action);
+                format!("Press a key for {}, or Escape to cancel", action.label())
+            }
+            ControlsAction::ResetDefaults => {
+                pending.0 = None;
+                *key_bindings = KeyBindings::default();
+                "Controls reset to defaults".to_string()
+            }
+        };
+
+        for mut text in &mut status_query {
+            text.sections[0].value = status.clone();
+        }
+    }
+}
+
+/// Bind the next key pressed to the action waiting for one
+///
+/// Runs before menu navigation and consumes the key so it doesn't also move focus, press a
+/// button or leave the screen. Escape cancels, and keys already bound to another action are
+/// rejected.
+pub fn capture_rebind_key(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut key_bindings: ResMut<KeyBindings>,
+    mut pending: ResMut<PendingRebind>,
+    mut status_query: Query<&mut Text, With<ControlsStatusText>>,
+) {
+    let Some(action) = pending.0 else {
+        return;
+    };
+    let Some(key) = keyboard_input.get_just_pressed().next().copied() else {
+        return;
+    };
+    keyboard_input.clear_just_pressed(key);
+    pending.0 = None;
+
+    let status = if key == KeyCode::Escape && key_bindings.key(action) != KeyCode::Escape {
+        "Rebinding cancelled".to_string()
+    } else {
+        match key_bindings.bind(action, key) {
+            Ok(()) => format!("{} bound to {}", action.label(), key_label(key)),
+            Err(conflict) => format!(
+                "{} is already bound to {}",
+                key_label(key),
+                conflict.label()
+            ),
+        }
+    };
+
+    info!("{}", status);
+    for mut text in &mut status_query {
+        text.sections[0].value = status.clone();
+    }
+}
+
+/// Keep the binding buttons showing the current keys, or a prompt while waiting
+pub fn update_controls_text(
+    key_bindings: Res<KeyBindings>,
+    pending: Res<PendingRebind>,
+    button_query: Query<(&ControlsButton, &Children)>,
+    mut text_query: Query<&mut Text, With<ButtonText>>,
+) {
+    for (controls_button, children) in &button_query {
+        let ControlsAction::Rebind(action) = controls_button.action else {
+            continue;
+        };
+
+        let label = if pending.0 == Some(action) {
+            WAITING_FOR_KEY.to_string()
+        } else {
+            key_label(key_bindings.key(action))
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                if text.sections[0].value != label {
+                    text.sections[0].value = label.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Cleanup controls screen and its UI camera
+pub fn cleanup_controls_menu(
+    mut commands: Commands,
+    menu_query: Query<Entity, With<ControlsMenu>>,
+    mut pending: ResMut<PendingRebind>,
+) {
+    for entity in &menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    pending.0 = None;
+
+    info!("Controls menu cleaned up");
+}
+
+/// Read the key bindings file, falling back to defaults if it is missing or unreadable
+///
+/// A key bindings file left in the working directory by older builds is used when the config
+/// directory has none yet. A file that can't be parsed is moved aside rather than overwritten.
+pub fn load_startup_key_bindings() -> KeyBindings {
+    let mut path = key_bindings_path();
+    let mut result = load_key_bindings(&path);
+    if matches!(&result, Err(error) if error.kind() == io::ErrorKind::NotFound) {
+        path = PathBuf::from(KEY_BINDINGS_FILE);
+        result = load_key_bindings(&path);
+    }
+
+    match result {
+        Ok(key_bindings) => {
+            info!("Loaded key bindings from {}", path.display());
+            key_bindings
+        }
+        Err(error) if error.kind() == io::ErrorKind::NotFound => KeyBindings::default(),
+        // A file that isn't valid JSON is kept, since the defaults are written in its place
+        Err(error) if matches!(error.kind(), io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof) => {
+            warn!("Failed to read key bindings from {}, using defaults: {}", path.display(), error);
+            match back_up_file(&path) {
+                Ok(backup) => warn!("Moved the unreadable key bindings file to {}", backup.display()),
+                Err(error) => error!("Failed to back up {}: {}", path.display(), error),
+            }
+            KeyBindings::default()
+        }
+        Err(error) => {
+            warn!("Failed to load key bindings from {}, using defaults: {}", path.display(), error);
+            KeyBindings::default()
+        }
+    }
+}
+
+/// Write the key bindings to the config directory when leaving the controls screen
+pub fn persist_key_bindings(key_bindings: Res<KeyBindings>) {
+    let path = key_bindings_path();
+    match save_key_bindings(&key_bindings, &path) {
+        Ok(()) => info!("Key bindings saved to {}", path.display()),
+        Err(error) => error!("Failed to save key bindings to {}: {}", path.display(), error),
+    }
+}
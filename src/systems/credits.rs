@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::components::camera::UiCamera;
+use crate::components::ui::*;
+use crate::resources::{Credits, GameState};
+
+/// Height of each credits line at the default text size, so the length of the roll is known
+/// up front
+const LINE_HEIGHT: f32 = 44.0;
+/// Scroll speed in UI pixels per second
+const SCROLL_SPEED: f32 = 60.0;
+
+/// Setup the credits screen with one line per credits entry, starting below the screen
+pub fn setup_credits_screen(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    credits: Res<Credits>,
+) {
+    info!("Setting up credits screen");
+
+    // Spawn a camera for UI rendering
+    commands.spawn((Camera2dBundle::default(), UiCamera, CreditsScreen));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: theme.background_color.into(),
+                ..default()
+            },
+            CreditsScreen,
+            ThemeColor::Background,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            top: Val::Percent(100.0),
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    CreditsRoll::default(),
+                ))
+                .with_children(|roll| {
+                    for (index, entry) in credits.0.iter().enumerate() {
+                        // The first line is the title
+                        let font_size = if index == 0 {
+                            theme.title_font_size * 0.6
+                        } else {
+                            theme.font_size(24.0)
+                        };
+
+                        roll.spawn(NodeBundle {
+                            style: Style {
+                                height: Val::Px(theme.font_size(LINE_HEIGHT)),
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|line| {
+                            line.spawn((
+                                TextBundle::from_section(
+                                    entry.clone(),
+                                    TextStyle {
+                                        font_size,
+                                        color: theme.title_color,
+                                        ..default()
+                                    },
+                                ),
+                                ThemeColor::Title,
+                            ));
+                        });
+                    }
+                });
+        });
+}
+
+/// Scroll the credits upward, returning to the main menu once the last line has left the screen
+pub fn scroll_credits(
+    time: Res<Time>,
+    credits: Res<Credits>,
+    theme: Res<MenuTheme>,
+    ui_scale: Res<UiScale>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut roll_query: Query<(&mut CreditsRoll, &mut Style)>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    // Style values are in UI pixels, which `UiScale` stretches to logical pixels
+    let screen_height = window.height() / ui_scale.0;
+    let roll_height = credits.0.len() as f32 * theme.font_size(LINE_HEIGHT);
+
+    for (mut roll, mut style) in &mut roll_query {
+        roll.scrolled += SCROLL_SPEED * time.delta_seconds();
+        style.top = Val::Px(screen_height - roll.scrolled);
+
+        if roll.scrolled >= screen_height + roll_height {
+            info!("Credits finished");
+            next_state.set(GameState::MainMenu);
+        }
+    }
+}
+
+/// Cleanup the credits screen and its UI camera
+pub fn cleanup_credits_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<CreditsScreen>>,
+) {
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("Credits screen cleaned up");
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use bevy::window::{CursorIcon, PrimaryWindow};
+use crate::components::camera::{CameraController, GameCamera};
+use crate::resources::{GameState, HoveredTile, SelectedTool, TerrainBrush};
+
+/// Set the OS cursor to match what the mouse is doing, see `cursor_icon`
+///
+/// Only system cursors are used, which winit maps to the platform's own; where a platform
+/// lacks one it shows the default arrow instead, so there is nothing to load or fail.
+pub fn update_cursor_icon(
+    state: Res<State<GameState>>,
+    selected_tool: Res<SelectedTool>,
+    brush: Res<TerrainBrush>,
+    hovered_tile: Res<HoveredTile>,
+    camera_query: Query<&CameraController, With<GameCamera>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    // The hovered tile is cleared while the cursor is over the in-game UI
+    let tool_over_map =
+        (selected_tool.0.is_some() || brush.tool.is_some()) && hovered_tile.0.is_some();
+    let icon = cursor_icon(
+        state.get().is_gameplay(),
+        camera_query.get_single().ok(),
+        tool_over_map,
+    );
+
+    // Avoid triggering change detection, which would push the window settings every frame
+    if window.cursor.icon != icon {
+        window.cursor.icon = icon;
+    }
+}
+
+/// Cursor for what the mouse is doing
+///
+/// While the camera is drag-panned the cursor is an open hand holding the map still, and a
+/// grabbing hand once it moves. Otherwise it is a crosshair over the map while a placement or
+/// terrain tool is selected. Anywhere else, including every screen outside the game, it is the
+/// default arrow, so leaving the game always restores it.
+pub fn cursor_icon(
+    in_game: bool,
+    camera: Option<&CameraController>,
+    tool_over_map: bool,
+) -> CursorIcon {
+    if !in_game {
+        return CursorIcon::Default;
+    }
+    match camera {
+        Some(controller) if controller.dragging => {
+            if controller.drag_velocity.length() > CameraController::STOP_SPEED {
+                CursorIcon::Grabbing
+            } else {
+                CursorIcon::Grab
+            }
+        }
+        _ if tool_over_map => CursorIcon::Crosshair,
+        _ => CursorIcon::Default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dragging(drag_velocity: Vec2) -> CameraController {
+        CameraController {
+            dragging: true,
+            drag_velocity,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn menus_always_show_the_arrow() {
+        let moving = dragging(Vec2::new(100.0, 0.0));
+
+        assert_eq!(cursor_icon(false, Some(&moving), true), CursorIcon::Default);
+        assert_eq!(cursor_icon(false, None, false), CursorIcon::Default);
+    }
+
+    #[test]
+    fn dragging_the_map_shows_a_hand_over_any_tool() {
+        let held = dragging(Vec2::ZERO);
+        let moving = dragging(Vec2::new(100.0, 0.0));
+
+        assert_eq!(cursor_icon(true, Some(&held), true), CursorIcon::Grab);
+        assert_eq!(cursor_icon(true, Some(&moving), true), CursorIcon::Grabbing);
+        assert_eq!(cursor_icon(true, Some(&moving), false), CursorIcon::Grabbing);
+    }
+
+    #[test]
+    fn tools_show_a_crosshair_only_over_the_map() {
+        let idle = CameraController::default();
+
+        assert_eq!(cursor_icon(true, Some(&idle), true), CursorIcon::Crosshair);
+        assert_eq!(cursor_icon(true, Some(&idle), false), CursorIcon::Default);
+        assert_eq!(cursor_icon(true, None, true), CursorIcon::Crosshair);
+    }
+}
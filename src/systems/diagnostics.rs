@@ -0,0 +1,119 @@
+use bevy::diagnostic::{
+    DiagnosticPath, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+};
+use bevy::prelude::*;
+use crate::components::ui::*;
+use crate::resources::{DebugOverlay, GameState, SimulationClock};
+
+/// Spawn the diagnostics overlay once at startup
+///
+/// It is never despawned, so it stays available on every screen; `DebugOverlay` only
+/// controls its visibility.
+pub fn setup_debug_overlay(mut commands: Commands, overlay: Res<DebugOverlay>) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.4, 1.0, 0.4),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            ..default()
+        })
+        .with_background_color(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        // Render above every screen, including full-screen menus
+        ZIndex::Global(i32::MAX),
+        overlay_visibility(&overlay),
+        DebugOverlayText,
+    ));
+}
+
+/// Show or hide the overlay with F3
+pub fn toggle_debug_overlay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    if keyboard_input.just_pressed(DebugOverlay::TOGGLE_KEY) {
+        overlay.visible = !overlay.visible;
+        info!("Debug overlay {}", if overlay.visible { "shown" } else { "hidden" });
+    }
+}
+
+/// Apply the overlay resource to the overlay's visibility
+pub fn apply_debug_overlay_visibility(
+    overlay: Res<DebugOverlay>,
+    mut overlay_query: Query<&mut Visibility, With<DebugOverlayText>>,
+) {
+    for mut visibility in &mut overlay_query {
+        *visibility = overlay_visibility(&overlay);
+    }
+}
+
+/// Refresh the overlay text with the latest FPS, frame time and entity count
+pub fn update_debug_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    mut overlay_query: Query<&mut Text, With<DebugOverlayText>>,
+) {
+    let smoothed = |path: &DiagnosticPath| {
+        diagnostics
+            .get(path)
+            .and_then(|diagnostic| diagnostic.smoothed())
+            .unwrap_or(0.0)
+    };
+
+    let fps = smoothed(&FrameTimeDiagnosticsPlugin::FPS);
+    let frame_time = smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME);
+    let entity_count = smoothed(&EntityCountDiagnosticsPlugin::ENTITY_COUNT);
+
+    for mut text in &mut overlay_query {
+        text.sections[0].value = format!(
+            "FPS: {:.0}\nFrame time: {:.2} ms\nEntities: {:.0}",
+            fps, frame_time, entity_count
+        );
+    }
+}
+
+fn overlay_visibility(overlay: &DebugOverlay) -> Visibility {
+    if overlay.visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// Log every `GameState` transition in one place
+///
+/// While a world is running the in-game time is included, so flow problems can be matched
+/// up with what the simulation was doing.
+pub fn log_state_transitions(
+    mut transitions: EventReader<StateTransitionEvent<GameState>>,
+    clock: Option<Res<SimulationClock>>,
+) {
+    for transition in transitions.read() {
+        let from = transition
+            .exited
+            .as_ref()
+            .map_or_else(|| "None".to_string(), |state| format!("{:?}", state));
+        let to = transition
+            .entered
+            .as_ref()
+            .map_or_else(|| "None".to_string(), |state| format!("{:?}", state));
+
+        let in_world = [&transition.exited, &transition.entered]
+            .into_iter()
+            .flatten()
+            .any(|state| matches!(state, GameState::InGame | GameState::Paused));
+
+        match clock.as_deref() {
+            Some(clock) if in_world => {
+                info!("State transition: {} -> {} at {}", from, to, clock.time_label());
+            }
+            _ => info!("State transition: {} -> {}", from, to),
+        }
+    }
+}
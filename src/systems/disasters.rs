@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use crate::components::placeable::{Health, Placed};
+use crate::components::tile::{Biome, Terrain, Tile};
+use crate::events::DisasterTriggered;
+use crate::resources::{
+    is_flammable, ActiveDisasters, BurningTile, Disaster, DisasterConfig, DisasterTimer, EventLog,
+    FloodedTile, Rng, SimulationClock, TerrainMaterials, Weather, WorldConfig, TILE_SIZE,
+};
+use crate::systems::health::kill_placed;
+use crate::utils::neighbors;
+
+/// Outline drawn over each burning tile
+const FIRE_COLOR: Color = Color::srgba(1.0, 0.45, 0.1, 0.9);
+
+/// Start each new world with no disasters under way
+pub fn reset_disasters(mut active: ResMut<ActiveDisasters>, mut timer: ResMut<DisasterTimer>) {
+    *active = ActiveDisasters::default();
+    *timer = DisasterTimer::default();
+}
+
+/// Roll for a disaster every `check_interval_hours` of in-game time
+///
+/// The odds depend on the weather and the rolls come from the seeded `Rng`, so a world replays
+/// the same disasters. At most one disaster strikes per check.
+pub fn roll_disasters(
+    clock: Res<SimulationClock>,
+    config: Res<DisasterConfig>,
+    weather: Res<Weather>,
+    mut timer: ResMut<DisasterTimer>,
+    mut rng: ResMut<Rng>,
+    mut triggered: EventWriter<DisasterTriggered>,
+) {
+    if clock.elapsed_hours < timer.next_check_hour {
+        return;
+    }
+    timer.next_check_hour = clock.elapsed_hours + config.check_interval_hours;
+
+    let struck = Disaster::ALL
+        .into_iter()
+        .find(|disaster| rng.chance(config.chance(*disaster, *weather)));
+    if let Some(disaster) = struck {
+        triggered.send(DisasterTriggered(disaster));
+    }
+}
+
+/// Set triggered disasters off at a random spot on the map
+///
+/// A wildfire starts on a flammable tile, a flood on low land at the water's edge, and an
+/// earthquake anywhere on land. A disaster with nowhere to start is skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn start_disasters(
+    mut commands: Commands,
+    mut triggered: EventReader<DisasterTriggered>,
+    clock: Res<SimulationClock>,
+    config: Res<DisasterConfig>,
+    terrain_materials: Res<TerrainMaterials>,
+    mut active: ResMut<ActiveDisasters>,
+    mut rng: ResMut<Rng>,
+    mut event_log: ResMut<EventLog>,
+    mut tile_query: Query<(&mut Tile, &mut Handle<ColorMaterial>)>,
+    mut placed_query: Query<(Entity, &Placed, &mut Health)>,
+) {
+    for DisasterTriggered(disaster) in triggered.read() {
+        let tiles: HashMap<UVec2, (Terrain, Biome)> = tile_query
+            .iter()
+            .map(|(tile, _)| (tile.coord, (tile.terrain, tile.biome)))
+            .collect();
+        let borders_water = |coord: UVec2| {
+            neighbors(coord).any(|neighbor| tiles.get(&neighbor).is_some_and(|(terrain, _)| terrain.is_water()))
+        };
+
+        let mut candidates: Vec<UVec2> = tiles
+            .iter()
+            .filter(|(coord, (terrain, biome))| match disaster {
+                Disaster::Wildfire => is_flammable(*terrain, *biome) && !active.burnt.contains(*coord),
+                Disaster::Flood => *terrain == Terrain::Sand && borders_water(**coord),
+                Disaster::Earthquake => !terrain.is_water(),
+            })
+            .map(|(coord, _)| *coord)
+            .collect();
+        // HashMap order varies between runs, so sort before choosing
+        candidates.sort_by_key(|coord| (coord.y, coord.x));
+        let Some(origin) = candidates.choose(&mut *rng).copied() else {
+            info!("Nowhere for a {} to strike", disaster.name().to_lowercase());
+            continue;
+        };
+        info!("{} struck at {}", disaster.name(), origin);
+
+        match disaster {
+            Disaster::Wildfire => {
+                active.burnt.insert(origin);
+                active.burning.push(BurningTile {
+                    coord: origin,
+                    burns_out_hour: clock.elapsed_hours + config.wildfire_burn_hours,
+                });
+                event_log.push(&clock, "A wildfire broke out", Some(origin));
+            }
+            Disaster::Flood => {
+                for (mut tile, mut material) in &mut tile_query {
+                    let distance = (tile.coord.as_ivec2() - origin.as_ivec2()).abs().max_element();
+                    if !floods(tile.terrain, distance, config.flood_radius) {
+                        continue;
+                    }
+                    active.flooded.push(FloodedTile {
+                        coord: tile.coord,
+                        terrain: tile.terrain,
+                        recedes_hour: clock.elapsed_hours + config.flood_hours,
+                    });
+                    tile.terrain = Terrain::Water;
+                    *material = terrain_materials.get(Terrain::Water, tile.biome);
+                }
+                event_log.push(&clock, "A flood swept over the land", Some(origin));
+            }
+            Disaster::Earthquake => {
+                let within_reach = |coord: UVec2| {
+                    (coord.as_ivec2() - origin.as_ivec2()).abs().max_element() <= config.earthquake_radius
+                };
+                for (mut tile, mut material) in &mut tile_query {
+                    if !within_reach(tile.coord) || !rng.chance(config.earthquake_tile_chance) {
+                        continue;
+                    }
+                    let terrain = quake_terrain(tile.terrain);
+                    if terrain != tile.terrain {
+                        tile.terrain = terrain;
+                        *material = terrain_materials.get(terrain, tile.biome);
+                    }
+                }
+                for (entity, placed, mut health) in &mut placed_query {
+                    if within_reach(placed.coord) {
+                        health.0 -= config.earthquake_damage;
+                        if health.is_dead() {
+                            kill_placed(&mut commands, &mut event_log, &clock, entity, placed, "in an earthquake");
+                        }
+                    }
+                }
+                event_log.push(&clock, "An earthquake shook the land", Some(origin));
+            }
+        }
+    }
+}
+
+/// Play out the disasters under way every `wildfire_step_hours` of in-game time
+///
+/// Fires hurt the entities standing in them, spread to flammable neighbors and go out after
+/// `wildfire_burn_hours`, leaving burnt forest as grassland. Floods recede after `flood_hours`,
+/// returning tiles still under water to their old terrain.
+#[allow(clippy::too_many_arguments)]
+pub fn advance_disasters(
+    mut commands: Commands,
+    clock: Res<SimulationClock>,
+    config: Res<DisasterConfig>,
+    terrain_materials: Res<TerrainMaterials>,
+    mut timer: ResMut<DisasterTimer>,
+    mut active: ResMut<ActiveDisasters>,
+    mut rng: ResMut<Rng>,
+    mut event_log: ResMut<EventLog>,
+    mut tile_query: Query<(&mut Tile, &mut Handle<ColorMaterial>)>,
+    mut placed_query: Query<(Entity, &Placed, &mut Health)>,
+) {
+    if clock.elapsed_hours < timer.next_step_hour {
+        return;
+    }
+    timer.next_step_hour = clock.elapsed_hours + config.wildfire_step_hours;
+    if active.burning.is_empty() && active.flooded.is_empty() {
+        return;
+    }
+    let now = clock.elapsed_hours;
+
+    for (entity, placed, mut health) in &mut placed_query {
+        if active.burning.iter().any(|burning| burning.coord == placed.coord) {
+            health.0 -= config.wildfire_damage;
+            if health.is_dead() {
+                kill_placed(&mut commands, &mut event_log, &clock, entity, placed, "in a wildfire");
+            }
+        }
+    }
+
+    if !active.burning.is_empty() {
+        let tiles: HashMap<UVec2, (Terrain, Biome)> = tile_query
+            .iter()
+            .map(|(tile, _)| (tile.coord, (tile.terrain, tile.biome)))
+            .collect();
+        let mut ignited: Vec<UVec2> = Vec::new();
+        for burning in &active.burning {
+            for neighbor in neighbors(burning.coord) {
+                let catches = tiles
+                    .get(&neighbor)
+                    .is_some_and(|(terrain, biome)| is_flammable(*terrain, *biome))
+                    && !active.burnt.contains(&neighbor)
+                    && !ignited.contains(&neighbor);
+                if catches && rng.chance(config.wildfire_spread_chance) {
+                    ignited.push(neighbor);
+                }
+            }
+        }
+        for coord in ignited {
+            active.burnt.insert(coord);
+            active.burning.push(BurningTile {
+                coord,
+                burns_out_hour: now + config.wildfire_burn_hours,
+            });
+        }
+    }
+
+    let burnt_out: Vec<UVec2> = active
+        .burning
+        .iter()
+        .filter(|burning| burning.burns_out_hour <= now)
+        .map(|burning| burning.coord)
+        .collect();
+    active.burning.retain(|burning| burning.burns_out_hour > now);
+    if !burnt_out.is_empty() && active.burning.is_empty() {
+        active.burnt.clear();
+        event_log.push(&clock, "The wildfire burned out", None);
+    }
+
+    let receded: Vec<FloodedTile> = active
+        .flooded
+        .iter()
+        .filter(|flooded| flooded.recedes_hour <= now)
+        .copied()
+        .collect();
+    active.flooded.retain(|flooded| flooded.recedes_hour > now);
+    if !receded.is_empty() && active.flooded.is_empty() {
+        event_log.push(&clock, "The floodwaters receded", None);
+    }
+
+    if burnt_out.is_empty() && receded.is_empty() {
+        return;
+    }
+    for (mut tile, mut material) in &mut tile_query {
+        if burnt_out.contains(&tile.coord) && tile.terrain == Terrain::Grass && tile.biome == Biome::Forest {
+            tile.biome = Biome::Grassland;
+            *material = terrain_materials.get(tile.terrain, tile.biome);
+        }
+        // The weather may have dried the tile out already
+        if let Some(flooded) = receded.iter().find(|flooded| flooded.coord == tile.coord) {
+            if tile.terrain == Terrain::Water {
+                tile.terrain = flooded.terrain;
+                *material = terrain_materials.get(tile.terrain, tile.biome);
+            }
+        }
+    }
+}
+
+/// Outline the burning tiles
+pub fn draw_wildfires(mut gizmos: Gizmos, active: Res<ActiveDisasters>, config: Res<WorldConfig>) {
+    for burning in &active.burning {
+        gizmos.rect_2d(
+            config.tile_center(burning.coord),
+            Rot2::IDENTITY,
+            Vec2::splat(TILE_SIZE * 0.8),
+            FIRE_COLOR,
+        );
+    }
+}
+
+/// Whether a flood reaches a tile of some terrain at a distance from where it started
+///
+/// Sand is the lowest land and floods across the whole reach, while grass only floods near the
+/// shore.
+fn floods(terrain: Terrain, distance: i32, radius: i32) -> bool {
+    match terrain {
+        Terrain::Sand => distance <= radius,
+        Terrain::Grass => distance <= radius / 2,
+        _ => false,
+    }
+}
+
+/// Terrain a tile shakes into during an earthquake
+fn quake_terrain(terrain: Terrain) -> Terrain {
+    match terrain {
+        Terrain::Grass => Terrain::Mountain,
+        Terrain::Mountain => Terrain::Grass,
+        Terrain::Sand => Terrain::Water,
+        Terrain::Water => Terrain::Water,
+        Terrain::River => Terrain::River,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::placeable::EntityKind;
+
+    /// Disasters only strike when triggered by the test
+    fn triggered_only() -> DisasterConfig {
+        DisasterConfig {
+            wildfire_chance: 0.0,
+            flood_chance: 0.0,
+            earthquake_chance: 0.0,
+            ..default()
+        }
+    }
+
+    fn disaster_test_app(config: DisasterConfig) -> App {
+        let terrain_materials = Terrain::ALL
+            .iter()
+            .flat_map(|terrain| Biome::ALL.iter().map(move |biome| ((*terrain, *biome), Handle::default())))
+            .collect();
+
+        let mut app = App::new();
+        app.init_resource::<SimulationClock>()
+            .insert_resource(config)
+            .insert_resource(TerrainMaterials(terrain_materials))
+            .init_resource::<ActiveDisasters>()
+            .init_resource::<DisasterTimer>()
+            .init_resource::<Weather>()
+            .insert_resource(Rng::from_seed(1))
+            .init_resource::<EventLog>()
+            .add_event::<DisasterTriggered>()
+            .add_systems(Update, (roll_disasters, start_disasters).chain());
+        app
+    }
+
+    /// Spawn a row of tiles along y = 0, one per terrain
+    fn spawn_row(app: &mut App, terrains: &[Terrain]) {
+        for (x, terrain) in terrains.iter().enumerate() {
+            app.world_mut().spawn((
+                Tile {
+                    coord: UVec2::new(x as u32, 0),
+                    terrain: *terrain,
+                    biome: Biome::Grassland,
+                },
+                Handle::<ColorMaterial>::default(),
+            ));
+        }
+    }
+
+    fn terrain_row(app: &mut App) -> Vec<Terrain> {
+        let world = app.world_mut();
+        let mut tiles: Vec<Tile> = world.query::<&Tile>().iter(world).cloned().collect();
+        tiles.sort_by_key(|tile| tile.coord.x);
+        tiles.into_iter().map(|tile| tile.terrain).collect()
+    }
+
+    /// Run a number of disaster checks, returning what struck at each
+    fn roll_checks(seed: u64, weather: Weather, checks: u32) -> Vec<Option<Disaster>> {
+        let config = DisasterConfig {
+            wildfire_chance: 0.3,
+            flood_chance: 0.3,
+            earthquake_chance: 0.1,
+            ..default()
+        };
+        let mut app = disaster_test_app(config.clone());
+        app.insert_resource(Rng::from_seed(seed)).insert_resource(weather);
+
+        (0..checks)
+            .map(|check| {
+                app.world_mut().resource_mut::<SimulationClock>().elapsed_hours =
+                    check as f64 * config.check_interval_hours;
+                app.update();
+                app.world_mut()
+                    .resource_mut::<Events<DisasterTriggered>>()
+                    .drain()
+                    .map(|DisasterTriggered(disaster)| disaster)
+                    .next()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn floods_reach_further_over_sand_than_grass() {
+        let radius = DisasterConfig::default().flood_radius;
+
+        assert!(floods(Terrain::Sand, radius, radius));
+        assert!(!floods(Terrain::Sand, radius + 1, radius));
+        assert!(floods(Terrain::Grass, radius / 2, radius));
+        assert!(!floods(Terrain::Grass, radius / 2 + 1, radius));
+        assert!(!floods(Terrain::Mountain, 0, radius));
+        assert!(!floods(Terrain::Water, 0, radius));
+    }
+
+    #[test]
+    fn floods_spread_from_the_shore() {
+        let mut app = disaster_test_app(triggered_only());
+        let radius = DisasterConfig::default().flood_radius as usize;
+        let mut row = vec![Terrain::Water, Terrain::Sand];
+        row.extend([Terrain::Grass; 6]);
+        spawn_row(&mut app, &row);
+
+        app.world_mut().send_event(DisasterTriggered(Disaster::Flood));
+        app.update();
+
+        // The flood starts on the only sand by the water, one tile from the shore
+        let flooded_until = 1 + radius / 2;
+        let terrain = terrain_row(&mut app);
+        for (x, terrain) in terrain.into_iter().enumerate() {
+            let expected = if x <= flooded_until { Terrain::Water } else { Terrain::Grass };
+            assert_eq!(terrain, expected, "tile {}", x);
+        }
+        assert_eq!(app.world().resource::<ActiveDisasters>().flooded.len(), flooded_until);
+    }
+
+    #[test]
+    fn earthquakes_reshape_the_land_within_reach() {
+        let mut app = disaster_test_app(DisasterConfig {
+            earthquake_tile_chance: 1.0,
+            ..triggered_only()
+        });
+        // Every tile is within reach wherever the quake strikes
+        spawn_row(&mut app, &[Terrain::Grass, Terrain::Sand, Terrain::Mountain, Terrain::Water]);
+        let villager = app
+            .world_mut()
+            .spawn((
+                Placed {
+                    kind: EntityKind::Villager,
+                    coord: UVec2::new(0, 0),
+                },
+                Health(Health::MAX),
+            ))
+            .id();
+
+        app.world_mut().send_event(DisasterTriggered(Disaster::Earthquake));
+        app.update();
+
+        assert_eq!(
+            terrain_row(&mut app),
+            vec![Terrain::Mountain, Terrain::Water, Terrain::Grass, Terrain::Water]
+        );
+        assert_eq!(
+            app.world().get::<Health>(villager).map(|health| health.0),
+            Some(Health::MAX - DisasterConfig::default().earthquake_damage)
+        );
+    }
+
+    #[test]
+    fn the_same_seed_rolls_the_same_disasters() {
+        let first = roll_checks(42, Weather::Clear, 50);
+
+        assert_eq!(first, roll_checks(42, Weather::Clear, 50));
+        assert!(first.iter().any(Option::is_some));
+    }
+
+    #[test]
+    fn rain_rules_out_wildfires() {
+        let struck = roll_checks(7, Weather::Rain, 50);
+
+        assert!(!struck.contains(&Some(Disaster::Wildfire)));
+        assert!(struck.contains(&Some(Disaster::Flood)));
+    }
+}
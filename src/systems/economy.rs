@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use crate::components::placeable::Placed;
+use crate::components::tile::Tile;
+use crate::resources::{
+    EventLog, GameOverReason, GameState, Population, ResourceKind, Resources, Sandbox, SimulationClock,
+};
+
+/// Start each new world with its starting stockpile
+pub fn reset_resources(mut resources: ResMut<Resources>) {
+    *resources = Resources::default();
+}
+
+/// Produce and consume resources once per in-game day, starving the population during
+/// prolonged shortages
+#[allow(clippy::too_many_arguments)]
+pub fn update_economy(
+    clock: Res<SimulationClock>,
+    tile_query: Query<&Tile>,
+    placed_query: Query<&Placed>,
+    mut resources: ResMut<Resources>,
+    mut population: ResMut<Population>,
+    mut event_log: ResMut<EventLog>,
+    sandbox: Res<Sandbox>,
+    mut game_over_reason: ResMut<GameOverReason>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let day = clock.elapsed_days();
+    if day <= resources.last_update_day {
+        return;
+    }
+    resources.last_update_day = day;
+
+    for kind in ResourceKind::ALL {
+        let produced = tile_query
+            .iter()
+            .map(|tile| kind.tile_yield(tile.terrain, tile.biome))
+            .chain(placed_query.iter().map(|placed| kind.entity_yield(placed.kind)))
+            .sum::<f32>();
+        let consumed = kind.consumption_per_person() * population.count as f32;
+        resources.apply_day(kind, produced, consumed);
+    }
+
+    let shortages: Vec<ResourceKind> = resources.shortages().collect();
+    for kind in &shortages {
+        // Only the first day past the grace period starts a shortage
+        if resources.days_empty(*kind) == Resources::SHORTAGE_GRACE_DAYS + 1 {
+            event_log.push(&clock, format!("{} shortage began", kind.label()), None);
+        }
+    }
+    for kind in &shortages {
+        let lost = ((population.count as f32 * Resources::SHORTAGE_PENALTY).ceil() as u32)
+            .min(population.count);
+        if lost > 0 {
+            warn!("{} lost to a shortage of {}", lost, kind.label().to_lowercase());
+            population.count -= lost;
+        }
+    }
+
+    // Sandbox worlds carry on without anyone left
+    if population.count == 0 && !sandbox.0 {
+        if let Some(kind) = shortages.first() {
+            game_over_reason.0 =
+                format!("Your people ran out of {} on day {}.", kind.label().to_lowercase(), clock.day());
+            next_state.set(GameState::GameOver);
+        }
+    }
+}
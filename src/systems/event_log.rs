@@ -0,0 +1,247 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use crate::components::camera::GameCamera;
+use crate::components::tooltip::Tooltip;
+use crate::components::ui::*;
+use crate::resources::{EventLog, WorldConfig};
+
+const PANEL_WIDTH: f32 = 320.0;
+/// Height of the visible part of the list, which scrolls when there are more entries
+const LIST_HEIGHT: f32 = 180.0;
+const ENTRY_FONT_SIZE: f32 = 16.0;
+/// Pixels scrolled per line of mouse wheel movement
+const SCROLL_LINE_HEIGHT: f32 = 20.0;
+/// The panel has a dark backdrop whatever the menu theme
+const ENTRY_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+/// Entries with a place on the map stand out, since clicking them moves the camera there
+const LOCATED_ENTRY_COLOR: Color = Color::srgb(1.0, 0.85, 0.4);
+
+/// Start each new world with an empty log
+pub fn reset_event_log(mut event_log: ResMut<EventLog>) {
+    *event_log = EventLog::default();
+}
+
+/// Setup the event log panel below the top bar on the left, expanded
+pub fn setup_event_log_panel(mut commands: Commands, theme: Res<MenuTheme>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(54.0),
+                    left: Val::Px(10.0),
+                    width: Val::Px(PANEL_WIDTH),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(6.0)),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            EventLogPanel { expanded: true },
+            // Tracks hovering so the panel takes clicks and scrolls meant for it
+            Interaction::default(),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        background_color: Color::NONE.into(),
+                        focus_policy: FocusPolicy::Pass,
+                        ..default()
+                    },
+                    EventLogToggle,
+                    Tooltip::new("Show or hide recent events"),
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        toggle_label(true),
+                        TextStyle {
+                            font_size: theme.font_size(20.0),
+                            color: ENTRY_COLOR,
+                            ..default()
+                        },
+                    ));
+                });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        height: Val::Px(LIST_HEIGHT),
+                        overflow: Overflow::clip(),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|viewport| {
+                    viewport.spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                width: Val::Percent(100.0),
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(2.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        EventLogList::default(),
+                    ));
+                });
+        });
+}
+
+/// The event log header, when its interaction state changed this frame
+type ChangedEventLogToggle<'w, 's> =
+    Query<'w, 's, (&'static Interaction, &'static Children), (Changed<Interaction>, With<EventLogToggle>)>;
+
+/// Collapse or expand the event log when its header is clicked
+pub fn toggle_event_log(
+    toggle_query: ChangedEventLogToggle,
+    mut panel_query: Query<&mut EventLogPanel>,
+    mut text_query: Query<&mut Text>,
+    list_query: Query<&Parent, With<EventLogList>>,
+    mut style_query: Query<&mut Style>,
+) {
+    for (interaction, children) in &toggle_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok(mut panel) = panel_query.get_single_mut() else {
+            return;
+        };
+        panel.expanded = !panel.expanded;
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = toggle_label(panel.expanded).to_string();
+            }
+        }
+        // The list's viewport holds everything below the header
+        for viewport in &list_query {
+            if let Ok(mut style) = style_query.get_mut(viewport.get()) {
+                style.display = if panel.expanded {
+                    Display::Flex
+                } else {
+                    Display::None
+                };
+            }
+        }
+    }
+}
+
+/// Rebuild the list from the log when it changes, newest entry first
+pub fn update_event_log_list(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    event_log: Res<EventLog>,
+    mut list_query: Query<(Entity, &mut EventLogList, &mut Style)>,
+) {
+    for (entity, mut list, mut style) in &mut list_query {
+        // A freshly spawned panel, such as after unpausing, needs filling too
+        if !event_log.is_changed() && !list.is_added() {
+            continue;
+        }
+        list.offset = 0.0;
+        style.top = Val::Px(0.0);
+
+        commands.entity(entity).despawn_descendants().with_children(|parent| {
+            for entry in event_log.latest() {
+                let color = if entry.location.is_some() {
+                    LOCATED_ENTRY_COLOR
+                } else {
+                    ENTRY_COLOR
+                };
+                let mut row = parent.spawn((
+                    ButtonBundle {
+                        background_color: Color::NONE.into(),
+                        focus_policy: FocusPolicy::Pass,
+                        ..default()
+                    },
+                    EventLogEntry {
+                        location: entry.location,
+                    },
+                ));
+                if entry.location.is_some() {
+                    row.insert(Tooltip::new("Click to go there"));
+                }
+                row.with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        format!("{}  {}", entry.timestamp, entry.message),
+                        TextStyle {
+                            font_size: theme.font_size(ENTRY_FONT_SIZE),
+                            color,
+                            ..default()
+                        },
+                    ));
+                });
+            }
+        });
+    }
+}
+
+/// Scroll the list with the mouse wheel while the cursor is over the panel
+pub fn scroll_event_log(
+    mut scroll_events: EventReader<MouseWheel>,
+    panel_query: Query<&Interaction, With<EventLogPanel>>,
+    mut list_query: Query<(&mut EventLogList, &mut Style, &Node, &Parent)>,
+    viewport_query: Query<&Node>,
+) {
+    let scroll: f32 = scroll_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        })
+        .sum();
+    if scroll == 0.0 || panel_query.iter().all(|interaction| *interaction == Interaction::None) {
+        return;
+    }
+
+    for (mut list, mut style, node, viewport) in &mut list_query {
+        let visible = viewport_query
+            .get(viewport.get())
+            .map_or(LIST_HEIGHT, |viewport| viewport.size().y);
+        let max_offset = (node.size().y - visible).max(0.0);
+        list.offset = (list.offset - scroll).clamp(0.0, max_offset);
+        style.top = Val::Px(-list.offset);
+    }
+}
+
+/// Recenter the gameplay camera on the location of a clicked entry
+pub fn recenter_camera_on_entry(
+    config: Res<WorldConfig>,
+    entry_query: Query<(&Interaction, &EventLogEntry), Changed<Interaction>>,
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
+) {
+    for (interaction, entry) in &entry_query {
+        let (Interaction::Pressed, Some(location)) = (interaction, entry.location) else {
+            continue;
+        };
+        let target = config.tile_center(location);
+        if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+            camera_transform.translation.x = target.x;
+            camera_transform.translation.y = target.y;
+        }
+    }
+}
+
+/// Cleanup the event log panel when leaving the game
+pub fn cleanup_event_log_panel(
+    mut commands: Commands,
+    panel_query: Query<Entity, With<EventLogPanel>>,
+) {
+    for entity in &panel_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn toggle_label(expanded: bool) -> &'static str {
+    if expanded {
+        "Event Log  [-]"
+    } else {
+        "Event Log  [+]"
+    }
+}
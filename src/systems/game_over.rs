@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use crate::components::camera::UiCamera;
+use crate::components::ui::*;
+use crate::i18n::Locale;
+use crate::resources::GameOverReason;
+use crate::systems::menu::create_menu_button;
+
+/// Setup the game over screen showing why the world ended
+pub fn setup_game_over_screen(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    reason: Res<GameOverReason>,
+) {
+    info!("Setting up game over screen: {}", reason.0);
+
+    // Spawn a camera for UI rendering
+    commands.spawn((Camera2dBundle::default(), UiCamera, GameOverScreen));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::srgb(0.15, 0.05, 0.05).into(),
+                ..default()
+            },
+            GameOverScreen,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                TextBundle::from_section(
+                    "Your world has ended",
+                    TextStyle {
+                        font_size: theme.title_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Title,
+            ));
+
+            // Cause of failure
+            parent.spawn(TextBundle::from_section(
+                reason.0.clone(),
+                TextStyle {
+                    font_size: theme.font_size(24.0),
+                    color: Color::srgb(0.8, 0.7, 0.7),
+                    ..default()
+                },
+            ));
+
+            // Button container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(15.0),
+                        margin: UiRect::top(Val::Px(40.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    create_menu_button(parent, &theme, &locale, "New World", MenuAction::NewWorldkeeper);
+                    create_menu_button(parent, &theme, &locale, "Return to Menu", MenuAction::ReturnToMenu);
+                });
+        });
+}
+
+/// Cleanup the game over screen and its UI camera
+pub fn cleanup_game_over_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<GameOverScreen>>,
+) {
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    info!("Game over screen cleaned up");
+}
@@ -0,0 +1,30 @@
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::prelude::*;
+
+/// Get the gamepad that should drive input, which is the first connected one
+///
+/// Returns `None` when no gamepad is connected so callers fall back to the keyboard.
+pub fn active_gamepad(gamepads: &Gamepads) -> Option<Gamepad> {
+    gamepads.iter().next()
+}
+
+/// Check whether a button was just pressed on the active gamepad
+pub fn gamepad_just_pressed(
+    gamepads: &Gamepads,
+    gamepad_buttons: &ButtonInput<GamepadButton>,
+    button_type: GamepadButtonType,
+) -> bool {
+    active_gamepad(gamepads)
+        .is_some_and(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+}
+
+/// Log gamepad connections and disconnections
+pub fn log_gamepad_connections(mut connection_events: EventReader<GamepadConnectionEvent>) {
+    for event in connection_events.read() {
+        if event.connected() {
+            info!("Gamepad {:?} connected", event.gamepad);
+        } else if event.disconnected() {
+            info!("Gamepad {:?} disconnected, falling back to keyboard", event.gamepad);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::components::camera::GameCamera;
+use crate::components::tile::{Terrain, Tile};
+use crate::resources::{InputAction, KeyBindings, Settings, ShowGrid, WorldConfig, TILE_SIZE};
+use crate::utils::visible_world_rect;
+
+const GRID_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.35);
+const PATTERN_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.45);
+/// Zoom level past which terrain patterns are too small to read and are no longer drawn
+const MAX_PATTERN_SCALE: f32 = 2.0;
+
+/// Show or hide the tile grid with the grid key, G by default
+pub fn toggle_grid(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut show_grid: ResMut<ShowGrid>,
+) {
+    if key_bindings.just_pressed(&keyboard_input, InputAction::ToggleGrid) {
+        show_grid.0 = !show_grid.0;
+    }
+}
+
+/// Draw grid lines along the tile edges, over the terrain and under the UI
+///
+/// Drawn with gizmos in world space, so the lines follow the tiles at any zoom. Only the lines
+/// crossing the visible part of the map are drawn.
+pub fn draw_grid(
+    mut gizmos: Gizmos,
+    show_grid: Res<ShowGrid>,
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+) {
+    if !show_grid.0 {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let bounds = config.bounds();
+    let visible = visible_world_rect(window.size(), projection, camera_transform)
+        .intersect(Rect::from_corners(bounds.min, bounds.max));
+    if visible.is_empty() {
+        return;
+    }
+    let (visible_min, visible_max) = (visible.min, visible.max);
+
+    // Tile edges are whole multiples of the tile size away from the map's bottom-left corner
+    let first = ((visible_min - bounds.min) / TILE_SIZE).ceil();
+    let last = ((visible_max - bounds.min) / TILE_SIZE).floor();
+
+    for column in first.x as u32..=last.x as u32 {
+        let x = bounds.min.x + column as f32 * TILE_SIZE;
+        gizmos.line_2d(Vec2::new(x, visible_min.y), Vec2::new(x, visible_max.y), GRID_COLOR);
+    }
+    for row in first.y as u32..=last.y as u32 {
+        let y = bounds.min.y + row as f32 * TILE_SIZE;
+        gizmos.line_2d(Vec2::new(visible_min.x, y), Vec2::new(visible_max.x, y), GRID_COLOR);
+    }
+}
+
+/// Mark each visible tile with a pattern for its terrain when terrain patterns are turned on
+///
+/// Lets terrain types be told apart without relying on color: water has two waves, rivers
+/// one, sand is dotted and mountains have a peak. Grass is left plain.
+pub fn draw_terrain_patterns(
+    mut gizmos: Gizmos,
+    settings: Res<Settings>,
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    tile_query: Query<&Tile>,
+) {
+    if !settings.terrain_patterns {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    if projection.scale > MAX_PATTERN_SCALE {
+        return;
+    }
+
+    // Grown by a tile so patterns on tiles cut by the window edge are drawn too
+    let visible = visible_world_rect(window.size(), projection, camera_transform).inflate(TILE_SIZE);
+    let unit = TILE_SIZE / 8.0;
+
+    for tile in &tile_query {
+        let center = config.tile_center(tile.coord);
+        if !visible.contains(center) {
+            continue;
+        }
+        match tile.terrain {
+            Terrain::Water => {
+                for offset in [unit, -unit] {
+                    draw_wave(&mut gizmos, center + Vec2::Y * offset, unit);
+                }
+            }
+            Terrain::River => draw_wave(&mut gizmos, center, unit),
+            Terrain::Sand => {
+                for offset in [Vec2::new(-1.0, 1.0), Vec2::new(1.5, 0.5), Vec2::new(-0.5, -1.5)] {
+                    gizmos.circle_2d(center + offset * unit, unit * 0.25, PATTERN_COLOR);
+                }
+            }
+            Terrain::Mountain => {
+                let peak = center + Vec2::Y * unit * 1.5;
+                gizmos.line_2d(center + Vec2::new(-unit * 2.0, -unit * 1.5), peak, PATTERN_COLOR);
+                gizmos.line_2d(peak, center + Vec2::new(unit * 2.0, -unit * 1.5), PATTERN_COLOR);
+            }
+            Terrain::Grass => {}
+        }
+    }
+}
+
+/// A small zigzag wave centered on a point
+fn draw_wave(gizmos: &mut Gizmos, center: Vec2, unit: f32) {
+    const WAVE: [Vec2; 5] = [
+        Vec2::new(-2.0, -0.4),
+        Vec2::new(-1.0, 0.4),
+        Vec2::new(0.0, -0.4),
+        Vec2::new(1.0, 0.4),
+        Vec2::new(2.0, -0.4),
+    ];
+    gizmos.linestrip_2d(WAVE.map(|point| center + point * unit), PATTERN_COLOR);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_follows_the_rebound_key() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<ShowGrid>()
+            .add_systems(Update, toggle_grid);
+        app.world_mut()
+            .resource_mut::<KeyBindings>()
+            .bind(InputAction::ToggleGrid, KeyCode::KeyH)
+            .unwrap();
+
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyG);
+        app.update();
+        assert!(!app.world().resource::<ShowGrid>().0);
+
+        let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard_input.clear();
+        keyboard_input.press(KeyCode::KeyH);
+        app.update();
+        assert!(app.world().resource::<ShowGrid>().0);
+    }
+}
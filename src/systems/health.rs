@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use crate::components::placeable::{Health, Placed};
+use crate::components::tile::{Terrain, Tile};
+use crate::resources::{
+    DecayConfig, DecayTimer, EventLog, HoveredTile, Selection, SimulationClock, TerrainRules, Weather,
+    WorldConfig, TILE_SIZE,
+};
+
+/// Health bars float this far above the center of their entity's tile
+const HEALTH_BAR_OFFSET: f32 = TILE_SIZE * 0.55;
+const HEALTH_BAR_WIDTH: f32 = TILE_SIZE * 0.8;
+const HEALTH_BAR_BACKGROUND: Color = Color::srgb(0.15, 0.15, 0.15);
+const HEALTH_FULL_COLOR: Color = Color::srgb(0.3, 0.85, 0.3);
+const HEALTH_EMPTY_COLOR: Color = Color::srgb(0.9, 0.2, 0.15);
+
+/// Start each new world's decay on the first tick
+pub fn reset_decay_timer(mut timer: ResMut<DecayTimer>) {
+    *timer = DecayTimer::default();
+}
+
+/// Wear down placed entities every `interval_hours` of in-game time
+///
+/// Entities lose health on terrain `TerrainRules` wouldn't allow them on and in harsh weather,
+/// at the rates in `DecayConfig`. Those that reach zero die, and their deaths are logged.
+#[allow(clippy::too_many_arguments)]
+pub fn decay_health(
+    mut commands: Commands,
+    clock: Res<SimulationClock>,
+    config: Res<DecayConfig>,
+    terrain_rules: Res<TerrainRules>,
+    weather: Res<Weather>,
+    mut timer: ResMut<DecayTimer>,
+    mut event_log: ResMut<EventLog>,
+    tile_query: Query<&Tile>,
+    mut placed_query: Query<(Entity, &Placed, &mut Health)>,
+) {
+    if clock.elapsed_hours < timer.next_decay_hour {
+        return;
+    }
+    timer.next_decay_hour = clock.elapsed_hours + config.interval_hours;
+
+    let terrain_by_coord: HashMap<UVec2, Terrain> =
+        tile_query.iter().map(|tile| (tile.coord, tile.terrain)).collect();
+    let days = (config.interval_hours / SimulationClock::HOURS_PER_DAY) as f32;
+
+    for (entity, placed, mut health) in &mut placed_query {
+        let hostile = terrain_by_coord
+            .get(&placed.coord)
+            .is_some_and(|terrain| !terrain_rules.can_place(placed.kind, *terrain));
+        let damage = config.damage_per_day(placed.kind.category(), *weather, hostile) * days;
+        if damage <= 0.0 {
+            continue;
+        }
+
+        health.0 -= damage;
+        if health.is_dead() {
+            let cause = if hostile {
+                "on hostile ground".to_string()
+            } else {
+                format!("in the {}", weather.label().to_lowercase())
+            };
+            kill_placed(&mut commands, &mut event_log, &clock, entity, placed, &cause);
+        }
+    }
+}
+
+/// Despawn a placed entity whose health ran out, logging how it died
+pub fn kill_placed(
+    commands: &mut Commands,
+    event_log: &mut EventLog,
+    clock: &SimulationClock,
+    entity: Entity,
+    placed: &Placed,
+    cause: &str,
+) {
+    info!("{} at {} died {}", placed.kind.name(), placed.coord, cause);
+    event_log.push(
+        clock,
+        format!("A {} died {}", placed.kind.name().to_lowercase(), cause),
+        Some(placed.coord),
+    );
+    commands.entity(entity).despawn_recursive();
+}
+
+/// Draw a small health bar above selected entities and the entity under the cursor
+pub fn draw_health_bars(
+    mut gizmos: Gizmos,
+    selection: Res<Selection>,
+    hovered_tile: Res<HoveredTile>,
+    config: Res<WorldConfig>,
+    placed_query: Query<(Entity, &Placed, &Health)>,
+) {
+    for (entity, placed, health) in &placed_query {
+        if !selection.0.contains(&entity) && hovered_tile.0 != Some(placed.coord) {
+            continue;
+        }
+
+        let start = config.tile_center(placed.coord)
+            + Vec2::new(-HEALTH_BAR_WIDTH / 2.0, HEALTH_BAR_OFFSET);
+        let fill = HEALTH_EMPTY_COLOR.mix(&HEALTH_FULL_COLOR, health.fraction());
+        gizmos.line_2d(start, start + Vec2::X * HEALTH_BAR_WIDTH, HEALTH_BAR_BACKGROUND);
+        gizmos.line_2d(start, start + Vec2::X * HEALTH_BAR_WIDTH * health.fraction(), fill);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::placeable::EntityKind;
+    use crate::components::Biome;
+
+    fn decay_test_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<SimulationClock>()
+            .init_resource::<DecayConfig>()
+            .init_resource::<TerrainRules>()
+            .init_resource::<Weather>()
+            .init_resource::<DecayTimer>()
+            .init_resource::<EventLog>()
+            .add_systems(Update, decay_health);
+        app
+    }
+
+    /// Spawn a tree on a tile of the given terrain
+    fn spawn_tree(app: &mut App, coord: UVec2, terrain: Terrain) -> Entity {
+        app.world_mut().spawn(Tile {
+            coord,
+            terrain,
+            biome: Biome::ALL[0],
+        });
+        app.world_mut()
+            .spawn((Placed { kind: EntityKind::Tree, coord }, Health(Health::MAX)))
+            .id()
+    }
+
+    fn health(app: &App, entity: Entity) -> Option<f32> {
+        app.world().get::<Health>(entity).map(|health| health.0)
+    }
+
+    #[test]
+    fn only_entities_on_hostile_ground_decay_in_fair_weather() {
+        let mut app = decay_test_app();
+        let rules = TerrainRules::default();
+        assert!(rules.can_place(EntityKind::Tree, Terrain::Grass));
+        assert!(!rules.can_place(EntityKind::Tree, Terrain::Sand));
+        let safe = spawn_tree(&mut app, UVec2::new(0, 0), Terrain::Grass);
+        let stranded = spawn_tree(&mut app, UVec2::new(1, 0), Terrain::Sand);
+
+        app.update();
+
+        let config = DecayConfig::default();
+        let expected = Health::MAX
+            - config.hostile_terrain * (config.interval_hours / SimulationClock::HOURS_PER_DAY) as f32;
+        assert_eq!(health(&app, safe), Some(Health::MAX));
+        assert_eq!(health(&app, stranded), Some(expected));
+
+        // Nothing more is lost until the next interval
+        app.update();
+        assert_eq!(health(&app, stranded), Some(expected));
+    }
+
+    #[test]
+    fn entities_that_run_out_of_health_die_and_are_logged() {
+        let mut app = decay_test_app();
+        *app.world_mut().resource_mut::<Weather>() = Weather::Storm;
+        // Enough to kill in a single interval
+        app.world_mut().resource_mut::<DecayConfig>().storm =
+            Health::MAX * SimulationClock::HOURS_PER_DAY as f32;
+        let tree = spawn_tree(&mut app, UVec2::new(2, 3), Terrain::Grass);
+
+        app.update();
+
+        assert!(app.world().get_entity(tree).is_none());
+        assert_eq!(app.world().resource::<EventLog>().latest().count(), 1);
+    }
+}
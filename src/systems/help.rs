@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use crate::components::ui::*;
+use crate::resources::{key_label, InputCategory, KeyBindings};
+
+/// Key that opens and closes the help overlay
+const HELP_KEY: KeyCode = KeyCode::F1;
+
+/// Open or close the help overlay with F1, and close it with Escape
+///
+/// Runs before the pause toggle and the selection clear and consumes Escape, so closing the
+/// overlay doesn't also pause the game. The overlay doesn't change state, so the simulation
+/// keeps running underneath it.
+pub fn toggle_help_overlay(
+    mut commands: Commands,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    theme: Res<MenuTheme>,
+    key_bindings: Res<KeyBindings>,
+    overlay_query: Query<Entity, With<HelpOverlay>>,
+) {
+    let is_open = !overlay_query.is_empty();
+
+    if is_open && keyboard_input.just_pressed(KeyCode::Escape) {
+        keyboard_input.clear_just_pressed(KeyCode::Escape);
+    } else if !keyboard_input.just_pressed(HELP_KEY) {
+        return;
+    }
+
+    if is_open {
+        for entity in &overlay_query {
+            commands.entity(entity).despawn_recursive();
+        }
+    } else {
+        spawn_help_overlay(&mut commands, &theme, &key_bindings);
+    }
+}
+
+/// Show remapped keys in the help overlay
+pub fn update_help_overlay(
+    key_bindings: Res<KeyBindings>,
+    mut text_query: Query<(&mut Text, &KeyBindingText)>,
+) {
+    for (mut text, KeyBindingText(action)) in &mut text_query {
+        text.sections[0].value = key_label(key_bindings.key(*action));
+    }
+}
+
+/// Close the help overlay when leaving gameplay
+pub fn cleanup_help_overlay(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<HelpOverlay>>,
+) {
+    for entity in &overlay_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Spawn the help overlay with every key binding, grouped by category
+fn spawn_help_overlay(commands: &mut Commands, theme: &MenuTheme, key_bindings: &KeyBindings) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                z_index: ZIndex::Global(500),
+                ..default()
+            },
+            HelpOverlay,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(6.0),
+                            padding: UiRect::all(Val::Px(20.0)),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        background_color: theme.background_color.into(),
+                        border_color: theme.button_hovered.into(),
+                        ..default()
+                    },
+                    ThemeColor::Background,
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        TextBundle::from_section(
+                            "Keyboard Shortcuts",
+                            TextStyle {
+                                font_size: theme.button_font_size,
+                                color: theme.title_color,
+                                ..default()
+                            },
+                        ),
+                        ThemeColor::Title,
+                    ));
+
+                    for category in InputCategory::ALL {
+                        panel.spawn((
+                            TextBundle::from_section(
+                                category.label(),
+                                TextStyle {
+                                    font_size: theme.font_size(22.0),
+                                    color: theme.title_color,
+                                    ..default()
+                                },
+                            )
+                            .with_style(Style {
+                                margin: UiRect::top(Val::Px(8.0)),
+                                ..default()
+                            }),
+                            ThemeColor::Title,
+                        ));
+
+                        for action in category.actions() {
+                            panel
+                                .spawn(NodeBundle {
+                                    style: Style {
+                                        min_width: Val::Px(320.0),
+                                        justify_content: JustifyContent::SpaceBetween,
+                                        column_gap: Val::Px(20.0),
+                                        ..default()
+                                    },
+                                    ..default()
+                                })
+                                .with_children(|row| {
+                                    row.spawn((
+                                        TextBundle::from_section(
+                                            action.label(),
+                                            TextStyle {
+                                                font_size: theme.font_size(20.0),
+                                                color: theme.button_text,
+                                                ..default()
+                                            },
+                                        ),
+                                        ThemeColor::Text,
+                                    ));
+                                    row.spawn((
+                                        TextBundle::from_section(
+                                            key_label(key_bindings.key(action)),
+                                            TextStyle {
+                                                font_size: theme.font_size(20.0),
+                                                color: theme.title_color,
+                                                ..default()
+                                            },
+                                        ),
+                                        KeyBindingText(action),
+                                        ThemeColor::Title,
+                                    ));
+                                });
+                        }
+                    }
+
+                    panel.spawn((
+                        TextBundle::from_section(
+                            "Press F1 or ESC to close",
+                            TextStyle {
+                                font_size: theme.font_size(18.0),
+                                color: theme.button_text,
+                                ..default()
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::top(Val::Px(12.0)),
+                            ..default()
+                        }),
+                        ThemeColor::Text,
+                    ));
+                });
+        });
+}
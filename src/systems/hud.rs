@@ -0,0 +1,338 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::components::tooltip::Tooltip;
+use crate::components::ui::*;
+use crate::components::GameCamera;
+use crate::i18n::Locale;
+use crate::resources::{
+    DebugOverlay, Population, ResourceKind, Resources, Sandbox, SimulationClock, SimulationSpeed, WorldConfig,
+};
+use crate::utils::cursor_to_world;
+
+/// Height of the top bar at the default text size
+const HUD_HEIGHT: f32 = 44.0;
+/// Size of the colored icon shown next to each resource
+const ICON_SIZE: f32 = 16.0;
+const HUD_FONT_SIZE: f32 = 24.0;
+/// The bar has a dark backdrop whatever the menu theme
+const HUD_TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+/// Gold, so the sandbox label stands apart from the figures beside it
+const SANDBOX_TEXT_COLOR: Color = Color::srgb(1.0, 0.8, 0.3);
+
+/// Setup the top bar showing the time, speed buttons, population and resources, the button
+/// that opens the statistics graphs, and whether the world is in sandbox mode
+pub fn setup_hud(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    clock: Res<SimulationClock>,
+    population: Res<Population>,
+    resources: Res<Resources>,
+    sandbox: Res<Sandbox>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    min_height: Val::Px(HUD_HEIGHT),
+                    align_items: AlignItems::Center,
+                    padding: UiRect::horizontal(Val::Px(10.0)),
+                    column_gap: Val::Px(30.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            Hud,
+        ))
+        .with_children(|parent| {
+            parent.spawn((hud_text(&theme, clock_text(&locale, &clock)), ClockDisplay));
+
+            // Speed buttons, which only freeze the clock rather than opening the pause menu
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for speed in SimulationSpeed::ALL {
+                        let tooltip = locale.t(match speed {
+                            SimulationSpeed::Paused => "hud.speed_paused_tooltip",
+                            SimulationSpeed::Normal => "hud.speed_normal_tooltip",
+                            SimulationSpeed::Fast => "hud.speed_fast_tooltip",
+                            SimulationSpeed::Fastest => "hud.speed_fastest_tooltip",
+                        });
+                        row.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    min_width: Val::Px(36.0),
+                                    min_height: Val::Px(28.0),
+                                    padding: UiRect::horizontal(Val::Px(6.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: theme.button_normal.into(),
+                                ..default()
+                            },
+                            SpeedButton { speed },
+                            ButtonScale::default(),
+                            Tooltip::new(tooltip),
+                        ))
+                        .with_children(|button| {
+                            button.spawn(TextBundle::from_section(
+                                speed.button_label(),
+                                TextStyle {
+                                    font_size: theme.font_size(20.0),
+                                    color: HUD_TEXT_COLOR,
+                                    ..default()
+                                },
+                            ));
+                        });
+                    }
+                });
+
+            parent.spawn((hud_text(&theme, population_text(&locale, &population)), PopulationDisplay));
+
+            // Resources, each with a colored icon
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for kind in ResourceKind::ALL {
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Px(ICON_SIZE),
+                                height: Val::Px(ICON_SIZE),
+                                ..default()
+                            },
+                            background_color: kind.color().into(),
+                            ..default()
+                        });
+                        row.spawn((
+                            hud_text(&theme, resource_text(&locale, &resources, kind)).with_style(Style {
+                                margin: UiRect::right(Val::Px(12.0)),
+                                ..default()
+                            }),
+                            ResourceText(kind),
+                        ));
+                    }
+                });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            min_height: Val::Px(28.0),
+                            padding: UiRect::horizontal(Val::Px(8.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: theme.button_normal.into(),
+                        ..default()
+                    },
+                    StatisticsToggle,
+                    ButtonScale::default(),
+                    Tooltip::new(locale.t("hud.statistics_tooltip")),
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        locale.t("hud.statistics"),
+                        TextStyle {
+                            font_size: theme.font_size(20.0),
+                            color: HUD_TEXT_COLOR,
+                            ..default()
+                        },
+                    ));
+                });
+
+            if sandbox.0 {
+                parent.spawn(TextBundle::from_section(
+                    locale.t("hud.sandbox"),
+                    TextStyle {
+                        font_size: theme.font_size(HUD_FONT_SIZE),
+                        color: SANDBOX_TEXT_COLOR,
+                        ..default()
+                    },
+                ));
+            }
+        });
+
+    // Cursor readout in the bottom-right corner, hidden until the debug overlay is shown
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: theme.font_size(18.0),
+                color: HUD_TEXT_COLOR,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            right: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(4.0)),
+            ..default()
+        })
+        .with_background_color(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        Visibility::Hidden,
+        CursorReadout,
+        Hud,
+    ));
+}
+
+/// Show the world position and tile under the cursor while the debug overlay is on
+///
+/// Hidden when the overlay is off or the cursor is outside the window.
+pub fn update_cursor_readout(
+    overlay: Res<DebugOverlay>,
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    mut readout_query: Query<(&mut Text, &mut Visibility), With<CursorReadout>>,
+) {
+    let world_position = overlay
+        .visible
+        .then(|| {
+            let window = window_query.get_single().ok()?;
+            let (camera_transform, projection) = camera_query.get_single().ok()?;
+            cursor_to_world(window, projection, camera_transform)
+        })
+        .flatten();
+
+    for (mut text, mut visibility) in &mut readout_query {
+        let Some(position) = world_position else {
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+            continue;
+        };
+
+        let tile = config
+            .tile_at(position)
+            .map_or_else(|| "none".to_string(), |coord| format!("({}, {})", coord.x, coord.y));
+        text.sections[0].value = format!("World: ({:.1}, {:.1})\nTile: {}", position.x, position.y, tile);
+        *visibility = Visibility::Inherited;
+    }
+}
+
+/// Keep the clock text in sync with the simulation clock
+pub fn update_hud_clock(
+    clock: Res<SimulationClock>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<ClockDisplay>>,
+) {
+    for mut text in &mut text_query {
+        text.sections[0].value = clock_text(&locale, &clock);
+    }
+}
+
+/// Set the simulation speed from the clicked top bar speed button
+pub fn handle_speed_buttons(
+    interaction_query: Query<(&Interaction, &SpeedButton), Changed<Interaction>>,
+    mut clock: ResMut<SimulationClock>,
+) {
+    for (interaction, speed_button) in &interaction_query {
+        if *interaction == Interaction::Pressed && clock.speed != speed_button.speed {
+            info!("Simulation speed set to {}", speed_button.speed.label());
+            clock.speed = speed_button.speed;
+        }
+    }
+}
+
+/// Highlight the active speed button, with the same hover and press feedback as menu buttons
+pub fn update_speed_button_colors(
+    theme: Res<MenuTheme>,
+    clock: Res<SimulationClock>,
+    mut button_query: Query<(&Interaction, &SpeedButton, &mut BackgroundColor, &mut ButtonScale)>,
+) {
+    for (interaction, speed_button, mut background_color, mut button_scale) in &mut button_query {
+        let (color, scale) = match *interaction {
+            Interaction::Pressed => (theme.button_pressed, ButtonScale::PRESSED),
+            Interaction::Hovered => (theme.button_hovered, ButtonScale::HOVERED),
+            Interaction::None if clock.speed == speed_button.speed => {
+                (theme.button_pressed, ButtonScale::RESTING)
+            }
+            Interaction::None => (theme.button_normal, ButtonScale::RESTING),
+        };
+
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+        if button_scale.target != scale {
+            button_scale.target = scale;
+        }
+    }
+}
+
+/// Keep the population text in sync with the population
+pub fn update_hud_population(
+    population: Res<Population>,
+    locale: Res<Locale>,
+    mut text_query: Query<&mut Text, With<PopulationDisplay>>,
+) {
+    for mut text in &mut text_query {
+        text.sections[0].value = population_text(&locale, &population);
+    }
+}
+
+/// Keep the resource texts in sync with the stockpile
+pub fn update_hud_resources(
+    resources: Res<Resources>,
+    locale: Res<Locale>,
+    mut text_query: Query<(&mut Text, &ResourceText)>,
+) {
+    for (mut text, ResourceText(kind)) in &mut text_query {
+        text.sections[0].value = resource_text(&locale, &resources, *kind);
+    }
+}
+
+/// Cleanup the top bar when leaving the game
+pub fn cleanup_hud(mut commands: Commands, hud_query: Query<Entity, With<Hud>>) {
+    for entity in &hud_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn hud_text(theme: &MenuTheme, value: String) -> TextBundle {
+    TextBundle::from_section(
+        value,
+        TextStyle {
+            font_size: theme.font_size(HUD_FONT_SIZE),
+            color: HUD_TEXT_COLOR,
+            ..default()
+        },
+    )
+}
+
+fn clock_text(locale: &Locale, clock: &SimulationClock) -> String {
+    format!(
+        "{} {} ({})",
+        clock.time_label(),
+        locale.t(clock.time_of_day().key()),
+        locale.t(clock.speed.key())
+    )
+}
+
+fn population_text(locale: &Locale, population: &Population) -> String {
+    format!("{}: {}", locale.t("hud.population"), population.count)
+}
+
+fn resource_text(locale: &Locale, resources: &Resources, kind: ResourceKind) -> String {
+    format!("{} {}", locale.t(kind.key()), resources.amount(kind).floor() as u32)
+}
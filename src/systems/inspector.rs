@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use crate::components::placeable::Placed;
+use crate::components::tile::Tile;
+use crate::components::ui::*;
+use crate::resources::SelectedTile;
+
+/// Setup the tile inspector panel along the right edge, hidden until a tile is selected
+pub fn setup_inspector_panel(mut commands: Commands, theme: Res<MenuTheme>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(60.0),
+                    right: Val::Px(10.0),
+                    width: Val::Px(260.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.0),
+                    padding: UiRect::all(Val::Px(12.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                background_color: theme.background_color.into(),
+                border_color: theme.button_hovered.into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            InspectorPanel,
+            ThemeColor::Background,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "Tile",
+                    TextStyle {
+                        font_size: theme.button_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Title,
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: theme.font_size(20.0),
+                        color: theme.button_text,
+                        ..default()
+                    },
+                ),
+                InspectorText,
+                ThemeColor::Text,
+            ));
+        });
+}
+
+/// Show the selected tile's details in the inspector, or hide it when nothing is selected
+pub fn update_inspector_panel(
+    selected_tile: Res<SelectedTile>,
+    tile_query: Query<&Tile>,
+    placed_query: Query<&Placed>,
+    mut panel_query: Query<&mut Visibility, With<InspectorPanel>>,
+    mut text_query: Query<&mut Text, With<InspectorText>>,
+) {
+    let tile = selected_tile.0.and_then(|entity| tile_query.get(entity).ok());
+
+    let visibility = if tile.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut panel_visibility in &mut panel_query {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+    }
+
+    let Some(tile) = tile else {
+        return;
+    };
+    let details = inspector_text(tile, &placed_query);
+    for mut text in &mut text_query {
+        if text.sections[0].value != details {
+            text.sections[0].value = details.clone();
+        }
+    }
+}
+
+/// Cleanup the inspector panel when leaving the game
+pub fn cleanup_inspector_panel(
+    mut commands: Commands,
+    panel_query: Query<Entity, With<InspectorPanel>>,
+) {
+    for entity in &panel_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn inspector_text(tile: &Tile, placed_query: &Query<&Placed>) -> String {
+    let occupants: Vec<&str> = placed_query
+        .iter()
+        .filter(|placed| placed.coord == tile.coord)
+        .map(|placed| placed.kind.name())
+        .collect();
+
+    let occupants = if occupants.is_empty() {
+        "None".to_string()
+    } else {
+        occupants.join(", ")
+    };
+
+    format!(
+        "Position: {}, {}\nTerrain: {}\nBiome: {}\nOccupants: {}",
+        tile.coord.x,
+        tile.coord.y,
+        tile.terrain.name(),
+        tile.biome.name(),
+        occupants
+    )
+}
@@ -0,0 +1,235 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use crate::components::ui::{DaylightOverlay, VignetteOverlay};
+use crate::resources::{ResourceKind, Resources, Settings, SimulationClock};
+
+/// Overlay tint at key hours of the day, interpolated in between. Must start at 0 and end at 24.
+const DAYLIGHT_KEYFRAMES: [(f64, [f32; 4]); 7] = [
+    (0.0, NIGHT_TINT),
+    (5.0, NIGHT_TINT),
+    (7.0, [0.9, 0.55, 0.35, 0.2]), // dawn
+    (12.0, [1.0, 1.0, 1.0, 0.0]),  // noon
+    (17.0, [1.0, 0.9, 0.7, 0.05]),
+    (20.0, [0.75, 0.35, 0.25, 0.3]), // dusk
+    (24.0, NIGHT_TINT),
+];
+
+const NIGHT_TINT: [f32; 4] = [0.03, 0.05, 0.2, 0.55];
+
+/// Side length in pixels of the generated vignette texture, stretched over the window
+const VIGNETTE_SIZE: u32 = 128;
+/// Vignette opacity while the world is doing well
+const VIGNETTE_BASE_ALPHA: f32 = 0.35;
+/// Extra opacity reached when a shortage is about to start costing lives
+const VIGNETTE_DANGER_ALPHA: f32 = 0.5;
+/// How quickly the vignette opacity follows the danger level, per second
+const VIGNETTE_EASE_RATE: f32 = 1.5;
+
+/// Overlay tint for an hour of the day in the range 0.0..24.0
+pub fn daylight_tint(hour_of_day: f64) -> Color {
+    let hour = hour_of_day.rem_euclid(SimulationClock::HOURS_PER_DAY);
+
+    for pair in DAYLIGHT_KEYFRAMES.windows(2) {
+        let (start_hour, start) = pair[0];
+        let (end_hour, end) = pair[1];
+        if hour <= end_hour {
+            let t = ((hour - start_hour) / (end_hour - start_hour)) as f32;
+            let [r, g, b, a]: [f32; 4] =
+                std::array::from_fn(|i| start[i] + (end[i] - start[i]) * t);
+            return Color::srgba(r, g, b, a);
+        }
+    }
+
+    let [r, g, b, a] = NIGHT_TINT;
+    Color::srgba(r, g, b, a)
+}
+
+/// Setup the full-screen day/night tint, drawn beneath the weather, the vignette and the in-game UI
+pub fn setup_daylight_overlay(mut commands: Commands, clock: Res<SimulationClock>) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: daylight_tint(clock.hour_of_day()).into(),
+            z_index: ZIndex::Global(-3),
+            ..default()
+        },
+        DaylightOverlay,
+    ));
+}
+
+/// Shift the overlay tint as the in-game day goes by
+pub fn update_daylight_overlay(
+    clock: Res<SimulationClock>,
+    mut overlay_query: Query<&mut BackgroundColor, With<DaylightOverlay>>,
+) {
+    for mut background_color in &mut overlay_query {
+        background_color.0 = daylight_tint(clock.hour_of_day());
+    }
+}
+
+/// How close the world is to a shortage that costs lives, from 0.0 (safe) to 1.0
+///
+/// Reaches 1.0 on the first day past `Resources::SHORTAGE_GRACE_DAYS`, when the population
+/// starts to shrink.
+pub fn vignette_danger(resources: &Resources) -> f32 {
+    ResourceKind::ALL
+        .into_iter()
+        .map(|kind| resources.days_empty(kind) as f32 / (Resources::SHORTAGE_GRACE_DAYS + 1) as f32)
+        .fold(0.0, f32::max)
+        .min(1.0)
+}
+
+/// Black texture that fades from transparent in the middle to opaque at the corners
+fn vignette_image() -> Image {
+    let center = (VIGNETTE_SIZE as f32 - 1.0) / 2.0;
+    let mut data = Vec::with_capacity((VIGNETTE_SIZE * VIGNETTE_SIZE * 4) as usize);
+    for y in 0..VIGNETTE_SIZE {
+        for x in 0..VIGNETTE_SIZE {
+            let dx = (x as f32 - center) / center;
+            let dy = (y as f32 - center) / center;
+            // 0.0 at the center, 1.0 at the middle of each edge
+            let distance = (dx * dx + dy * dy).sqrt();
+            let t = ((distance - 0.5) / 0.9).clamp(0.0, 1.0);
+            let alpha = t * t * (3.0 - 2.0 * t);
+            data.extend_from_slice(&[0, 0, 0, (alpha * 255.0).round() as u8]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: VIGNETTE_SIZE,
+            height: VIGNETTE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Setup the full-screen vignette, drawn above the world tints but beneath the in-game UI
+pub fn setup_vignette(mut commands: Commands, mut images: ResMut<Assets<Image>>, settings: Res<Settings>) {
+    commands.spawn((
+        ImageBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            image: UiImage::new(images.add(vignette_image()))
+                .with_color(Color::srgba(1.0, 1.0, 1.0, VIGNETTE_BASE_ALPHA)),
+            visibility: if settings.vignette {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            },
+            z_index: ZIndex::Global(-1),
+            ..default()
+        },
+        VignetteOverlay,
+    ));
+}
+
+/// Show or hide the vignette with its setting, and darken it as shortages drag on
+pub fn update_vignette(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    resources: Res<Resources>,
+    mut overlay_query: Query<(&mut UiImage, &mut Visibility), With<VignetteOverlay>>,
+) {
+    let target = VIGNETTE_BASE_ALPHA + vignette_danger(&resources) * VIGNETTE_DANGER_ALPHA;
+    let step = VIGNETTE_EASE_RATE * time.delta_seconds();
+
+    for (mut image, mut visibility) in &mut overlay_query {
+        let wanted = if settings.vignette {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        visibility.set_if_neq(wanted);
+
+        let alpha = image.color.alpha();
+        if alpha != target {
+            let eased = alpha + (target - alpha).clamp(-step, step);
+            image.color.set_alpha(eased);
+        }
+    }
+}
+
+/// Cleanup the vignette when leaving the game
+pub fn cleanup_vignette(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<VignetteOverlay>>,
+) {
+    for entity in &overlay_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Cleanup the day/night overlay when leaving the game
+pub fn cleanup_daylight_overlay(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<DaylightOverlay>>,
+) {
+    for entity in &overlay_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run a day that leaves a resource empty
+    fn empty_day(resources: &mut Resources, kind: ResourceKind) {
+        resources.apply_day(kind, 0.0, f32::MAX);
+    }
+
+    #[test]
+    fn danger_grows_with_each_empty_day_until_lives_are_lost() {
+        let mut resources = Resources::default();
+        assert_eq!(vignette_danger(&resources), 0.0);
+
+        let mut previous = 0.0;
+        for _ in 0..Resources::SHORTAGE_GRACE_DAYS {
+            empty_day(&mut resources, ResourceKind::Water);
+            let danger = vignette_danger(&resources);
+            assert!(danger > previous && danger < 1.0);
+            previous = danger;
+        }
+
+        // The first day of the shortage proper is full danger, and it stays there
+        empty_day(&mut resources, ResourceKind::Water);
+        assert_eq!(vignette_danger(&resources), 1.0);
+        assert!(resources.shortages().next().is_some());
+        empty_day(&mut resources, ResourceKind::Water);
+        assert_eq!(vignette_danger(&resources), 1.0);
+    }
+
+    #[test]
+    fn danger_follows_the_emptiest_resource_and_clears_once_refilled() {
+        let mut resources = Resources::default();
+        empty_day(&mut resources, ResourceKind::Food);
+        empty_day(&mut resources, ResourceKind::Food);
+        empty_day(&mut resources, ResourceKind::Wood);
+        let food_only = {
+            let mut resources = Resources::default();
+            empty_day(&mut resources, ResourceKind::Food);
+            empty_day(&mut resources, ResourceKind::Food);
+            vignette_danger(&resources)
+        };
+        assert_eq!(vignette_danger(&resources), food_only);
+
+        resources.apply_day(ResourceKind::Food, 50.0, 0.0);
+        resources.apply_day(ResourceKind::Wood, 50.0, 0.0);
+        assert_eq!(vignette_danger(&resources), 0.0);
+    }
+}
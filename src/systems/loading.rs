@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use crate::components::camera::UiCamera;
+use crate::components::ui::*;
+use crate::resources::WorldGenProgress;
+
+/// Setup the loading screen with a progress bar for world generation
+pub fn setup_loading_screen(mut commands: Commands, theme: Res<MenuTheme>) {
+    info!("Setting up loading screen");
+
+    // Spawn a camera for UI rendering
+    commands.spawn((Camera2dBundle::default(), UiCamera, LoadingScreen));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: theme.background_color.into(),
+                ..default()
+            },
+            LoadingScreen,
+            ThemeColor::Background,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    loading_text(&WorldGenProgress::default()),
+                    TextStyle {
+                        font_size: theme.button_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                ),
+                LoadingText,
+                ThemeColor::Title,
+            ));
+
+            // Progress bar track with a fill that grows from the left
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(400.0),
+                            height: Val::Px(30.0),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        background_color: theme.button_normal.into(),
+                        border_color: theme.button_hovered.into(),
+                        ..default()
+                    },
+                    ThemeColor::Widget,
+                ))
+                .with_children(|track| {
+                    track.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: theme.title_color.into(),
+                            ..default()
+                        },
+                        LoadingBarFill,
+                        ThemeColor::Accent,
+                    ));
+                });
+        });
+}
+
+/// Keep the loading bar and text in sync with world generation progress
+pub fn update_loading_screen(
+    progress: Res<WorldGenProgress>,
+    mut fill_query: Query<&mut Style, With<LoadingBarFill>>,
+    mut text_query: Query<&mut Text, With<LoadingText>>,
+) {
+    for mut style in &mut fill_query {
+        style.width = Val::Percent(progress.fraction() * 100.0);
+    }
+    for mut text in &mut text_query {
+        text.sections[0].value = loading_text(&progress);
+    }
+}
+
+/// Cleanup loading screen and its UI camera
+pub fn cleanup_loading_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<LoadingScreen>>,
+) {
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("Loading screen cleaned up");
+}
+
+fn loading_text(progress: &WorldGenProgress) -> String {
+    let stage = if progress.is_map_ready() {
+        "Placing tiles"
+    } else {
+        "Generating world"
+    };
+    format!("{}... {}%", stage, (progress.fraction() * 100.0).round() as u32)
+}
@@ -1,13 +1,56 @@
 use bevy::prelude::*;
+use bevy::render::camera::ClearColorConfig;
+use bevy::window::PrimaryWindow;
+use crate::components::camera::UiCamera;
+use crate::components::confirm_dialog::{spawn_choice_dialog, spawn_confirm_dialog, ConfirmDialogButton};
+use crate::components::input_field::TextInput;
+use crate::components::tooltip::Tooltip;
 use crate::components::ui::*;
-use crate::resources::GameState;
+use crate::events::{
+    AfterSave, ButtonActivated, LoadGameRequested, Notification, PlaySoundEffect, SaveGameRequested,
+};
+use crate::i18n::Locale;
+use crate::resources::{GameState, InputAction, KeyBindings, SettingsOrigin, SoundEffect, SystemClipboard, WorldConfig};
+use crate::save::{latest_save, UnsavedChanges};
+use crate::systems::gamepad::gamepad_just_pressed;
+use crate::systems::new_worldkeeper::copy_seed;
+use crate::systems::pause::spawn_save_as_dialog;
+
+/// How quickly buttons ease toward their hover and press scale, per second
+const BUTTON_SCALE_SPEED: f32 = 20.0;
+
+/// Stars drifting behind the main menu
+const MENU_STAR_COUNT: usize = 150;
+/// Window size assumed for the starfield when there is no window, in logical pixels
+const FALLBACK_WINDOW_SIZE: Vec2 = Vec2::new(1024.0, 768.0);
 
 /// Setup the main menu UI
-pub fn setup_main_menu(mut commands: Commands) {
+pub fn setup_main_menu(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+) {
     info!("Setting up main menu");
     
-    // Spawn a camera for UI rendering
-    commands.spawn(Camera2dBundle::default());
+    // Spawn a camera for UI rendering. It clears to the theme background, and the starfield
+    // is drawn on top of that behind the transparent menu.
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                clear_color: ClearColorConfig::Custom(theme.background_color),
+                ..default()
+            },
+            ..default()
+        },
+        UiCamera,
+        MainMenu,
+    ));
+
+    let window_size = window_query
+        .get_single()
+        .map_or(FALLBACK_WINDOW_SIZE, |window| window.size());
+    spawn_menu_starfield(&mut commands, &theme, window_size);
     
     // Main menu root container
     commands
@@ -22,7 +65,6 @@ pub fn setup_main_menu(mut commands: Commands) {
                     row_gap: Val::Px(20.0),
                     ..default()
                 },
-                background_color: MenuTheme::BACKGROUND_COLOR.into(),
                 ..default()
             },
             MainMenu,
@@ -31,21 +73,22 @@ pub fn setup_main_menu(mut commands: Commands) {
             // Title
             parent.spawn((
                 TextBundle::from_section(
-                    "WorldKeeper",
+                    locale.t("menu.title"),
                     TextStyle {
-                        font_size: MenuTheme::TITLE_FONT_SIZE,
-                        color: MenuTheme::TITLE_COLOR,
+                        font_size: theme.title_font_size,
+                        color: theme.title_color,
                         ..default()
                     },
                 ),
                 MenuTitle,
+                ThemeColor::Title,
             ));
             
             // Subtitle
             parent.spawn(TextBundle::from_section(
-                "God Simulation Game",
+                locale.t("menu.subtitle"),
                 TextStyle {
-                    font_size: 24.0,
+                    font_size: theme.font_size(24.0),
                     color: Color::srgb(0.7, 0.7, 0.7),
                     ..default()
                 },
@@ -64,110 +107,560 @@ pub fn setup_main_menu(mut commands: Commands) {
                     ..default()
                 })
                 .with_children(|parent| {
-                    // New Worldkeeper button
-                    create_menu_button(
-                        parent,
-                        "New Worldkeeper",
-                        MenuAction::NewWorldkeeper,
-                    );
-                    
-                    // Start New Game button
-                    create_menu_button(
-                        parent,
-                        "Start New Game",
-                        MenuAction::StartNewGame,
-                    );
+                    for (label, action) in main_menu_items(&locale, latest_save().is_some()) {
+                        create_menu_button(parent, &theme, &locale, &label, action);
+                    }
                 });
         });
 }
 
+/// Main menu buttons from top to bottom, with Continue only offered when there is a save to
+/// continue from
+fn main_menu_items(locale: &Locale, has_save: bool) -> Vec<(String, MenuAction)> {
+    let mut items = Vec::new();
+    if has_save {
+        items.push((locale.t("menu.continue"), MenuAction::Continue));
+    }
+    items.extend([
+        (locale.t("menu.new_worldkeeper"), MenuAction::NewWorldkeeper),
+        (locale.t("menu.start_new_game"), MenuAction::StartNewGame),
+        (locale.t("menu.load_world"), MenuAction::LoadWorld),
+        (locale.t("menu.settings"), MenuAction::Settings),
+        (locale.t("menu.credits"), MenuAction::Credits),
+        (
+            locale.t("menu.quit"),
+            MenuAction::confirm(locale.t("menu.quit_confirm"), MenuAction::Quit),
+        ),
+    ]);
+    items
+}
+
+/// Scatter stars of random size, brightness and speed across the window
+fn spawn_menu_starfield(commands: &mut Commands, theme: &MenuTheme, window_size: Vec2) {
+    for _ in 0..MENU_STAR_COUNT {
+        // Nearer stars are larger, brighter and faster
+        let depth: f32 = rand::random();
+        let position = (Vec2::new(rand::random(), rand::random()) - 0.5) * window_size;
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: theme.title_color.with_alpha(0.2 + 0.6 * depth),
+                    custom_size: Some(Vec2::splat(1.0 + 2.0 * depth)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(0.0)),
+                ..default()
+            },
+            MenuStar {
+                speed: 4.0 + 26.0 * depth,
+            },
+            MainMenu,
+        ));
+    }
+}
+
+/// Drift the menu stars to the left, wrapping them around to the right edge
+pub fn animate_menu_starfield(
+    time: Res<Time>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut star_query: Query<(&mut Transform, &MenuStar)>,
+) {
+    let half_width = window_query
+        .get_single()
+        .map_or(FALLBACK_WINDOW_SIZE, |window| window.size())
+        .x
+        / 2.0;
+
+    for (mut transform, star) in &mut star_query {
+        transform.translation.x -= star.speed * time.delta_seconds();
+        if transform.translation.x < -half_width {
+            transform.translation.x += 2.0 * half_width;
+        }
+    }
+}
+
 /// Helper function to create a menu button
-fn create_menu_button(
+pub fn create_menu_button(
     parent: &mut ChildBuilder,
+    theme: &MenuTheme,
+    locale: &Locale,
     text: &str,
     action: MenuAction,
+) {
+    let tooltip = Tooltip::new(locale.t(action.description_key()));
+    create_sized_button(
+        parent,
+        theme,
+        text,
+        Val::Px(300.0),
+        Val::Px(60.0),
+        (MenuButton::new(action), tooltip),
+    );
+}
+
+/// Helper function to create a themed button tagged with the given components
+///
+/// `width` and `height` are minimums, so larger text grows the button instead of clipping.
+pub fn create_sized_button(
+    parent: &mut ChildBuilder,
+    theme: &MenuTheme,
+    text: &str,
+    width: Val,
+    height: Val,
+    components: impl Bundle,
 ) {
     parent
         .spawn((
             ButtonBundle {
                 style: Style {
-                    width: Val::Px(300.0),
-                    height: Val::Px(60.0),
+                    min_width: width,
+                    min_height: height,
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
                 },
-                background_color: MenuTheme::BUTTON_NORMAL.into(),
+                background_color: theme.button_normal.into(),
                 ..default()
             },
-            MenuButton::new(action),
+            components,
         ))
         .with_children(|parent| {
             parent.spawn((
                 TextBundle::from_section(
                     text,
                     TextStyle {
-                        font_size: MenuTheme::BUTTON_FONT_SIZE,
-                        color: MenuTheme::BUTTON_TEXT,
+                        font_size: theme.button_font_size,
+                        color: theme.button_text,
                         ..default()
                     },
                 ),
                 ButtonText,
+                ThemeColor::Text,
+            ));
+        });
+}
+
+/// Helper function to create a row with a label on the left followed by controls
+pub fn create_labeled_row(
+    parent: &mut ChildBuilder,
+    theme: &MenuTheme,
+    label: &str,
+    controls: impl FnOnce(&mut ChildBuilder),
+) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(15.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(260.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|label_node| {
+                label_node.spawn((
+                    TextBundle::from_section(
+                        label,
+                        TextStyle {
+                            font_size: theme.button_font_size,
+                            color: theme.title_color,
+                            ..default()
+                        },
+                    ),
+                    ThemeColor::Title,
+                ));
+            });
+
+            controls(row);
+        });
+}
+
+/// Helper function to create fixed-width text showing the current value of an option
+pub fn create_value_text(
+    parent: &mut ChildBuilder,
+    theme: &MenuTheme,
+    value: String,
+    components: impl Bundle,
+) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(160.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    value,
+                    TextStyle {
+                        font_size: theme.button_font_size,
+                        color: theme.button_text,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Text,
+                components,
             ));
         });
 }
 
-/// Handle button interactions (hover effects)
+/// Run condition for systems that should only run on screens with interactive UI
+pub fn ui_interaction_allowed(current_state: Res<State<GameState>>) -> bool {
+    current_state.get().allows_ui_interaction()
+}
+
+/// Buttons whose interaction state changed this frame
+type ChangedButtons<'w, 's> =
+    Query<'w, 's, (Entity, &'static Interaction), (Changed<Interaction>, With<Button>)>;
+
+/// Turn mouse clicks on buttons into `ButtonActivated` events
+pub fn detect_button_presses(
+    interaction_query: ChangedButtons,
+    mut activated: EventWriter<ButtonActivated>,
+) {
+    for (entity, interaction) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            activated.send(ButtonActivated { entity });
+        }
+    }
+}
+
+/// Move keyboard focus between buttons with Up/Down and activate with Enter/Space
+///
+/// The active gamepad's D-pad and South (A) button mirror the keyboard controls.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_menu_keyboard_navigation(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut focused: ResMut<FocusedButton>,
+    button_query: Query<(Entity, &GlobalTransform, Has<ConfirmDialogButton>), With<Button>>,
+    hovered_query: ChangedButtons,
+    text_inputs: Query<&TextInput>,
+    mut activated: EventWriter<ButtonActivated>,
+) {
+    // Mouse hover moves focus too, so only one button is ever highlighted
+    for (entity, interaction) in &hovered_query {
+        if *interaction == Interaction::Hovered {
+            focused.0 = Some(entity);
+        }
+    }
+
+    // Keys typed into a text field must not also move focus or press buttons
+    if text_inputs.iter().any(|text_input| text_input.focused) {
+        return;
+    }
+
+    // An open confirm dialog keeps focus on its own buttons
+    let dialog_open = button_query.iter().any(|(_, _, in_dialog)| in_dialog);
+
+    // Buttons in on-screen reading order: top to bottom, then left to right
+    let mut buttons: Vec<(Entity, Vec3)> = button_query
+        .iter()
+        .filter(|(_, _, in_dialog)| *in_dialog || !dialog_open)
+        .map(|(entity, transform, _)| (entity, transform.translation()))
+        .collect();
+    buttons.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    if buttons.is_empty() {
+        focused.0 = None;
+        return;
+    }
+
+    let current_index = focused
+        .0
+        .and_then(|entity| buttons.iter().position(|(button, _)| *button == entity));
+    let last_index = buttons.len() - 1;
+    
+    let down_pressed = keyboard_input.just_pressed(KeyCode::ArrowDown)
+        || gamepad_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::DPadDown);
+    let up_pressed = keyboard_input.just_pressed(KeyCode::ArrowUp)
+        || gamepad_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::DPadUp);
+    let activate_pressed = keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space])
+        || gamepad_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::South);
+
+    if down_pressed {
+        let next_index = match current_index {
+            Some(index) if index < last_index => index + 1,
+            _ => 0,
+        };
+        focused.0 = Some(buttons[next_index].0);
+    } else if up_pressed {
+        let next_index = match current_index {
+            Some(index) if index > 0 => index - 1,
+            _ => last_index,
+        };
+        focused.0 = Some(buttons[next_index].0);
+    } else if current_index.is_none() {
+        // Drop focus that points at a despawned button
+        focused.0 = None;
+    }
+
+    if activate_pressed {
+        if let Some(entity) = focused.0 {
+            activated.send(ButtonActivated { entity });
+        }
+    }
+}
+
+/// Handle button interactions (hover and focus effects) for all themed buttons
+///
+/// Also picks the scale each button animates toward, adding `ButtonScale` to buttons that
+/// don't have one yet.
 pub fn handle_button_interactions(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    focused: Res<FocusedButton>,
     mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<MenuButton>),
+        (Entity, &Interaction, &mut BackgroundColor, Option<&mut ButtonScale>),
+        With<Button>,
     >,
+    mut sounds: EventWriter<PlaySoundEffect>,
 ) {
-    for (interaction, mut background_color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                *background_color = MenuTheme::BUTTON_PRESSED.into();
+    for (entity, interaction, mut background_color, button_scale) in &mut interaction_query {
+        let (color, scale) = match *interaction {
+            Interaction::Pressed => (theme.button_pressed, ButtonScale::PRESSED),
+            Interaction::Hovered => (theme.button_hovered, ButtonScale::HOVERED),
+            Interaction::None if focused.0 == Some(entity) => (theme.button_hovered, ButtonScale::HOVERED),
+            Interaction::None => (theme.button_normal, ButtonScale::RESTING),
+        };
+        
+        // Only write when the color actually changes to keep change detection quiet
+        if background_color.0 != color {
+            // Highlighting a resting button, by mouse or keyboard focus, plays the hover sound
+            if background_color.0 == theme.button_normal && color == theme.button_hovered {
+                sounds.send(PlaySoundEffect(SoundEffect::Hover));
             }
-            Interaction::Hovered => {
-                *background_color = MenuTheme::BUTTON_HOVERED.into();
+            background_color.0 = color;
+        }
+
+        match button_scale {
+            Some(mut button_scale) if button_scale.target != scale => button_scale.target = scale,
+            Some(_) => {}
+            None => {
+                commands.entity(entity).insert(ButtonScale { target: scale });
             }
-            Interaction::None => {
-                *background_color = MenuTheme::BUTTON_NORMAL.into();
+        }
+    }
+}
+
+/// Ease buttons toward their target scale over a few frames
+pub fn animate_button_scale(
+    time: Res<Time>,
+    mut button_query: Query<(&ButtonScale, &mut Transform)>,
+) {
+    // Fraction of the remaining distance covered per frame, independent of frame rate
+    let blend = 1.0 - (-BUTTON_SCALE_SPEED * time.delta_seconds()).exp();
+
+    for (button_scale, mut transform) in &mut button_query {
+        let current = transform.scale.x;
+        if current == button_scale.target {
+            continue;
+        }
+        let scale = if (button_scale.target - current).abs() < 0.001 {
+            button_scale.target
+        } else {
+            current + (button_scale.target - current) * blend
+        };
+        transform.scale = Vec3::new(scale, scale, 1.0);
+    }
+}
+
+/// Every themed node, with whichever of its colors the theme sets
+type ThemedNodes<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ThemeColor,
+        Option<&'static mut BackgroundColor>,
+        Option<&'static mut BorderColor>,
+        Option<&'static mut Text>,
+    ),
+>;
+
+/// Recolor themed nodes that are already on screen when the theme changes
+///
+/// Buttons are left to `handle_button_interactions`, which picks their color every frame.
+pub fn recolor_themed_ui(
+    theme: Res<MenuTheme>,
+    mut themed_query: ThemedNodes,
+) {
+    for (role, background_color, border_color, text) in &mut themed_query {
+        let color = theme.color(*role);
+        if let Some(mut text) = text {
+            for section in &mut text.sections {
+                section.style.color = color;
             }
+        } else if let Some(mut background_color) = background_color {
+            background_color.0 = color;
+        }
+        if let Some(mut border_color) = border_color {
+            border_color.0 = theme.button_hovered;
         }
     }
 }
 
-/// Handle button clicks and trigger state changes
+/// Handle activated menu buttons and trigger state changes
+#[allow(clippy::too_many_arguments)]
 pub fn handle_menu_actions(
-    mut interaction_query: Query<
-        (&Interaction, &MenuButton),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    mut activated: EventReader<ButtonActivated>,
+    button_query: Query<&MenuButton>,
+    current_state: Res<State<GameState>>,
+    mut settings_origin: ResMut<SettingsOrigin>,
+    world_config: Res<WorldConfig>,
+    mut clipboard: ResMut<SystemClipboard>,
+    unsaved_changes: Res<UnsavedChanges>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut save_requests: EventWriter<SaveGameRequested>,
+    mut load_requests: EventWriter<LoadGameRequested>,
     mut exit: EventWriter<AppExit>,
+    mut sounds: EventWriter<PlaySoundEffect>,
+    mut notifications: EventWriter<Notification>,
 ) {
-    for (interaction, menu_button) in &mut interaction_query {
-        if *interaction == Interaction::Pressed {
-            match menu_button.action {
-                MenuAction::NewWorldkeeper => {
-                    info!("New Worldkeeper selected");
-                    next_state.set(GameState::NewWorldkeeper);
+    for event in activated.read() {
+        let Ok(menu_button) = button_query.get(event.entity) else {
+            continue;
+        };
+        
+        sounds.send(PlaySoundEffect(SoundEffect::Click));
+        
+        match &menu_button.action {
+            MenuAction::Continue => {
+                info!("Continue selected");
+                match latest_save() {
+                    Some(path) => {
+                        load_requests.send(LoadGameRequested { path });
+                    }
+                    None => {
+                        notifications.send(Notification::new("No save to continue from"));
+                    }
                 }
-                MenuAction::StartNewGame => {
-                    info!("Start New Game selected");
-                    next_state.set(GameState::InGame);
+            }
+            MenuAction::NewWorldkeeper => {
+                next_state.set(GameState::NewWorldkeeper);
+            }
+            MenuAction::StartNewGame => {
+                next_state.set(GameState::Loading);
+            }
+            MenuAction::LoadWorld => {
+                next_state.set(GameState::Saves);
+            }
+            // The saves screen deletes the file, since it has to refresh its list afterwards
+            MenuAction::DeleteSave(_) => {}
+            // The settings screen resets them, since it also has to move its sliders
+            MenuAction::ResetSettings => {}
+            // Regeneration sends the request itself, since it also checks no world is generating
+            MenuAction::RegenerateWorld(_) => {}
+            MenuAction::SaveGame => {
+                info!("Save selected");
+                save_requests.send(SaveGameRequested::default());
+            }
+            MenuAction::SaveAs => {
+                info!("Save As selected");
+                spawn_save_as_dialog(&mut commands, &theme, &locale);
+            }
+            MenuAction::SaveGameAs(name) => {
+                save_requests.send(SaveGameRequested {
+                    name: Some(name.clone()),
+                    ..default()
+                });
+            }
+            MenuAction::CopySeed => {
+                notifications.send(copy_seed(&mut clipboard, world_config.seed));
+            }
+            MenuAction::Settings => {
+                settings_origin.0 = current_state.get().clone();
+                next_state.set(GameState::Settings);
+            }
+            MenuAction::Controls => {
+                next_state.set(GameState::Controls);
+            }
+            MenuAction::Credits => {
+                next_state.set(GameState::Credits);
+            }
+            MenuAction::Back => {
+                match current_state.get() {
+                    GameState::Settings => next_state.set(settings_origin.0.clone()),
+                    GameState::Controls => next_state.set(GameState::Settings),
+                    _ => next_state.set(GameState::MainMenu),
                 }
-                MenuAction::Settings => {
-                    info!("Settings selected");
-                    // TODO: Implement settings menu
+            }
+            MenuAction::Resume => {
+                next_state.set(GameState::InGame);
+            }
+            // The world is torn down on reaching the menu, so unsaved changes are offered a save
+            MenuAction::QuitToMenu => {
+                if unsaved_changes.0 {
+                    spawn_choice_dialog(
+                        &mut commands,
+                        &theme,
+                        &locale.t("pause.quit_to_menu_unsaved"),
+                        vec![
+                            (locale.t("dialog.save_and_quit"), MenuAction::SaveAndQuitToMenu),
+                            (locale.t("dialog.quit"), MenuAction::ReturnToMenu),
+                        ],
+                        &locale.t("dialog.cancel"),
+                    );
+                } else {
+                    next_state.set(GameState::MainMenu);
                 }
-                MenuAction::Quit => {
-                    info!("Quit selected");
+            }
+            MenuAction::SaveAndQuitToMenu => {
+                save_requests.send(SaveGameRequested {
+                    after: AfterSave::ReturnToMenu,
+                    ..default()
+                });
+            }
+            MenuAction::ReturnToMenu => {
+                next_state.set(GameState::MainMenu);
+            }
+            MenuAction::QuitToDesktop => {
+                info!("Quit to desktop selected");
+                if unsaved_changes.0 {
+                    spawn_choice_dialog(
+                        &mut commands,
+                        &theme,
+                        &locale.t("pause.quit_to_desktop_unsaved"),
+                        vec![
+                            (locale.t("dialog.save_and_quit"), MenuAction::SaveAndQuit),
+                            (locale.t("dialog.quit"), MenuAction::Quit),
+                        ],
+                        &locale.t("dialog.cancel"),
+                    );
+                } else {
                     exit.send(AppExit::Success);
                 }
             }
+            MenuAction::SaveAndQuit => {
+                info!("Save and quit selected");
+                save_requests.send(SaveGameRequested {
+                    after: AfterSave::Quit,
+                    ..default()
+                });
+            }
+            MenuAction::Quit => {
+                info!("Quit selected");
+                exit.send(AppExit::Success);
+            }
+            MenuAction::Confirm { message, action } => {
+                spawn_confirm_dialog(&mut commands, &theme, &locale, message, (**action).clone());
+            }
         }
     }
 }
@@ -176,33 +669,113 @@ pub fn handle_menu_actions(
 pub fn cleanup_main_menu(
     mut commands: Commands,
     menu_query: Query<Entity, With<MainMenu>>,
-    camera_query: Query<Entity, With<Camera>>,
 ) {
+    // The UI camera and the starfield are tagged with `MainMenu` too
     for entity in &menu_query {
         commands.entity(entity).despawn_recursive();
     }
     
-    // Also cleanup the UI camera
-    for entity in &camera_query {
-        commands.entity(entity).despawn();
-    }
-    
     info!("Main menu cleaned up");
 }
 
-/// Handle escape key to return to main menu from other states
-pub fn handle_escape_to_menu(
+/// Handle Escape (or the gamepad East/B button) in every state, following
+/// `GameState::escape_target`
+///
+/// In game and while paused, the bound pause key and the gamepad Start button do the same, so
+/// pausing still works when pause is bound to another key.
+pub fn handle_escape(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
     mut next_state: ResMut<NextState<GameState>>,
     current_state: Res<State<GameState>>,
+    settings_origin: Res<SettingsOrigin>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        match current_state.get() {
-            GameState::InGame | GameState::NewWorldkeeper => {
-                info!("Returning to main menu");
-                next_state.set(GameState::MainMenu);
-            }
-            _ => {}
-        }
+    let state = current_state.get();
+    let back = keyboard_input.just_pressed(KeyCode::Escape)
+        || gamepad_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::East);
+    let pause = matches!(state, GameState::InGame | GameState::Paused)
+        && (key_bindings.just_pressed(&keyboard_input, InputAction::Pause)
+            || gamepad_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::Start));
+    if !back && !pause {
+        return;
+    }
+
+    if let Some(target) = state.escape_target(&settings_origin.0) {
+        next_state.set(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use crate::components::confirm_dialog::ConfirmDialog;
+
+    fn quit_test_app(unsaved: bool) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(GameState::Paused)
+            .init_resource::<MenuTheme>()
+            .init_resource::<Locale>()
+            .init_resource::<SettingsOrigin>()
+            .init_resource::<WorldConfig>()
+            .init_resource::<SystemClipboard>()
+            .insert_resource(UnsavedChanges(unsaved))
+            .add_event::<ButtonActivated>()
+            .add_event::<SaveGameRequested>()
+            .add_event::<LoadGameRequested>()
+            .add_event::<AppExit>()
+            .add_event::<PlaySoundEffect>()
+            .add_event::<Notification>()
+            .add_systems(Update, handle_menu_actions);
+        app
+    }
+
+    fn activate(app: &mut App, action: MenuAction) {
+        let world = app.world_mut();
+        let entity = world
+            .query::<(Entity, &MenuButton)>()
+            .iter(world)
+            .find(|(_, button)| button.action == action)
+            .map(|(entity, _)| entity)
+            .unwrap_or_else(|| world.spawn(MenuButton::new(action)).id());
+        world.send_event(ButtonActivated { entity });
+        // The state changes on the update after it is set
+        app.update();
+        app.update();
+    }
+
+    fn state(app: &App) -> GameState {
+        app.world().resource::<State<GameState>>().get().clone()
+    }
+
+    #[test]
+    fn quitting_a_saved_world_returns_to_the_menu_at_once() {
+        let mut app = quit_test_app(false);
+
+        activate(&mut app, MenuAction::QuitToMenu);
+        assert_eq!(state(&app), GameState::MainMenu);
+    }
+
+    #[test]
+    fn quitting_with_unsaved_changes_asks_first() {
+        let mut app = quit_test_app(true);
+
+        activate(&mut app, MenuAction::QuitToMenu);
+        assert_eq!(state(&app), GameState::Paused);
+        let world = app.world_mut();
+        assert_eq!(world.query_filtered::<(), With<ConfirmDialog>>().iter(world).count(), 1);
+
+        activate(&mut app, MenuAction::SaveAndQuitToMenu);
+        let events = app.world().resource::<Events<SaveGameRequested>>();
+        let requests: Vec<_> = events.get_reader().read(events).map(|request| request.after).collect();
+        assert_eq!(requests, vec![AfterSave::ReturnToMenu]);
+        // Only a successful save leaves the game
+        assert_eq!(state(&app), GameState::Paused);
+
+        activate(&mut app, MenuAction::ReturnToMenu);
+        assert_eq!(state(&app), GameState::MainMenu);
     }
-}
\ No newline at end of file
+}
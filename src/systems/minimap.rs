@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::ImageSampler;
+use bevy::ui::RelativeCursorPosition;
+use bevy::window::PrimaryWindow;
+use crate::components::camera::GameCamera;
+use crate::components::tile::Tile;
+use crate::components::ui::*;
+use crate::resources::{TerrainPalette, WorldBounds, WorldConfig};
+
+/// Length of the minimap's longer side, in logical pixels
+pub const MINIMAP_SIZE: f32 = 180.0;
+/// Width of the minimap border and the camera view outline
+pub const MINIMAP_BORDER: f32 = 2.0;
+/// Distance of the minimap from the bottom and left of the screen
+pub const MINIMAP_MARGIN: f32 = 10.0;
+
+/// Setup the minimap in the bottom-left corner, one pixel per tile
+pub fn setup_minimap(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    config: Res<WorldConfig>,
+    palette: Res<TerrainPalette>,
+    tile_query: Query<&Tile>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let image = tile_image(&config, &palette, tile_query.iter());
+    let scale = MINIMAP_SIZE / config.width.max(config.height) as f32;
+
+    commands
+        .spawn((
+            ImageBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(MINIMAP_MARGIN),
+                    left: Val::Px(MINIMAP_MARGIN),
+                    width: Val::Px(config.width as f32 * scale),
+                    height: Val::Px(config.height as f32 * scale),
+                    border: UiRect::all(Val::Px(MINIMAP_BORDER)),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                image: UiImage::new(images.add(image)),
+                ..default()
+            },
+            BorderColor(theme.button_hovered),
+            Interaction::default(),
+            RelativeCursorPosition::default(),
+            Minimap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Val::Px(MINIMAP_BORDER)),
+                        ..default()
+                    },
+                    border_color: Color::WHITE.into(),
+                    ..default()
+                },
+                MinimapViewport,
+            ));
+        });
+}
+
+/// Redraw the minimap when any tile's terrain changes, e.g. from weather or miracles, or the
+/// terrain palette does
+pub fn update_minimap_image(
+    config: Res<WorldConfig>,
+    palette: Res<TerrainPalette>,
+    changed_tiles: Query<(), Changed<Tile>>,
+    tile_query: Query<&Tile>,
+    minimap_query: Query<&UiImage, With<Minimap>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if changed_tiles.is_empty() && !palette.is_changed() {
+        return;
+    }
+
+    for ui_image in &minimap_query {
+        if let Some(image) = images.get_mut(&ui_image.texture) {
+            write_minimap_pixels(image, &config, &palette, tile_query.iter());
+        }
+    }
+}
+
+/// Outline the area the gameplay camera can see
+pub fn update_minimap_viewport(
+    bounds: Res<WorldBounds>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    mut viewport_query: Query<&mut Style, With<MinimapViewport>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let world_size = bounds.size();
+    let view_size = Vec2::new(window.width(), window.height()) * projection.scale;
+    let view_top_left = camera_transform.translation.truncate() + Vec2::new(-view_size.x, view_size.y) / 2.0;
+
+    // Minimap y points down while world y points up
+    let left = (view_top_left.x - bounds.min.x) / world_size.x;
+    let top = (bounds.max.y - view_top_left.y) / world_size.y;
+    let size = view_size / world_size;
+
+    for mut style in &mut viewport_query {
+        style.left = Val::Percent(left * 100.0);
+        style.top = Val::Percent(top * 100.0);
+        style.width = Val::Percent(size.x * 100.0);
+        style.height = Val::Percent(size.y * 100.0);
+    }
+}
+
+/// Recenter the gameplay camera on the point of the minimap that is clicked or dragged
+pub fn recenter_camera_from_minimap(
+    bounds: Res<WorldBounds>,
+    minimap_query: Query<(&Interaction, &RelativeCursorPosition), With<Minimap>>,
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
+) {
+    let Ok((interaction, cursor)) = minimap_query.get_single() else {
+        return;
+    };
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    let Some(position) = cursor.normalized else {
+        return;
+    };
+
+    let position = position.clamp(Vec2::ZERO, Vec2::ONE);
+    let target = bounds.min + Vec2::new(position.x, 1.0 - position.y) * bounds.size();
+    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+        camera_transform.translation.x = target.x;
+        camera_transform.translation.y = target.y;
+    }
+}
+
+/// Cleanup the minimap when leaving the game
+pub fn cleanup_minimap(mut commands: Commands, minimap_query: Query<Entity, With<Minimap>>) {
+    for entity in &minimap_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Image of a world with one pixel per tile, as drawn on the minimap
+pub fn tile_image<'a>(
+    config: &WorldConfig,
+    palette: &TerrainPalette,
+    tiles: impl Iterator<Item = &'a Tile>,
+) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    // Keep tiles as crisp squares rather than blurring them together
+    image.sampler = ImageSampler::nearest();
+    write_minimap_pixels(&mut image, config, palette, tiles);
+    image
+}
+
+/// Color one pixel per tile by terrain and biome, with tile row 0 at the bottom of the image
+fn write_minimap_pixels<'a>(
+    image: &mut Image,
+    config: &WorldConfig,
+    palette: &TerrainPalette,
+    tiles: impl Iterator<Item = &'a Tile>,
+) {
+    for tile in tiles {
+        if tile.coord.x >= config.width || tile.coord.y >= config.height {
+            continue;
+        }
+        let row = config.height - 1 - tile.coord.y;
+        let index = ((row * config.width + tile.coord.x) * 4) as usize;
+        let color = palette.color(tile.terrain, tile.biome).to_srgba();
+        image.data[index..index + 4].copy_from_slice(&[
+            (color.red * 255.0) as u8,
+            (color.green * 255.0) as u8,
+            (color.blue * 255.0) as u8,
+            255,
+        ]);
+    }
+}
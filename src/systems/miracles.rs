@@ -0,0 +1,316 @@
+use bevy::prelude::*;
+use crate::components::tooltip::Tooltip;
+use crate::components::ui::*;
+use crate::events::{DisasterTriggered, MiracleCast, Notification, PlaySoundEffect};
+use crate::resources::{
+    CastError, DivinePower, EventLog, KeyBindings, Miracle, Sandbox, SimulationClock, SoundEffect,
+    Weather, WeatherConfig, WeatherTimer,
+};
+
+const MIRACLE_BUTTON_WIDTH: f32 = 130.0;
+const MIRACLE_BUTTON_GAP: f32 = 10.0;
+/// Width of the mana bar, spanning the miracle buttons below it
+const MANA_BAR_WIDTH: f32 =
+    Miracle::ALL.len() as f32 * (MIRACLE_BUTTON_WIDTH + MIRACLE_BUTTON_GAP) - MIRACLE_BUTTON_GAP;
+/// Seconds the mana bar stays red after a rejected cast
+const MANA_FLASH_SECONDS: f32 = 0.4;
+const MANA_COLOR: Color = Color::srgb(0.35, 0.5, 1.0);
+const MANA_FLASH_COLOR: Color = Color::srgb(0.9, 0.15, 0.15);
+/// Background of miracle buttons that can't be cast right now
+const UNAVAILABLE_COLOR: Color = Color::srgb(0.08, 0.08, 0.08);
+
+/// Start each new world with a full mana pool and no cooldowns, unlimited in sandbox mode
+pub fn reset_divine_power(mut divine_power: ResMut<DivinePower>, sandbox: Res<Sandbox>) {
+    *divine_power = DivinePower::default();
+    divine_power.unlimited = sandbox.0;
+}
+
+/// Setup the miracle bar in the bottom-right corner, with the mana bar above its buttons
+pub fn setup_miracle_bar(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    divine_power: Res<DivinePower>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(60.0),
+                    right: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                ..default()
+            },
+            MiracleBar,
+        ))
+        .with_children(|parent| {
+            // Mana bar
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(MANA_BAR_WIDTH),
+                            height: Val::Px(12.0),
+                            ..default()
+                        },
+                        background_color: theme.button_normal.into(),
+                        ..default()
+                    },
+                    ManaBar::default(),
+                    ThemeColor::Widget,
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(divine_power.fraction() * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: MANA_COLOR.into(),
+                            ..default()
+                        },
+                        ManaBarFill,
+                    ));
+                });
+
+            // Miracle buttons
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(MIRACLE_BUTTON_GAP),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for miracle in Miracle::ALL {
+                        row.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    min_width: Val::Px(MIRACLE_BUTTON_WIDTH),
+                                    min_height: Val::Px(40.0),
+                                    padding: UiRect::horizontal(Val::Px(8.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: theme.button_normal.into(),
+                                ..default()
+                            },
+                            MiracleButton { miracle },
+                            Tooltip::new(format!(
+                                "Costs {:.0} mana, ready again after {:.0} hours",
+                                miracle.cost(),
+                                miracle.cooldown_hours()
+                            )),
+                        ))
+                        .with_children(|button| {
+                            button.spawn((
+                                TextBundle::from_section(
+                                    miracle.name(),
+                                    TextStyle {
+                                        font_size: theme.font_size(24.0),
+                                        color: theme.button_text,
+                                        ..default()
+                                    },
+                                ),
+                                ThemeColor::Text,
+                            ));
+                        });
+                    }
+                });
+        });
+}
+
+/// Regain mana as in-game time passes
+pub fn regenerate_mana(clock: Res<SimulationClock>, mut divine_power: ResMut<DivinePower>) {
+    if divine_power.last_update_hour != clock.elapsed_hours {
+        divine_power.regenerate(clock.elapsed_hours);
+    }
+}
+
+/// Cast miracles from their hotkeys and toolbar buttons
+///
+/// Casts without enough mana, or during a cooldown, are rejected with a sound and a red
+/// flash of the mana bar.
+#[allow(clippy::too_many_arguments)]
+pub fn cast_miracles(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    clock: Res<SimulationClock>,
+    button_query: Query<(&Interaction, &MiracleButton), Changed<Interaction>>,
+    mut divine_power: ResMut<DivinePower>,
+    mut mana_bar_query: Query<&mut ManaBar>,
+    mut casts: EventWriter<MiracleCast>,
+    mut sounds: EventWriter<PlaySoundEffect>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let clicked = button_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| button.miracle);
+    // Ctrl combinations are undo and redo, which share keys with the default hotkeys
+    let ctrl_held = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let pressed = Miracle::ALL.into_iter().filter(|miracle| {
+        !ctrl_held && key_bindings.just_pressed(&keyboard_input, miracle.input_action())
+    });
+
+    for miracle in clicked.chain(pressed) {
+        match divine_power.try_cast(miracle, clock.elapsed_hours) {
+            Ok(()) => {
+                info!("Cast {}", miracle.name());
+                casts.send(MiracleCast(miracle));
+            }
+            Err(error) => {
+                let message = match error {
+                    CastError::NotEnoughMana => "Not enough mana".to_string(),
+                    CastError::CoolingDown { remaining_hours } => format!(
+                        "{} is ready again in {:.0} hours",
+                        miracle.name(),
+                        remaining_hours.ceil()
+                    ),
+                };
+                notifications.send(Notification::new(message));
+                sounds.send(PlaySoundEffect(SoundEffect::Reject));
+                for mut mana_bar in &mut mana_bar_query {
+                    mana_bar.flash_seconds = MANA_FLASH_SECONDS;
+                }
+            }
+        }
+    }
+}
+
+/// Apply the effects of cast miracles to the weather, and set off cast disasters
+pub fn apply_miracles(
+    mut casts: EventReader<MiracleCast>,
+    clock: Res<SimulationClock>,
+    weather_config: Res<WeatherConfig>,
+    mut weather: ResMut<Weather>,
+    mut weather_timer: ResMut<WeatherTimer>,
+    mut event_log: ResMut<EventLog>,
+    mut disasters: EventWriter<DisasterTriggered>,
+) {
+    for MiracleCast(miracle) in casts.read() {
+        if let Some(disaster) = miracle.disaster() {
+            disasters.send(DisasterTriggered(disaster));
+            continue;
+        }
+        *weather = if *miracle == Miracle::Rain {
+            Weather::Rain
+        } else {
+            Weather::Clear
+        };
+        // Hold the new weather for a full interval before it may change again
+        weather_timer.next_change_hour = clock.elapsed_hours + weather_config.change_interval_hours;
+        event_log.push(&clock, format!("You cast {}", miracle.name()), None);
+    }
+}
+
+/// Resize the mana bar to the pool and flash it red after a rejected cast
+pub fn update_mana_bar(
+    time: Res<Time>,
+    divine_power: Res<DivinePower>,
+    mut bar_query: Query<(&mut ManaBar, &Children)>,
+    mut fill_query: Query<(&mut Style, &mut BackgroundColor), With<ManaBarFill>>,
+) {
+    for (mut mana_bar, children) in &mut bar_query {
+        if mana_bar.flash_seconds > 0.0 {
+            mana_bar.flash_seconds = (mana_bar.flash_seconds - time.delta_seconds()).max(0.0);
+        }
+        let color = if mana_bar.flash_seconds > 0.0 {
+            MANA_FLASH_COLOR
+        } else {
+            MANA_COLOR
+        };
+
+        for child in children.iter() {
+            if let Ok((mut style, mut background_color)) = fill_query.get_mut(*child) {
+                style.width = Val::Percent(divine_power.fraction() * 100.0);
+                if background_color.0 != color {
+                    background_color.0 = color;
+                }
+            }
+        }
+    }
+}
+
+/// Dim miracle buttons that can't be cast right now, and show hover on the rest
+pub fn update_miracle_buttons(
+    theme: Res<MenuTheme>,
+    clock: Res<SimulationClock>,
+    divine_power: Res<DivinePower>,
+    mut button_query: Query<(&Interaction, &MiracleButton, &mut BackgroundColor)>,
+) {
+    for (interaction, button, mut background_color) in &mut button_query {
+        let available = divine_power.can_cast(button.miracle, clock.elapsed_hours);
+        let color = if !available {
+            UNAVAILABLE_COLOR
+        } else if *interaction == Interaction::Hovered {
+            theme.button_hovered
+        } else {
+            theme.button_normal
+        };
+
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+    }
+}
+
+/// Cleanup the miracle bar when leaving the game
+pub fn cleanup_miracle_bar(
+    mut commands: Commands,
+    bar_query: Query<Entity, With<MiracleBar>>,
+) {
+    for entity in &bar_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::InputAction;
+
+    fn miracle_test_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<SimulationClock>()
+            .init_resource::<DivinePower>()
+            .add_event::<MiracleCast>()
+            .add_event::<PlaySoundEffect>()
+            .add_event::<Notification>()
+            .add_systems(Update, cast_miracles);
+        app
+    }
+
+    fn casts(app: &App) -> usize {
+        app.world().resource::<Events<MiracleCast>>().len()
+    }
+
+    #[test]
+    fn ctrl_z_undoes_without_casting_rain() {
+        let mut app = miracle_test_app();
+        let rain_key = app.world().resource::<KeyBindings>().key(InputAction::CastRain);
+        assert_eq!(rain_key, KeyCode::KeyZ);
+
+        let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard_input.press(KeyCode::ControlLeft);
+        keyboard_input.press(rain_key);
+        app.update();
+        assert_eq!(casts(&app), 0);
+        assert_eq!(app.world().resource::<DivinePower>().mana, DivinePower::MAX_MANA);
+
+        let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard_input.release_all();
+        keyboard_input.clear();
+        keyboard_input.press(rain_key);
+        app.update();
+        assert_eq!(casts(&app), 1);
+    }
+}
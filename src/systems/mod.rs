@@ -1,3 +1,85 @@
+pub mod action_history;
+pub mod attract_mode;
+pub mod audio;
+pub mod autosave;
+pub mod camera;
+pub mod controls;
+pub mod credits;
+pub mod cursor;
+pub mod diagnostics;
+pub mod disasters;
+pub mod economy;
+pub mod event_log;
+pub mod game_over;
+pub mod gamepad;
+pub mod grid;
+pub mod health;
+pub mod help;
+pub mod hud;
+pub mod inspector;
+pub mod lighting;
+pub mod loading;
 pub mod menu;
+pub mod minimap;
+pub mod miracles;
+pub mod new_worldkeeper;
+pub mod notifications;
+pub mod pause;
+pub mod placement;
+pub mod population;
+pub mod regeneration;
+pub mod save_load;
+pub mod saves;
+pub mod screenshot;
+pub mod selection;
+pub mod settings;
+pub mod simulation;
+pub mod statistics;
+pub mod terrain_brush;
+pub mod tooltip;
+pub mod ui_scale;
+pub mod weather;
+pub mod world_gen;
 
-pub use menu::*;
\ No newline at end of file
+pub use action_history::*;
+pub use attract_mode::*;
+pub use audio::*;
+pub use autosave::*;
+pub use camera::*;
+pub use controls::*;
+pub use credits::*;
+pub use cursor::*;
+pub use diagnostics::*;
+pub use disasters::*;
+pub use economy::*;
+pub use event_log::*;
+pub use game_over::*;
+pub use gamepad::*;
+pub use grid::*;
+pub use health::*;
+pub use help::*;
+pub use hud::*;
+pub use inspector::*;
+pub use lighting::*;
+pub use loading::*;
+pub use menu::*;
+pub use minimap::*;
+pub use miracles::*;
+pub use new_worldkeeper::*;
+pub use notifications::*;
+pub use pause::*;
+pub use placement::*;
+pub use population::*;
+pub use regeneration::*;
+pub use save_load::*;
+pub use saves::*;
+pub use screenshot::*;
+pub use selection::*;
+pub use settings::*;
+pub use simulation::*;
+pub use statistics::*;
+pub use terrain_brush::*;
+pub use tooltip::*;
+pub use ui_scale::*;
+pub use weather::*;
+pub use world_gen::*;
\ No newline at end of file
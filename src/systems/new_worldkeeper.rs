@@ -0,0 +1,302 @@
+use bevy::prelude::*;
+use crate::components::input_field::{spawn_text_input, TextInput};
+use crate::components::slider::{spawn_slider, Slider, SliderChanged};
+use crate::components::camera::UiCamera;
+use crate::components::ui::*;
+use crate::events::{ButtonActivated, Notification};
+use crate::i18n::Locale;
+use crate::resources::{parse_seed, GameState, MapSize, Sandbox, SystemClipboard, WorldConfig};
+use crate::systems::menu::{create_labeled_row, create_menu_button, create_sized_button, create_value_text};
+
+/// Setup the New Worldkeeper screen with the world generation form
+pub fn setup_new_worldkeeper_screen(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    config: Res<WorldConfig>,
+    sandbox: Res<Sandbox>,
+) {
+    info!("Setting up New Worldkeeper screen");
+
+    // Spawn a camera for UI rendering
+    commands.spawn((Camera2dBundle::default(), UiCamera, NewWorldkeeperScreen));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::srgb(0.1, 0.2, 0.1).into(),
+                ..default()
+            },
+            NewWorldkeeperScreen,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn(TextBundle::from_section(
+                "New Worldkeeper",
+                TextStyle {
+                    font_size: theme.font_size(48.0),
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            // Seed row
+            create_labeled_row(parent, &theme, "Seed", |row| {
+                spawn_text_input(
+                    row,
+                    &theme,
+                    TextInput::new("Random", SeedField::MAX_LENGTH),
+                    Val::Px(360.0),
+                    SeedField,
+                );
+                create_sized_button(
+                    row,
+                    &theme,
+                    "Copy",
+                    Val::Px(100.0),
+                    Val::Px(50.0),
+                    WorldSetupButton::new(WorldSetupAction::CopySeed),
+                );
+                create_sized_button(
+                    row,
+                    &theme,
+                    "Paste",
+                    Val::Px(100.0),
+                    Val::Px(50.0),
+                    WorldSetupButton::new(WorldSetupAction::PasteSeed),
+                );
+            });
+
+            // Map size row
+            create_labeled_row(parent, &theme, "Map Size", |row| {
+                for size in [MapSize::Small, MapSize::Medium, MapSize::Large] {
+                    create_sized_button(
+                        row,
+                        &theme,
+                        size.label(),
+                        Val::Px(120.0),
+                        Val::Px(50.0),
+                        WorldSetupButton::new(WorldSetupAction::SelectMapSize(size)),
+                    );
+                }
+                create_value_text(row, &theme, config.size_label(), WorldSetupValueText::MapSize);
+            });
+
+            // Water level row
+            create_labeled_row(parent, &theme, "Water Level", |row| {
+                spawn_slider(
+                    row,
+                    &theme,
+                    Slider::new(
+                        WorldConfig::MIN_WATER_LEVEL as f32,
+                        WorldConfig::MAX_WATER_LEVEL as f32,
+                        config.water_level as f32,
+                        WorldConfig::WATER_LEVEL_STEP as f32,
+                    ),
+                    Val::Px(300.0),
+                    WaterLevelSlider,
+                );
+                create_value_text(row, &theme, config.water_level_label(), WorldSetupValueText::WaterLevel);
+            });
+
+            // Day length row
+            create_labeled_row(parent, &theme, "Day Length", |row| {
+                spawn_slider(
+                    row,
+                    &theme,
+                    Slider::new(
+                        WorldConfig::MIN_DAY_LENGTH,
+                        WorldConfig::MAX_DAY_LENGTH,
+                        config.day_length_seconds,
+                        WorldConfig::DAY_LENGTH_STEP,
+                    ),
+                    Val::Px(300.0),
+                    DayLengthSlider,
+                );
+                create_value_text(row, &theme, config.day_length_label(), WorldSetupValueText::DayLength);
+            });
+
+            // Sandbox row
+            create_labeled_row(parent, &theme, "Sandbox", |row| {
+                create_sized_button(
+                    row,
+                    &theme,
+                    "Toggle",
+                    Val::Px(120.0),
+                    Val::Px(50.0),
+                    WorldSetupButton::new(WorldSetupAction::ToggleSandbox),
+                );
+                create_value_text(row, &theme, sandbox.label().to_string(), WorldSetupValueText::Sandbox);
+            });
+
+            // Button container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(15.0),
+                        margin: UiRect::top(Val::Px(30.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    create_sized_button(
+                        parent,
+                        &theme,
+                        "Create World",
+                        Val::Px(300.0),
+                        Val::Px(60.0),
+                        WorldSetupButton::new(WorldSetupAction::CreateWorld),
+                    );
+                    create_menu_button(parent, &theme, &locale, "Back", MenuAction::Back);
+                });
+
+            parent.spawn(TextBundle::from_section(
+                "Press ESC to return to main menu",
+                TextStyle {
+                    font_size: theme.font_size(24.0),
+                    color: Color::srgb(0.8, 0.8, 0.8),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Handle activated world setup buttons
+#[allow(clippy::too_many_arguments)]
+pub fn handle_world_setup_actions(
+    mut activated: EventReader<ButtonActivated>,
+    button_query: Query<&WorldSetupButton>,
+    mut seed_query: Query<&mut TextInput, With<SeedField>>,
+    mut config: ResMut<WorldConfig>,
+    mut sandbox: ResMut<Sandbox>,
+    mut clipboard: ResMut<SystemClipboard>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut notifications: EventWriter<Notification>,
+) {
+    for event in activated.read() {
+        let Ok(setup_button) = button_query.get(event.entity) else {
+            continue;
+        };
+
+        match setup_button.action {
+            WorldSetupAction::SelectMapSize(size) => {
+                config.set_map_size(size);
+            }
+            WorldSetupAction::CopySeed => {
+                let seed = seed_query
+                    .get_single()
+                    .ok()
+                    .and_then(|seed_input| parse_seed(&seed_input.value))
+                    .unwrap_or(config.seed);
+                notifications.send(copy_seed(&mut clipboard, seed));
+            }
+            WorldSetupAction::PasteSeed => {
+                let Ok(mut seed_input) = seed_query.get_single_mut() else {
+                    continue;
+                };
+                match clipboard.get_text() {
+                    Ok(text) => match parse_seed(&text) {
+                        Some(seed) => {
+                            seed_input.value = seed.to_string();
+                        }
+                        None => {
+                            notifications.send(Notification::new("Clipboard doesn't hold a seed"));
+                        }
+                    },
+                    Err(error) => {
+                        warn!("Failed to read the clipboard: {}", error);
+                        notifications.send(Notification::new("Couldn't read the clipboard"));
+                    }
+                }
+            }
+            WorldSetupAction::ToggleSandbox => {
+                sandbox.0 = !sandbox.0;
+            }
+            WorldSetupAction::CreateWorld => {
+                let seed_text = seed_query
+                    .get_single()
+                    .map(|seed_input| seed_input.value.as_str())
+                    .unwrap_or_default();
+
+                // Empty or non-numeric seeds fall back to a random one
+                config.seed = parse_seed(seed_text).unwrap_or_else(|| {
+                    let seed = rand::random();
+                    info!("Seed '{}' is not a number, using random seed {}", seed_text, seed);
+                    seed
+                });
+
+                info!("Creating world: {:?}, sandbox {}", *config, sandbox.label());
+                next_state.set(GameState::Loading);
+            }
+        }
+    }
+}
+
+/// Copy a seed to the clipboard, returning a toast that reports the outcome
+pub fn copy_seed(clipboard: &mut SystemClipboard, seed: u64) -> Notification {
+    match clipboard.set_text(&seed.to_string()) {
+        Ok(()) => {
+            info!("Copied seed {} to the clipboard", seed);
+            Notification::new(format!("Copied seed {}", seed))
+        }
+        Err(error) => {
+            warn!("Failed to copy seed {} to the clipboard: {}", seed, error);
+            Notification::new("Couldn't copy to the clipboard")
+        }
+    }
+}
+
+/// Apply world setup slider changes to the world config
+pub fn handle_world_setup_sliders(
+    mut changed: EventReader<SliderChanged>,
+    water_level_query: Query<(), With<WaterLevelSlider>>,
+    day_length_query: Query<(), With<DayLengthSlider>>,
+    mut config: ResMut<WorldConfig>,
+) {
+    for event in changed.read() {
+        if water_level_query.contains(event.entity) {
+            config.set_water_level(event.value as f64);
+        } else if day_length_query.contains(event.entity) {
+            config.set_day_length(event.value);
+        }
+    }
+}
+
+/// Keep the displayed world setup values in sync with the world config and sandbox flag
+pub fn update_world_setup_text(
+    config: Res<WorldConfig>,
+    sandbox: Res<Sandbox>,
+    mut text_query: Query<(&mut Text, &WorldSetupValueText)>,
+) {
+    for (mut text, kind) in &mut text_query {
+        text.sections[0].value = match kind {
+            WorldSetupValueText::MapSize => config.size_label(),
+            WorldSetupValueText::WaterLevel => config.water_level_label(),
+            WorldSetupValueText::DayLength => config.day_length_label(),
+            WorldSetupValueText::Sandbox => sandbox.label().to_string(),
+        };
+    }
+}
+
+/// Cleanup New Worldkeeper screen and its UI camera
+pub fn cleanup_new_worldkeeper_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<NewWorldkeeperScreen>>,
+) {
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("New Worldkeeper screen cleaned up");
+}
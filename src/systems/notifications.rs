@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use crate::components::ui::*;
+use crate::events::Notification;
+
+/// Seconds a notification stays on screen, including its fade out
+const NOTIFICATION_SECONDS: f32 = 3.0;
+/// Seconds at the end of a notification's life spent fading out
+const FADE_SECONDS: f32 = 1.0;
+/// Older notifications are dropped once this many are on screen
+const MAX_NOTIFICATIONS: usize = 5;
+/// Toasts sit on a dark backdrop whatever the menu theme
+const NOTIFICATION_TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+/// Spawn the column notifications stack in, once at startup
+///
+/// It is never despawned, so messages stay visible across screen changes.
+pub fn setup_notification_stack(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(54.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            // Above every screen, but below the debug overlay
+            z_index: ZIndex::Global(i32::MAX - 1),
+            ..default()
+        },
+        NotificationStack,
+    ));
+}
+
+/// Add a message to the bottom of the stack for each `Notification` event
+pub fn spawn_notifications(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    mut notifications: EventReader<Notification>,
+    stack_query: Query<Entity, With<NotificationStack>>,
+    toast_query: Query<(Entity, &NotificationToast)>,
+) {
+    let Ok(stack) = stack_query.get_single() else {
+        notifications.clear();
+        return;
+    };
+
+    let mut active: Vec<(Entity, f32)> = toast_query
+        .iter()
+        .map(|(entity, toast)| (entity, toast.remaining_seconds))
+        .collect();
+
+    for notification in notifications.read() {
+        info!("Notification: {}", notification.message);
+        let toast = commands
+            .spawn((
+                TextBundle::from_section(
+                    notification.message.clone(),
+                    TextStyle {
+                        font_size: theme.font_size(24.0),
+                        color: NOTIFICATION_TEXT_COLOR,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    ..default()
+                })
+                .with_background_color(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                NotificationToast {
+                    remaining_seconds: NOTIFICATION_SECONDS,
+                },
+            ))
+            .id();
+        commands.entity(stack).add_child(toast);
+        active.push((toast, NOTIFICATION_SECONDS));
+    }
+
+    // Drop the oldest messages, which have the least time left, when too many pile up
+    if active.len() > MAX_NOTIFICATIONS {
+        active.sort_by(|a, b| a.1.total_cmp(&b.1));
+        for (entity, _) in &active[..active.len() - MAX_NOTIFICATIONS] {
+            commands.entity(*entity).despawn_recursive();
+        }
+    }
+}
+
+/// Fade notifications out at the end of their life and remove them once expired
+pub fn expire_notifications(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut NotificationToast, &mut Text, &mut BackgroundColor)>,
+) {
+    for (entity, mut toast, mut text, mut background_color) in &mut toast_query {
+        toast.remaining_seconds -= time.delta_seconds();
+        if toast.remaining_seconds <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let alpha = (toast.remaining_seconds / FADE_SECONDS).min(1.0);
+        for section in &mut text.sections {
+            section.style.color.set_alpha(alpha);
+        }
+        background_color.0.set_alpha(alpha * 0.6);
+    }
+}
@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use crate::components::confirm_dialog::{spawn_confirm_dialog, spawn_dialog, ConfirmDialogButton};
+use crate::components::input_field::{spawn_text_input, TextInput, TextInputSubmitted};
+use crate::components::ui::*;
+use crate::events::{ButtonActivated, Notification, SaveGameRequested};
+use crate::i18n::Locale;
+use crate::resources::{GameState, Settings, WorldConfig};
+use crate::save::{sanitize_save_name, save_path};
+use crate::systems::menu::{create_menu_button, create_sized_button};
+
+/// Longest name a save can be given in the save-as dialog
+const MAX_SAVE_NAME_LENGTH: usize = 32;
+
+/// Pause the game when the window loses focus, if enabled in the settings
+///
+/// Regaining focus leaves the player in the pause menu rather than resuming on its own.
+pub fn pause_on_focus_loss(
+    mut focus_events: EventReader<WindowFocused>,
+    settings: Res<Settings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let lost_focus = focus_events.read().any(|event| !event.focused);
+    if lost_focus && settings.pause_on_focus_loss {
+        info!("Window lost focus, pausing game");
+        next_state.set(GameState::Paused);
+    }
+}
+
+/// Setup the pause menu overlay on top of the paused game world
+pub fn setup_pause_menu(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    world_config: Res<WorldConfig>,
+) {
+    info!("Setting up pause menu");
+
+    // The gameplay camera is still alive while paused, so the overlay renders through it
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            PauseMenu,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                TextBundle::from_section(
+                    locale.t("pause.title"),
+                    TextStyle {
+                        font_size: theme.title_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Title,
+            ));
+
+            // Seed of the current world, so it can be shared
+            parent.spawn((
+                TextBundle::from_section(
+                    format!("{}: {}", locale.t("pause.seed"), world_config.seed),
+                    TextStyle {
+                        font_size: theme.font_size(24.0),
+                        color: theme.button_text,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Text,
+            ));
+
+            // Button container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(15.0),
+                        margin: UiRect::top(Val::Px(40.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    create_menu_button(parent, &theme, &locale, &locale.t("pause.resume"), MenuAction::Resume);
+                    create_menu_button(parent, &theme, &locale, &locale.t("pause.save"), MenuAction::SaveGame);
+                    create_menu_button(parent, &theme, &locale, &locale.t("pause.save_as"), MenuAction::SaveAs);
+                    create_menu_button(parent, &theme, &locale, &locale.t("pause.copy_seed"), MenuAction::CopySeed);
+                    create_menu_button(parent, &theme, &locale, &locale.t("menu.settings"), MenuAction::Settings);
+                    // Asks first itself, and only when there are unsaved changes
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        &locale,
+                        &locale.t("pause.quit_to_menu"),
+                        MenuAction::QuitToMenu,
+                    );
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        &locale,
+                        &locale.t("pause.quit_to_desktop"),
+                        MenuAction::QuitToDesktop,
+                    );
+                });
+        });
+}
+
+/// Spawn a modal asking for the name to save the current world under
+pub fn spawn_save_as_dialog(commands: &mut Commands, theme: &MenuTheme, locale: &Locale) {
+    spawn_dialog(commands, theme, SaveAsDialog, |panel| {
+        panel.spawn((
+            TextBundle::from_section(
+                locale.t("save_as.title"),
+                TextStyle {
+                    font_size: theme.button_font_size,
+                    color: theme.title_color,
+                    ..default()
+                },
+            ),
+            ThemeColor::Title,
+        ));
+
+        // Focused straight away so the player can start typing
+        let mut name_input = TextInput::new(locale.t("save_as.placeholder"), MAX_SAVE_NAME_LENGTH).with_submit();
+        name_input.focused = true;
+        spawn_text_input(panel, theme, name_input, Val::Px(360.0), SaveAsField);
+
+        panel
+            .spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|row| {
+                create_sized_button(
+                    row,
+                    theme,
+                    &locale.t("pause.save"),
+                    Val::Px(140.0),
+                    Val::Px(50.0),
+                    (SaveAsButton, ConfirmDialogButton),
+                );
+                create_sized_button(
+                    row,
+                    theme,
+                    &locale.t("dialog.cancel"),
+                    Val::Px(140.0),
+                    Val::Px(50.0),
+                    ConfirmDialogButton,
+                );
+            });
+    });
+}
+
+/// Save under the name entered in the save-as dialog, from its Save button or Enter
+///
+/// The name is sanitized into a file name, and saving over an existing file asks first.
+/// Runs before the dialog closes itself, so the entered name can still be read.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_save_as(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    mut activated: EventReader<ButtonActivated>,
+    mut submitted: EventReader<TextInputSubmitted>,
+    button_query: Query<(), With<SaveAsButton>>,
+    field_query: Query<&TextInput, With<SaveAsField>>,
+    dialog_query: Query<Entity, With<SaveAsDialog>>,
+    mut save_requests: EventWriter<SaveGameRequested>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let pressed = activated.read().any(|event| button_query.contains(event.entity));
+    let entered = submitted.read().any(|event| field_query.contains(event.entity));
+    if !pressed && !entered {
+        return;
+    }
+    let Ok(name_input) = field_query.get_single() else {
+        return;
+    };
+    let name = name_input.value.trim().to_string();
+
+    // Enter doesn't go through the dialog's buttons, so the dialog has to be closed here
+    if !pressed {
+        for entity in &dialog_query {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    let file_name = sanitize_save_name(&name);
+    if file_name.is_empty() {
+        notifications.send(Notification::new(locale.t("save_as.invalid_name")));
+        return;
+    }
+
+    if save_path(&file_name).exists() {
+        spawn_confirm_dialog(
+            &mut commands,
+            &theme,
+            &locale,
+            &locale.t("save_as.overwrite_confirm").replace("{name}", &name),
+            MenuAction::SaveGameAs(name),
+        );
+    } else {
+        save_requests.send(SaveGameRequested {
+            name: Some(name),
+            ..default()
+        });
+    }
+}
+
+/// Cleanup the pause menu overlay when leaving the Paused state
+pub fn cleanup_pause_menu(
+    mut commands: Commands,
+    menu_query: Query<Entity, With<PauseMenu>>,
+) {
+    for entity in &menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    info!("Pause menu cleaned up");
+}
@@ -0,0 +1,391 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::components::camera::GameCamera;
+use crate::components::placeable::{Deletable, EntityKind, Health, Placed};
+use crate::components::tile::Tile;
+use crate::components::tooltip::Tooltip;
+use crate::components::ui::*;
+use crate::events::PlaySoundEffect;
+use crate::resources::{
+    ActionHistory, PlacedAssets, PlacementTool, SelectedTool, SoundEffect, TerrainBrush, TerrainRules,
+    WorldConfig, WorldEdit, TILE_SIZE,
+};
+use crate::utils::cursor_to_world;
+
+/// Placed entities draw above the tiles they stand on
+const PLACED_Z: f32 = 1.0;
+const DELETE_CURSOR_COLOR: Color = Color::srgba(1.0, 0.25, 0.2, 0.8);
+
+/// Setup the entity toolbar along the bottom of the screen
+pub fn setup_entity_toolbar(mut commands: Commands, theme: Res<MenuTheme>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            },
+            EntityToolbar,
+        ))
+        .with_children(|parent| {
+            for tool in PlacementTool::ALL {
+                let tooltip = match tool {
+                    PlacementTool::Place(kind) => {
+                        format!("Place a {} on the map", kind.name().to_lowercase())
+                    }
+                    PlacementTool::Delete => "Remove placed entities from the map".to_string(),
+                };
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                min_width: Val::Px(120.0),
+                                min_height: Val::Px(40.0),
+                                padding: UiRect::horizontal(Val::Px(8.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: theme.button_normal.into(),
+                            ..default()
+                        },
+                        ToolbarButton { tool },
+                        Tooltip::new(tooltip),
+                    ))
+                    .with_children(|button| {
+                        button.spawn((
+                            TextBundle::from_section(
+                                tool.name(),
+                                TextStyle {
+                                    font_size: theme.font_size(24.0),
+                                    color: theme.button_text,
+                                    ..default()
+                                },
+                            ),
+                            ThemeColor::Text,
+                        ));
+                    });
+            }
+        });
+}
+
+/// Select the clicked toolbar tool, or deselect it when clicked again
+///
+/// Selecting a toolbar tool puts away any terrain tool.
+pub fn handle_toolbar_buttons(
+    interaction_query: Query<(&Interaction, &ToolbarButton), Changed<Interaction>>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut brush: ResMut<TerrainBrush>,
+) {
+    for (interaction, toolbar_button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            selected_tool.0 = if selected_tool.0 == Some(toolbar_button.tool) {
+                None
+            } else {
+                brush.tool = None;
+                Some(toolbar_button.tool)
+            };
+        }
+    }
+}
+
+/// Color toolbar buttons to show hover and the selected tool
+pub fn update_toolbar_colors(
+    theme: Res<MenuTheme>,
+    selected_tool: Res<SelectedTool>,
+    mut button_query: Query<(&Interaction, &ToolbarButton, &mut BackgroundColor)>,
+) {
+    for (interaction, toolbar_button, mut background_color) in &mut button_query {
+        let color = if selected_tool.0 == Some(toolbar_button.tool) {
+            theme.button_pressed
+        } else if *interaction == Interaction::Hovered {
+            theme.button_hovered
+        } else {
+            theme.button_normal
+        };
+
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+    }
+}
+
+/// Place the selected entity kind on the tile under the cursor when the map is left-clicked
+///
+/// Placement is rejected, with a sound, on terrain `TerrainRules` doesn't allow the kind on
+/// and on tiles that are already occupied. Accepted placements are recorded so they can be
+/// undone.
+#[allow(clippy::too_many_arguments)]
+pub fn place_entity(
+    mut commands: Commands,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    selected_tool: Res<SelectedTool>,
+    config: Res<WorldConfig>,
+    terrain_rules: Res<TerrainRules>,
+    placed_assets: Res<PlacedAssets>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    toolbar_query: Query<&Interaction, MapBlockingUi>,
+    tile_query: Query<&Tile>,
+    placed_query: Query<&Placed>,
+    mut history: ResMut<ActionHistory>,
+    mut sounds: EventWriter<PlaySoundEffect>,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(PlacementTool::Place(kind)) = selected_tool.0 else {
+        return;
+    };
+
+    // Clicks on the toolbars and minimap aren't meant for the map underneath
+    if toolbar_query.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(world_position) = cursor_to_world(window, projection, camera_transform) else {
+        return;
+    };
+    let Some(coord) = config.tile_at(world_position) else {
+        return;
+    };
+
+    let Some(tile) = tile_query.iter().find(|tile| tile.coord == coord) else {
+        return;
+    };
+    if !terrain_rules.can_place(kind, tile.terrain) {
+        info!("Cannot place {} on {}", kind.name(), tile.terrain.name());
+        sounds.send(PlaySoundEffect(SoundEffect::Reject));
+        return;
+    }
+    if placed_query.iter().any(|placed| placed.coord == coord) {
+        info!("Tile {} is already occupied", coord);
+        sounds.send(PlaySoundEffect(SoundEffect::Reject));
+        return;
+    }
+
+    spawn_placed(&mut commands, &placed_assets, &config, kind, coord);
+    history.record(WorldEdit::Place { kind, coord });
+    info!("Placed {} at {}", kind.name(), coord);
+}
+
+/// Remove the placed entity on the tile under the cursor when the map is left-clicked with the
+/// delete tool
+///
+/// Only entities marked `Deletable` can be removed; clicking anything else is rejected with a
+/// sound. Deletions are recorded so they can be undone.
+#[allow(clippy::too_many_arguments)]
+pub fn delete_entity(
+    mut commands: Commands,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    selected_tool: Res<SelectedTool>,
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    toolbar_query: Query<&Interaction, MapBlockingUi>,
+    placed_query: Query<(Entity, &Placed), With<Deletable>>,
+    mut history: ResMut<ActionHistory>,
+    mut sounds: EventWriter<PlaySoundEffect>,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Left)
+        || selected_tool.0 != Some(PlacementTool::Delete)
+    {
+        return;
+    }
+    if toolbar_query.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(coord) = cursor_to_world(window, projection, camera_transform)
+        .and_then(|position| config.tile_at(position))
+    else {
+        return;
+    };
+
+    let Some((entity, placed)) = placed_query.iter().find(|(_, placed)| placed.coord == coord) else {
+        sounds.send(PlaySoundEffect(SoundEffect::Reject));
+        return;
+    };
+    commands.entity(entity).despawn_recursive();
+    history.record(WorldEdit::Delete { kind: placed.kind, coord });
+    info!("Deleted {} at {}", placed.kind.name(), coord);
+}
+
+/// Outline the tile under the cursor in red while the delete tool is selected
+pub fn draw_delete_cursor(
+    mut gizmos: Gizmos,
+    selected_tool: Res<SelectedTool>,
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+) {
+    if selected_tool.0 != Some(PlacementTool::Delete) {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(coord) = cursor_to_world(window, projection, camera_transform)
+        .and_then(|position| config.tile_at(position))
+    else {
+        return;
+    };
+
+    gizmos.rect_2d(
+        config.tile_center(coord),
+        Rot2::IDENTITY,
+        Vec2::splat(TILE_SIZE),
+        DELETE_CURSOR_COLOR,
+    );
+}
+
+/// Spawn a placed entity of a kind standing on a tile
+pub fn spawn_placed(
+    commands: &mut Commands,
+    placed_assets: &PlacedAssets,
+    config: &WorldConfig,
+    kind: EntityKind,
+    coord: UVec2,
+) -> Entity {
+    commands.spawn((
+        ColorMesh2dBundle {
+            mesh: placed_assets.mesh.clone().into(),
+            material: placed_assets.materials[&kind].clone(),
+            transform: Transform::from_translation(config.tile_center(coord).extend(PLACED_Z)),
+            ..default()
+        },
+        Placed { kind, coord },
+        Health::default(),
+        Deletable,
+    )).id()
+}
+
+/// Cleanup the entity toolbar when leaving the game
+pub fn cleanup_entity_toolbar(
+    mut commands: Commands,
+    toolbar_query: Query<Entity, With<EntityToolbar>>,
+) {
+    for entity in &toolbar_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// App running the delete tool with the cursor over a tile
+    fn delete_test_app(tool: PlacementTool, cursor_coord: UVec2) -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<MouseButton>>()
+            .insert_resource(SelectedTool(Some(tool)))
+            .init_resource::<WorldConfig>()
+            .init_resource::<ActionHistory>()
+            .add_event::<PlaySoundEffect>()
+            .add_systems(Update, delete_entity);
+
+        let mut window = Window::default();
+        window.set_cursor_position(Some(Vec2::new(window.width(), window.height()) / 2.0));
+        app.world_mut().spawn((window, PrimaryWindow));
+
+        let center = app.world().resource::<WorldConfig>().tile_center(cursor_coord);
+        app.world_mut().spawn((
+            Transform::from_translation(center.extend(0.0)),
+            OrthographicProjection::default(),
+            GameCamera,
+        ));
+        app
+    }
+
+    fn click(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+    }
+
+    fn rejections(app: &App) -> usize {
+        let events = app.world().resource::<Events<PlaySoundEffect>>();
+        events
+            .get_reader()
+            .read(events)
+            .filter(|event| event.0 == SoundEffect::Reject)
+            .count()
+    }
+
+    #[test]
+    fn clicking_a_placed_entity_deletes_it_and_records_the_edit() {
+        let coord = UVec2::new(4, 6);
+        let mut app = delete_test_app(PlacementTool::Delete, coord);
+        let tree = app
+            .world_mut()
+            .spawn((Placed { kind: EntityKind::Tree, coord }, Deletable))
+            .id();
+        let neighbour = app
+            .world_mut()
+            .spawn((Placed { kind: EntityKind::Animal, coord: coord + UVec2::X }, Deletable))
+            .id();
+
+        click(&mut app);
+
+        assert!(app.world().get_entity(tree).is_none());
+        assert!(app.world().get_entity(neighbour).is_some());
+        assert_eq!(rejections(&app), 0);
+        assert_eq!(
+            app.world_mut().resource_mut::<ActionHistory>().undo(),
+            Some(&WorldEdit::Delete { kind: EntityKind::Tree, coord })
+        );
+    }
+
+    #[test]
+    fn entities_that_are_not_deletable_are_kept() {
+        let coord = UVec2::new(4, 6);
+        let mut app = delete_test_app(PlacementTool::Delete, coord);
+        let entity = app
+            .world_mut()
+            .spawn(Placed { kind: EntityKind::Villager, coord })
+            .id();
+
+        click(&mut app);
+
+        assert!(app.world().get_entity(entity).is_some());
+        assert_eq!(rejections(&app), 1);
+        assert_eq!(app.world_mut().resource_mut::<ActionHistory>().undo(), None);
+    }
+
+    #[test]
+    fn other_tools_do_not_delete() {
+        let coord = UVec2::new(4, 6);
+        let mut app = delete_test_app(PlacementTool::Place(EntityKind::Tree), coord);
+        let entity = app
+            .world_mut()
+            .spawn((Placed { kind: EntityKind::Tree, coord }, Deletable))
+            .id();
+
+        click(&mut app);
+
+        assert!(app.world().get_entity(entity).is_some());
+        assert_eq!(rejections(&app), 0);
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use crate::components::placeable::Placed;
+use crate::components::tile::Tile;
+use crate::resources::{EventLog, GameOverReason, GameState, Population, Sandbox, SimulationClock};
+
+/// Start each new world with its founding population
+pub fn reset_population(mut population: ResMut<Population>) {
+    *population = Population::default();
+}
+
+/// Grow or shrink the population once per in-game day, ending the game when nobody is left
+#[allow(clippy::too_many_arguments)]
+pub fn update_population(
+    clock: Res<SimulationClock>,
+    tile_query: Query<&Tile>,
+    placed_query: Query<&Placed>,
+    mut population: ResMut<Population>,
+    mut event_log: ResMut<EventLog>,
+    sandbox: Res<Sandbox>,
+    mut game_over_reason: ResMut<GameOverReason>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let day = clock.elapsed_days();
+    if day <= population.last_update_day {
+        return;
+    }
+
+    let capacity = tile_query
+        .iter()
+        .map(|tile| Population::terrain_capacity(tile.terrain))
+        .chain(placed_query.iter().map(|placed| Population::resource_capacity(placed.kind)))
+        .sum::<f32>();
+
+    let count = Population::next_count(population.count, capacity);
+    if count != population.count {
+        info!(
+            "Population changed from {} to {} (capacity {:.0})",
+            population.count, count, capacity
+        );
+    }
+    let milestone = count / Population::MILESTONE_STEP;
+    if milestone > population.count / Population::MILESTONE_STEP {
+        let reached = milestone * Population::MILESTONE_STEP;
+        event_log.push(&clock, format!("Population reached {}", reached), None);
+    }
+    population.count = count;
+    population.last_update_day = day;
+
+    if count == 0 && !sandbox.0 {
+        game_over_reason.0 = format!("Your people died out on day {}.", clock.day());
+        next_state.set(GameState::GameOver);
+    }
+}
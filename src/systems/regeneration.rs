@@ -0,0 +1,333 @@
+use bevy::prelude::*;
+use crate::components::camera::{CameraController, CameraZoom, GameCamera};
+use crate::components::confirm_dialog::spawn_confirm_dialog;
+use crate::components::placeable::Placed;
+use crate::components::tile::{Tile, TileChunk};
+use crate::components::ui::{MenuAction, MenuButton, MenuTheme};
+use crate::events::{ButtonActivated, Notification, RegenerateWorld};
+use crate::i18n::Locale;
+use crate::resources::{
+    DebugOverlay, InputAction, KeyBindings, Sandbox, SimulationClock, WorldConfig, WorldGenProgress,
+};
+use crate::save::UnsavedChanges;
+use crate::systems::world_gen::WorldGenerator;
+
+/// Regenerate the world in place with Ctrl and the bound key (R by default), from a random
+/// seed, or the same seed with Shift held too to see the effect of changed generation
+/// parameters
+///
+/// Meant for sandbox worlds and debugging, where it takes effect at once. In a normal world,
+/// with the debug overlay hidden, the player is asked to confirm replacing it first.
+#[allow(clippy::too_many_arguments)]
+pub fn regenerate_world_hotkey(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    config: Res<WorldConfig>,
+    sandbox: Res<Sandbox>,
+    overlay: Res<DebugOverlay>,
+    generator: Option<Res<WorldGenerator>>,
+    mut requests: EventWriter<RegenerateWorld>,
+) {
+    let ctrl_held = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    if !ctrl_held
+        || !key_bindings.just_pressed(&keyboard_input, InputAction::RegenerateWorld)
+        || generator.is_some()
+    {
+        return;
+    }
+
+    let seed = if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        config.seed
+    } else {
+        rand::random()
+    };
+    if sandbox.0 || overlay.visible {
+        requests.send(RegenerateWorld { seed });
+    } else {
+        spawn_confirm_dialog(
+            &mut commands,
+            &theme,
+            &locale,
+            &locale.t("regenerate.confirm"),
+            MenuAction::RegenerateWorld(seed),
+        );
+    }
+}
+
+/// Regenerate the world once replacing it is confirmed in the dialog
+pub fn handle_regeneration_actions(
+    mut activated: EventReader<ButtonActivated>,
+    button_query: Query<&MenuButton>,
+    generator: Option<Res<WorldGenerator>>,
+    mut requests: EventWriter<RegenerateWorld>,
+) {
+    for event in activated.read() {
+        if let Ok(MenuButton {
+            action: MenuAction::RegenerateWorld(seed),
+        }) = button_query.get(event.entity)
+        {
+            if generator.is_none() {
+                requests.send(RegenerateWorld { seed: *seed });
+            }
+        }
+    }
+}
+
+/// Filter for the tiles, chunks and placed entities that make up a generated world
+type GeneratedWorld = Or<(With<Tile>, With<TileChunk>, With<Placed>)>;
+
+/// Clear the world for regeneration: take the new seed, despawn the map and everything placed
+/// on it, and recenter the camera
+///
+/// `start_world_generation` then runs as it does on the loading screen, and also resets the
+/// `WorldBounds`, and the `WorldReset` systems run once it has.
+pub fn begin_world_regeneration(
+    mut commands: Commands,
+    mut requests: EventReader<RegenerateWorld>,
+    mut config: ResMut<WorldConfig>,
+    mut clock: ResMut<SimulationClock>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
+    world_query: Query<Entity, GeneratedWorld>,
+    mut camera_query: Query<(&mut Transform, &mut CameraController, &mut CameraZoom), With<GameCamera>>,
+) {
+    let Some(request) = requests.read().last() else {
+        return;
+    };
+    info!("Regenerating the world in place with seed {}", request.seed);
+    config.seed = request.seed;
+
+    for entity in &world_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    // The new world starts its clock from the beginning, like one made from the menu
+    *clock = SimulationClock {
+        speed: clock.speed,
+        ..default()
+    };
+    // The new world isn't saved anywhere yet, so quitting offers to save it
+    unsaved_changes.0 = true;
+
+    if let Ok((mut camera_transform, mut controller, mut zoom)) = camera_query.get_single_mut() {
+        let center = config.bounds().center();
+        camera_transform.translation.x = center.x;
+        camera_transform.translation.y = center.y;
+        controller.velocity = Vec2::ZERO;
+        controller.drag_velocity = Vec2::ZERO;
+        controller.focus_target = None;
+        controller.reset_view(&mut zoom);
+    }
+}
+
+/// Drop the generator once the regenerated world's last tile has been spawned
+pub fn finish_world_regeneration(
+    mut commands: Commands,
+    config: Res<WorldConfig>,
+    progress: Res<WorldGenProgress>,
+    mut notifications: EventWriter<Notification>,
+) {
+    if progress.is_complete() {
+        info!("World regenerated");
+        notifications.send(Notification::new(format!("World regenerated with seed {}", config.seed)));
+        commands.remove_resource::<WorldGenerator>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::confirm_dialog::ConfirmDialog;
+    use crate::components::placeable::EntityKind;
+    use crate::components::tile::{Biome, Terrain};
+    use crate::resources::{
+        BiomeTable, Population, SimulationSpeed, TerrainPalette, TileRendering, WorldBounds,
+        WorldReset,
+    };
+    use crate::systems::population::reset_population;
+    use crate::systems::world_gen::start_world_generation;
+
+    fn hotkey_test_app(sandbox: bool) -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<MenuTheme>()
+            .init_resource::<Locale>()
+            .init_resource::<WorldConfig>()
+            .init_resource::<DebugOverlay>()
+            .insert_resource(Sandbox(sandbox))
+            .add_event::<RegenerateWorld>()
+            .add_event::<ButtonActivated>()
+            .add_systems(Update, (regenerate_world_hotkey, handle_regeneration_actions).chain());
+        app
+    }
+
+    /// Press Ctrl+Shift and a key, keeping the seed so the request can be checked
+    fn press_with_ctrl_shift(app: &mut App, key: KeyCode) {
+        let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard_input.press(KeyCode::ControlLeft);
+        keyboard_input.press(KeyCode::ShiftLeft);
+        keyboard_input.press(key);
+        app.update();
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().reset_all();
+    }
+
+    fn requested_seeds(app: &App) -> Vec<u64> {
+        let events = app.world().resource::<Events<RegenerateWorld>>();
+        events.get_reader().read(events).map(|request| request.seed).collect()
+    }
+
+    fn dialog_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query_filtered::<(), With<ConfirmDialog>>().iter(world).count()
+    }
+
+    #[test]
+    fn sandbox_worlds_regenerate_at_once() {
+        let mut app = hotkey_test_app(true);
+        let seed = app.world().resource::<WorldConfig>().seed;
+
+        press_with_ctrl_shift(&mut app, KeyCode::KeyR);
+        assert_eq!(requested_seeds(&app), vec![seed]);
+        assert_eq!(dialog_count(&mut app), 0);
+    }
+
+    #[test]
+    fn normal_worlds_regenerate_once_confirmed() {
+        let mut app = hotkey_test_app(false);
+        let seed = app.world().resource::<WorldConfig>().seed;
+
+        press_with_ctrl_shift(&mut app, KeyCode::KeyR);
+        assert!(requested_seeds(&app).is_empty());
+        assert_eq!(dialog_count(&mut app), 1);
+
+        let world = app.world_mut();
+        let confirm = world
+            .query::<(Entity, &MenuButton)>()
+            .iter(world)
+            .find(|(_, button)| button.action == MenuAction::RegenerateWorld(seed))
+            .map(|(entity, _)| entity)
+            .unwrap();
+        world.send_event(ButtonActivated { entity: confirm });
+        app.update();
+        assert_eq!(requested_seeds(&app), vec![seed]);
+    }
+
+    #[test]
+    fn regenerate_key_can_be_rebound() {
+        let mut app = hotkey_test_app(true);
+        app.world_mut()
+            .resource_mut::<KeyBindings>()
+            .bind(InputAction::RegenerateWorld, KeyCode::F5)
+            .unwrap();
+
+        press_with_ctrl_shift(&mut app, KeyCode::KeyR);
+        assert!(requested_seeds(&app).is_empty());
+
+        press_with_ctrl_shift(&mut app, KeyCode::F5);
+        assert_eq!(requested_seeds(&app).len(), 1);
+    }
+
+    #[test]
+    fn regeneration_clears_the_world_and_resets_it_once() {
+        let config = WorldConfig {
+            width: 40,
+            height: 24,
+            ..default()
+        };
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(config)
+            .insert_resource(SimulationClock {
+                elapsed_hours: 100.0,
+                speed: SimulationSpeed::Fast,
+            })
+            .insert_resource(Population {
+                count: 3,
+                ..default()
+            })
+            .init_resource::<UnsavedChanges>()
+            .init_resource::<WorldBounds>()
+            .init_resource::<BiomeTable>()
+            .init_resource::<TerrainPalette>()
+            .init_resource::<TileRendering>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .add_event::<RegenerateWorld>()
+            // Set up as the loading and regeneration plugins do
+            .configure_sets(Update, WorldReset.run_if(resource_added::<WorldGenerator>))
+            .add_systems(
+                Update,
+                (
+                    (begin_world_regeneration, start_world_generation)
+                        .chain()
+                        .before(WorldReset)
+                        .run_if(on_event::<RegenerateWorld>()),
+                    reset_population.in_set(WorldReset),
+                ),
+            );
+
+        let world = app.world_mut();
+        let tile = Tile {
+            coord: UVec2::ZERO,
+            terrain: Terrain::Water,
+            biome: Biome::Grassland,
+        };
+        let map = [
+            world.spawn(tile).id(),
+            world.spawn(TileChunk { origin: UVec2::ZERO }).id(),
+            world
+                .spawn(Placed {
+                    kind: EntityKind::Tree,
+                    coord: UVec2::ZERO,
+                })
+                .id(),
+        ];
+        let camera = world
+            .spawn((
+                Transform::from_xyz(500.0, -300.0, 0.0),
+                CameraController {
+                    velocity: Vec2::new(40.0, 0.0),
+                    drag_velocity: Vec2::ONE,
+                    focus_target: Some(Vec2::ONE),
+                    ..default()
+                },
+                CameraZoom {
+                    target_scale: 3.0,
+                    ..default()
+                },
+                GameCamera,
+            ))
+            .id();
+        world.send_event(RegenerateWorld { seed: 99 });
+        app.update();
+
+        let world = app.world();
+        let config = world.resource::<WorldConfig>();
+        assert_eq!(config.seed, 99);
+        assert!(map.iter().all(|entity| world.get_entity(*entity).is_none()));
+        assert_eq!(world.resource::<SimulationClock>().elapsed_hours, 0.0);
+        assert_eq!(world.resource::<SimulationClock>().speed, SimulationSpeed::Fast);
+        assert_eq!(*world.resource::<UnsavedChanges>(), UnsavedChanges(true));
+        assert_eq!(*world.resource::<WorldBounds>(), config.bounds());
+        assert!(world.contains_resource::<WorldGenerator>());
+        assert_eq!(*world.resource::<Population>(), Population::default());
+
+        let center = config.bounds().center();
+        let controller = world.get::<CameraController>(camera).unwrap();
+        assert_eq!(world.get::<Transform>(camera).unwrap().translation.truncate(), center);
+        assert_eq!(controller.velocity, Vec2::ZERO);
+        assert_eq!(controller.drag_velocity, Vec2::ZERO);
+        assert_eq!(controller.focus_target, None);
+        assert_eq!(
+            world.get::<CameraZoom>(camera).unwrap().target_scale,
+            controller.default_scale
+        );
+
+        // The resets run once for the new world, not again while it generates
+        app.world_mut().resource_mut::<Population>().count = 7;
+        app.update();
+        assert_eq!(app.world().resource::<Population>().count, 7);
+    }
+}
@@ -0,0 +1,357 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use crate::components::{CameraZoom, Health, Placed, Tile};
+use crate::events::{AfterSave, LoadGameRequested, Notification, SaveGameRequested};
+use crate::resources::{
+    BiomeTable, DivinePower, GameState, PlacedAssets, Population, Resources, Rng, Sandbox,
+    SimulationClock, TerrainMaterials, TileEntities, WorldConfig,
+};
+use crate::save::*;
+use crate::systems::placement::spawn_placed;
+use crate::systems::world_gen::WorldMap;
+
+/// Read access to everything that goes into a save
+#[derive(SystemParam)]
+pub struct SaveSnapshot<'w, 's> {
+    camera_query: Query<'w, 's, (&'static Transform, &'static OrthographicProjection), With<CameraZoom>>,
+    clock: Res<'w, SimulationClock>,
+    population: Res<'w, Population>,
+    resources: Res<'w, Resources>,
+    rng: Res<'w, Rng>,
+    sandbox: Res<'w, Sandbox>,
+    world_config: Res<'w, WorldConfig>,
+    placed_query: Query<'w, 's, (&'static Placed, &'static Health)>,
+    tile_query: Query<'w, 's, &'static Tile>,
+    biome_table: Res<'w, BiomeTable>,
+}
+
+impl SaveSnapshot<'_, '_> {
+    /// Capture the current game state under a save name, or `None` when no game world is loaded
+    pub fn save_data(&self, name: &str) -> Option<SaveData> {
+        let (camera_transform, projection) = self.camera_query.get_single().ok()?;
+
+        Some(SaveData {
+            version: SAVE_VERSION,
+            header: Some(SaveHeader::now(name, self.world_config.seed)),
+            world_config: self.world_config.clone(),
+            camera: CameraSave {
+                translation: camera_transform.translation.truncate().to_array(),
+                scale: projection.scale,
+            },
+            simulation_hours: self.clock.elapsed_hours,
+            population: Some(self.population.count),
+            resources: Some(self.resources.clone()),
+            rng_word_pos: Some(self.rng.word_pos()),
+            sandbox: self.sandbox.0,
+            placed: Some(self.placed()),
+            terrain_changes: Some(self.terrain_changes()),
+        })
+    }
+
+    /// Every placed entity, in tile order so unchanged worlds save identically
+    fn placed(&self) -> Vec<PlacedSave> {
+        let mut placed: Vec<PlacedSave> = self
+            .placed_query
+            .iter()
+            .map(|(placed, health)| PlacedSave {
+                kind: placed.kind,
+                coord: placed.coord.to_array(),
+                health: health.0,
+            })
+            .collect();
+        placed.sort_by_key(|placed| (placed.coord[1], placed.coord[0]));
+        placed
+    }
+
+    /// Tiles whose terrain differs from the map the world config generates
+    ///
+    /// The map is regenerated to compare against, since only the config is saved for the rest.
+    fn terrain_changes(&self) -> Vec<TerrainSave> {
+        let generated = WorldMap::generate(&self.world_config, &self.biome_table);
+        let mut changes: Vec<TerrainSave> = self
+            .tile_query
+            .iter()
+            .filter(|tile| generated.tile(tile.coord).map(|original| original.terrain) != Some(tile.terrain))
+            .map(|tile| TerrainSave {
+                coord: tile.coord.to_array(),
+                terrain: tile.terrain,
+            })
+            .collect();
+        changes.sort_by_key(|change| (change.coord[1], change.coord[0]));
+        changes
+    }
+}
+
+/// Write the current game state to the requested save file, or the default one
+///
+/// Requests to quit or return to the menu afterwards only leave once the save succeeded, so a
+/// failed write leaves the player in the game.
+pub fn handle_save_requests(
+    mut save_requests: EventReader<SaveGameRequested>,
+    snapshot: SaveSnapshot,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
+    mut notifications: EventWriter<Notification>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    // Several requests in one frame still only need a single write
+    let mut requested = false;
+    let mut after = AfterSave::Stay;
+    let mut name = None;
+    for request in save_requests.read() {
+        requested = true;
+        after = after.max(request.after);
+        if request.name.is_some() {
+            name = request.name.clone();
+        }
+    }
+    if !requested {
+        return;
+    }
+    let name = name.unwrap_or_else(|| DEFAULT_SAVE_NAME.to_string());
+
+    let Some(save_data) = snapshot.save_data(&name) else {
+        warn!("Cannot save: no game world is loaded");
+        return;
+    };
+
+    let path = save_path(&sanitize_save_name(&name));
+    match save_game(&save_data, &path) {
+        Ok(()) => {
+            info!("Game saved to {}", path.display());
+            notifications.send(Notification::new(format!("Game saved as \"{}\"", name)));
+            unsaved_changes.0 = false;
+            match after {
+                AfterSave::Stay => {}
+                AfterSave::ReturnToMenu => next_state.set(GameState::MainMenu),
+                AfterSave::Quit => {
+                    exit.send(AppExit::Success);
+                }
+            }
+        }
+        Err(error) => {
+            error!("Failed to save game to {}: {}", path.display(), error);
+            notifications.send(Notification::new("Failed to save the game"));
+        }
+    }
+}
+
+/// A new or freshly loaded world starts out matching its save
+pub fn reset_unsaved_changes(mut unsaved_changes: ResMut<UnsavedChanges>) {
+    unsaved_changes.0 = false;
+}
+
+/// Read the requested save file and enter the game
+///
+/// A missing or corrupt save leaves the player where they are.
+pub fn handle_load_requests(
+    mut commands: Commands,
+    mut load_requests: EventReader<LoadGameRequested>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut notifications: EventWriter<Notification>,
+) {
+    // Only the latest request matters if several arrive in one frame
+    let Some(request) = load_requests.read().last() else {
+        return;
+    };
+
+    let path = &request.path;
+    match load_game(path) {
+        Ok(save_data) => {
+            info!("Loaded save from {}", path.display());
+            // The world is regenerated from its config before the rest of the save is applied,
+            // and in its saved mode, which decides the mana pool it starts with
+            commands.insert_resource(save_data.world_config.clone());
+            commands.insert_resource(Sandbox(save_data.sandbox));
+            commands.insert_resource(PendingLoad(save_data));
+            next_state.set(GameState::Loading);
+        }
+        Err(error) => {
+            error!("Failed to load save from {}: {}", path.display(), error);
+            let message = match error {
+                SaveError::Version { .. } => "This save is from a newer version of the game",
+                SaveError::NotFound(_) => "This save no longer exists",
+                SaveError::Serialization(_) => "This save is damaged and can't be loaded",
+                SaveError::Io(_) => "No save could be loaded",
+            };
+            notifications.send(Notification::new(message));
+        }
+    }
+}
+
+/// Forget a loaded save that never reached the game, such as when loading was cancelled
+///
+/// Otherwise it would be applied to the next new world instead.
+pub fn discard_pending_load(mut commands: Commands) {
+    commands.remove_resource::<PendingLoad>();
+}
+
+/// Apply loaded save data to the freshly set up game world
+#[allow(clippy::too_many_arguments)]
+pub fn apply_pending_load(
+    mut commands: Commands,
+    pending_load: Option<Res<PendingLoad>>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection, &mut CameraZoom)>,
+    mut clock: ResMut<SimulationClock>,
+    mut population: ResMut<Population>,
+    mut resources: ResMut<Resources>,
+    mut divine_power: ResMut<DivinePower>,
+    mut rng: ResMut<Rng>,
+    placed_assets: Res<PlacedAssets>,
+    config: Res<WorldConfig>,
+    tile_entities: Res<TileEntities>,
+    terrain_materials: Res<TerrainMaterials>,
+    mut tile_query: Query<(&mut Tile, Option<&mut Handle<ColorMaterial>>)>,
+) {
+    let Some(pending_load) = pending_load else {
+        return;
+    };
+    let save_data = &pending_load.0;
+
+    if let Ok((mut camera_transform, mut projection, mut zoom)) = camera_query.get_single_mut() {
+        let translation = Vec2::from_array(save_data.camera.translation);
+        camera_transform.translation.x = translation.x;
+        camera_transform.translation.y = translation.y;
+        projection.scale = save_data.camera.scale;
+        zoom.target_scale = save_data.camera.scale;
+    }
+    clock.elapsed_hours = save_data.simulation_hours;
+    // Days already lived through in the save shouldn't be simulated again
+    population.last_update_day = clock.elapsed_days();
+    if let Some(count) = save_data.population {
+        population.count = count;
+    }
+    if let Some(saved_resources) = &save_data.resources {
+        *resources = saved_resources.clone();
+    }
+    resources.last_update_day = population.last_update_day;
+    // Mana only regenerates from the loaded time onwards
+    divine_power.last_update_hour = clock.elapsed_hours;
+    // Pick up the random stream where the save left it
+    if let Some(word_pos) = save_data.rng_word_pos {
+        rng.set_word_pos(word_pos);
+    }
+    // The map was regenerated from its config, so only the tiles changed since then need setting
+    for change in save_data.terrain_changes.iter().flatten() {
+        let Some(entity) = tile_entities.get(UVec2::from_array(change.coord)) else {
+            continue;
+        };
+        if let Ok((mut tile, material)) = tile_query.get_mut(entity) {
+            tile.terrain = change.terrain;
+            // Batched tiles have no material of their own, their chunk is redrawn instead
+            if let Some(mut material) = material {
+                *material = terrain_materials.get(change.terrain, tile.biome);
+            }
+        }
+    }
+    for placed in save_data.placed.iter().flatten() {
+        let entity = spawn_placed(
+            &mut commands,
+            &placed_assets,
+            &config,
+            placed.kind,
+            UVec2::from_array(placed.coord),
+        );
+        commands.entity(entity).insert(Health(placed.health));
+    }
+
+    commands.remove_resource::<PendingLoad>();
+    info!("Save data applied");
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::components::{EntityKind, Terrain};
+
+    /// App holding a freshly generated world and its camera, as the loading screen leaves it
+    fn world_test_app(config: &WorldConfig) -> App {
+        let mut app = App::new();
+        app.insert_resource(config.clone())
+            .init_resource::<BiomeTable>()
+            .init_resource::<SimulationClock>()
+            .init_resource::<Population>()
+            .init_resource::<Resources>()
+            .init_resource::<DivinePower>()
+            .init_resource::<Rng>()
+            .init_resource::<Sandbox>()
+            .init_resource::<TerrainMaterials>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .init_resource::<PlacedAssets>();
+
+        let world = app.world_mut();
+        world.spawn((Transform::default(), OrthographicProjection::default(), CameraZoom::default()));
+        // Tiles are spawned without materials, as batched tiles are
+        let map = WorldMap::generate(config, &BiomeTable::default());
+        let tile_entities = map
+            .tiles
+            .into_iter()
+            .map(|tile| (tile.coord, world.spawn(tile).id()))
+            .collect();
+        world.insert_resource(TileEntities(tile_entities));
+        app
+    }
+
+    fn snapshot(snapshot: SaveSnapshot) -> Option<SaveData> {
+        snapshot.save_data("Round trip")
+    }
+
+    fn terrain_at(app: &mut App, coord: UVec2) -> Terrain {
+        let entity = app.world().resource::<TileEntities>().get(coord).unwrap();
+        app.world().get::<Tile>(entity).unwrap().terrain
+    }
+
+    #[test]
+    fn placed_entities_and_terrain_changes_survive_saving_and_loading() {
+        let config = WorldConfig {
+            seed: 11,
+            width: 16,
+            height: 12,
+            ..default()
+        };
+        let edited_coord = UVec2::new(3, 4);
+
+        let mut app = world_test_app(&config);
+        let edited = match terrain_at(&mut app, edited_coord) {
+            Terrain::Mountain => Terrain::Sand,
+            _ => Terrain::Mountain,
+        };
+        let world = app.world_mut();
+        let entity = world.resource::<TileEntities>().get(edited_coord).unwrap();
+        world.get_mut::<Tile>(entity).unwrap().terrain = edited;
+        world.spawn((Placed { kind: EntityKind::Tree, coord: UVec2::new(1, 2) }, Health(40.0)));
+        world.spawn((Placed { kind: EntityKind::Villager, coord: UVec2::new(5, 0) }, Health::default()));
+
+        let save_data = world.run_system_once(snapshot).expect("the world has a camera");
+        let json = serde_json::to_string(&save_data).unwrap();
+        let loaded = parse_save(&json).unwrap();
+        // Only the edited tile differs from the regenerated map
+        assert_eq!(
+            loaded.terrain_changes,
+            Some(vec![TerrainSave { coord: edited_coord.to_array(), terrain: edited }])
+        );
+
+        let mut fresh = world_test_app(&config);
+        fresh.insert_resource(PendingLoad(loaded));
+        fresh.world_mut().run_system_once(apply_pending_load);
+
+        assert_eq!(terrain_at(&mut fresh, edited_coord), edited);
+        let world = fresh.world_mut();
+        let mut placed: Vec<(EntityKind, UVec2, f32)> = world
+            .query::<(&Placed, &Health)>()
+            .iter(world)
+            .map(|(placed, health)| (placed.kind, placed.coord, health.0))
+            .collect();
+        placed.sort_by_key(|(_, coord, _)| (coord.y, coord.x));
+        assert_eq!(
+            placed,
+            vec![
+                (EntityKind::Villager, UVec2::new(5, 0), Health::MAX),
+                (EntityKind::Tree, UVec2::new(1, 2), 40.0),
+            ]
+        );
+    }
+}
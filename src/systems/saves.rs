@@ -0,0 +1,329 @@
+use std::io;
+use std::path::Path;
+
+use bevy::prelude::*;
+use crate::components::camera::UiCamera;
+use crate::components::confirm_dialog::spawn_confirm_dialog;
+use crate::components::input_field::{spawn_text_input, TextInput, TextInputSubmitted};
+use crate::components::ui::*;
+use crate::events::{ButtonActivated, LoadGameRequested, Notification};
+use crate::i18n::Locale;
+use crate::save::{delete_save, list_saves, rename_save, unix_timestamp, SaveError, SaveSlot, SaveSlots};
+use crate::systems::menu::{create_menu_button, create_sized_button};
+
+/// Longest name a save can be given on the saves screen
+const MAX_NAME_LENGTH: usize = 32;
+
+/// Read the save headers from disk when the saves screen opens
+pub fn refresh_save_slots(mut slots: ResMut<SaveSlots>) {
+    slots.0 = list_saves();
+}
+
+/// Setup the saves screen; the slot rows are filled in by `update_save_list`
+pub fn setup_saves_screen(mut commands: Commands, theme: Res<MenuTheme>, locale: Res<Locale>) {
+    info!("Setting up saves screen");
+
+    // Spawn a camera for UI rendering
+    commands.spawn((Camera2dBundle::default(), UiCamera, SavesScreen));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: theme.background_color.into(),
+                ..default()
+            },
+            SavesScreen,
+            ThemeColor::Background,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                TextBundle::from_section(
+                    "Saves",
+                    TextStyle {
+                        font_size: theme.title_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Title,
+            ));
+
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                SaveList,
+            ));
+
+            create_menu_button(parent, &theme, &locale, "Back", MenuAction::Back);
+        });
+}
+
+/// Rebuild the save slot rows from the current `SaveSlots`
+pub fn update_save_list(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    slots: Res<SaveSlots>,
+    list_query: Query<Entity, With<SaveList>>,
+) {
+    let Ok(list) = list_query.get_single() else {
+        return;
+    };
+    let now = unix_timestamp();
+
+    commands.entity(list).despawn_descendants().with_children(|parent| {
+        if slots.0.is_empty() {
+            parent.spawn((
+                TextBundle::from_section(
+                    "No saved worlds yet",
+                    TextStyle {
+                        font_size: theme.button_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Title,
+            ));
+        }
+
+        for slot in &slots.0 {
+            spawn_save_row(parent, &theme, slot, now);
+        }
+    });
+}
+
+/// One save slot: an editable name, its seed and age, and its Load/Rename/Delete buttons
+fn spawn_save_row(parent: &mut ChildBuilder, theme: &MenuTheme, slot: &SaveSlot, now: u64) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|row| {
+            spawn_text_input(
+                row,
+                theme,
+                TextInput::new("Save name", MAX_NAME_LENGTH)
+                    .with_value(slot.name.clone())
+                    .with_submit(),
+                Val::Px(280.0),
+                SaveNameField {
+                    path: slot.path.clone(),
+                },
+            );
+
+            row.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(220.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|details| {
+                details.spawn(TextBundle::from_section(
+                    format!(
+                        "Seed {}\nSaved {}",
+                        slot.header.seed,
+                        describe_age(now.saturating_sub(slot.header.saved_at))
+                    ),
+                    TextStyle {
+                        font_size: theme.font_size(20.0),
+                        color: Color::srgb(0.8, 0.8, 0.8),
+                        ..default()
+                    },
+                ));
+            });
+
+            for (label, action) in [
+                ("Load", SavesAction::Load(slot.path.clone())),
+                ("Rename", SavesAction::Rename(slot.path.clone())),
+                ("Delete", SavesAction::Delete(slot.path.clone())),
+            ] {
+                create_sized_button(
+                    row,
+                    theme,
+                    label,
+                    Val::Px(120.0),
+                    Val::Px(50.0),
+                    SavesButton::new(action),
+                );
+            }
+        });
+}
+
+/// Handle activated saves screen buttons, and deletions confirmed in the dialog
+#[allow(clippy::too_many_arguments)]
+pub fn handle_saves_actions(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    mut activated: EventReader<ButtonActivated>,
+    saves_button_query: Query<&SavesButton>,
+    menu_button_query: Query<&MenuButton>,
+    name_query: Query<(&SaveNameField, &TextInput)>,
+    mut slots: ResMut<SaveSlots>,
+    mut load_requests: EventWriter<LoadGameRequested>,
+    mut notifications: EventWriter<Notification>,
+) {
+    for event in activated.read() {
+        if let Ok(MenuButton {
+            action: MenuAction::DeleteSave(path),
+        }) = menu_button_query.get(event.entity)
+        {
+            match delete_save(path) {
+                Ok(()) => {
+                    info!("Deleted save {}", path.display());
+                    notifications.send(Notification::new("Save deleted"));
+                }
+                Err(error) => {
+                    error!("Failed to delete save {}: {}", path.display(), error);
+                    notifications.send(Notification::new("Failed to delete the save"));
+                }
+            }
+            slots.0 = list_saves();
+            continue;
+        }
+
+        let Ok(saves_button) = saves_button_query.get(event.entity) else {
+            continue;
+        };
+
+        match &saves_button.action {
+            SavesAction::Load(path) => {
+                load_requests.send(LoadGameRequested { path: path.clone() });
+            }
+            SavesAction::Rename(path) => {
+                let Some((_, name_input)) = name_query.iter().find(|(field, _)| field.path == *path) else {
+                    continue;
+                };
+                rename_slot(path, &name_input.value, &mut slots, &mut notifications);
+            }
+            SavesAction::Delete(path) => {
+                let name = slots
+                    .0
+                    .iter()
+                    .find(|slot| slot.path == *path)
+                    .map(|slot| slot.name.clone())
+                    .unwrap_or_default();
+                spawn_confirm_dialog(
+                    &mut commands,
+                    &theme,
+                    &locale,
+                    &locale.t("saves.delete_confirm").replace("{name}", &name),
+                    MenuAction::DeleteSave(path.clone()),
+                );
+            }
+        }
+    }
+}
+
+/// Rename a save when Enter is pressed in its name field
+pub fn handle_save_name_submit(
+    mut submitted: EventReader<TextInputSubmitted>,
+    field_query: Query<&SaveNameField>,
+    mut slots: ResMut<SaveSlots>,
+    mut notifications: EventWriter<Notification>,
+) {
+    for event in submitted.read() {
+        if let Ok(field) = field_query.get(event.entity) {
+            rename_slot(&field.path, &event.value, &mut slots, &mut notifications);
+        }
+    }
+}
+
+/// Rename a save file and relist the saves, reporting the result with a notification
+fn rename_slot(
+    path: &Path,
+    new_name: &str,
+    slots: &mut SaveSlots,
+    notifications: &mut EventWriter<Notification>,
+) {
+    match rename_save(path, new_name) {
+        Ok(new_path) => {
+            info!("Renamed save {} to {}", path.display(), new_path.display());
+            notifications.send(Notification::new("Save renamed"));
+        }
+        Err(error) => {
+            warn!("Failed to rename save {}: {}", path.display(), error);
+            let message = match &error {
+                SaveError::Io(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                    "A save with that name already exists"
+                }
+                SaveError::Io(error) if error.kind() == io::ErrorKind::InvalidInput => {
+                    "Save names need at least one letter or number"
+                }
+                _ => "Failed to rename the save",
+            };
+            notifications.send(Notification::new(message));
+        }
+    }
+    // Relisting also puts a rejected name back in its field
+    slots.0 = list_saves();
+}
+
+/// Cleanup the saves screen and its UI camera
+pub fn cleanup_saves_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<SavesScreen>>,
+) {
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    info!("Saves screen cleaned up");
+}
+
+/// How long ago something happened, e.g. "5 minutes ago"
+fn describe_age(seconds: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    let (count, unit) = if seconds < MINUTE {
+        return "just now".to_string();
+    } else if seconds < HOUR {
+        (seconds / MINUTE, "minute")
+    } else if seconds < DAY {
+        (seconds / HOUR, "hour")
+    } else {
+        (seconds / DAY, "day")
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{} ago", count, unit, plural)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ages_are_described_in_their_largest_whole_unit() {
+        assert_eq!(describe_age(0), "just now");
+        assert_eq!(describe_age(59), "just now");
+        assert_eq!(describe_age(60), "1 minute ago");
+        assert_eq!(describe_age(5 * 60 + 30), "5 minutes ago");
+        assert_eq!(describe_age(60 * 60), "1 hour ago");
+        assert_eq!(describe_age(23 * 60 * 60), "23 hours ago");
+        assert_eq!(describe_age(3 * 24 * 60 * 60), "3 days ago");
+    }
+}
@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
+use crate::events::Notification;
+use crate::resources::{InputAction, KeyBindings, ScreenshotResult, Screenshots};
+
+/// Filter for the roots of UI trees, which hide the rest of their tree with them
+type RootUi = (With<Node>, Without<Parent>);
+
+/// Capture the window to a timestamped PNG in the screenshots directory with the screenshot
+/// key, F12 by default
+///
+/// Holding Shift leaves the UI out of the shot. The file is written once the frame has been
+/// rendered, and `report_screenshots` shows where it went.
+pub fn take_screenshot(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    window_query: Query<Entity, With<PrimaryWindow>>,
+    mut ui_query: Query<(Entity, &mut Visibility), RootUi>,
+    mut screenshot_manager: ResMut<ScreenshotManager>,
+    mut screenshots: ResMut<Screenshots>,
+    mut notifications: EventWriter<Notification>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, InputAction::Screenshot) {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    let directory = Path::new(Screenshots::DIRECTORY);
+    if let Err(error) = fs::create_dir_all(directory) {
+        error!("Failed to create {}: {}", directory.display(), error);
+        notifications.send(Notification::new("Failed to save the screenshot"));
+        return;
+    }
+    let path = directory.join(screenshot_name(SystemTime::now()));
+
+    let sender = screenshots.sender.clone();
+    let target = path.clone();
+    let capture = screenshot_manager.take_screenshot(window, move |image| {
+        let result = image
+            .try_into_dynamic()
+            .map_err(|error| error.to_string())
+            // Drop the alpha channel, which holds brightness rather than opacity with HDR
+            .and_then(|image| image.to_rgb8().save(&target).map_err(|error| error.to_string()))
+            .map(|()| target);
+        // The receiver only goes away with the app
+        let _ = sender.send(result);
+    });
+    if capture.is_err() {
+        warn!("A screenshot is already being taken");
+        return;
+    }
+
+    // The UI hidden now is left out of the frame being captured
+    if keyboard_input.any_pressed(Screenshots::HIDE_UI_MODIFIERS) {
+        for (entity, mut visibility) in &mut ui_query {
+            screenshots.hidden_ui.push((entity, *visibility));
+            *visibility = Visibility::Hidden;
+        }
+    }
+    info!("Taking screenshot {}", path.display());
+}
+
+/// Restore any hidden UI and confirm where each finished screenshot was saved
+pub fn report_screenshots(
+    mut screenshots: ResMut<Screenshots>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let results: Vec<ScreenshotResult> = match screenshots.receiver.lock() {
+        Ok(receiver) => receiver.try_iter().collect(),
+        Err(_) => return,
+    };
+    if results.is_empty() {
+        return;
+    }
+
+    for (entity, visibility) in screenshots.hidden_ui.drain(..) {
+        if let Ok(mut current) = visibility_query.get_mut(entity) {
+            *current = visibility;
+        }
+    }
+
+    for result in results {
+        match result {
+            Ok(path) => {
+                info!("Screenshot saved to {}", path.display());
+                notifications.send(Notification::new(format!("Screenshot saved to {}", path.display())));
+            }
+            Err(error) => {
+                error!("Failed to save screenshot: {}", error);
+                notifications.send(Notification::new("Failed to save the screenshot"));
+            }
+        }
+    }
+}
+
+/// File name for a screenshot taken at a given time, in milliseconds since the Unix epoch so
+/// names sort in the order they were taken
+fn screenshot_name(time: SystemTime) -> PathBuf {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis());
+    PathBuf::from(format!("worldkeeper-{}.png", millis))
+}
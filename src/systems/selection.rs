@@ -0,0 +1,310 @@
+use bevy::prelude::*;
+use bevy::math::Rot2;
+use bevy::window::PrimaryWindow;
+use crate::components::camera::GameCamera;
+use crate::components::placeable::Placed;
+use crate::components::tile::Tile;
+use crate::components::ui::MapBlockingUi;
+use crate::resources::{
+    HoveredTile, SelectedTile, SelectedTool, Selection, SelectionDrag, TerrainBrush, WorldConfig,
+    TILE_SIZE,
+};
+use crate::utils::cursor_to_world;
+
+/// Outline color of the selected tile and selected entities
+const HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.95, 0.3);
+/// Faint outline of the tile under the cursor, so it never competes with the selection
+const HOVER_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.35);
+/// Color of the rectangle drawn while drag-selecting
+const SELECTION_BOX_COLOR: Color = Color::srgba(1.0, 0.95, 0.3, 0.6);
+
+/// Track the tile under the cursor
+///
+/// Looks the tile up from the grid rather than searching the tile entities, so it stays cheap
+/// on large maps. Only writes the resource when the hovered tile changes.
+pub fn hover_tile(
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    ui_query: Query<&Interaction, MapBlockingUi>,
+    mut hovered_tile: ResMut<HoveredTile>,
+) {
+    // In-game UI under the cursor hides the map beneath it
+    let over_ui = ui_query.iter().any(|interaction| *interaction != Interaction::None);
+
+    let hovered = window_query
+        .get_single()
+        .ok()
+        .filter(|_| !over_ui)
+        .zip(camera_query.get_single().ok())
+        .and_then(|(window, (camera_transform, projection))| {
+            cursor_to_world(window, projection, camera_transform)
+        })
+        .and_then(|position| config.tile_at(position));
+
+    if hovered_tile.0 != hovered {
+        hovered_tile.0 = hovered;
+    }
+}
+
+/// Start a click or drag-select when the map is left-pressed
+///
+/// Only runs while no toolbar or terrain tool is selected, since clicks then place or delete
+/// entities or reshape the land instead.
+pub fn start_selection_drag(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    selected_tool: Res<SelectedTool>,
+    brush: Res<TerrainBrush>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    toolbar_query: Query<&Interaction, MapBlockingUi>,
+    mut drag: ResMut<SelectionDrag>,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Left)
+        || selected_tool.0.is_some()
+        || brush.tool.is_some()
+    {
+        return;
+    }
+
+    // Clicks on the toolbars and minimap don't change the selection
+    if toolbar_query.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let (Some(cursor), Some(world_position)) = (
+        window.cursor_position(),
+        cursor_to_world(window, projection, camera_transform),
+    ) else {
+        return;
+    };
+
+    *drag = SelectionDrag {
+        start_screen: Some(cursor),
+        start_world: world_position,
+    };
+}
+
+/// Finish a press on the map when the left button is released
+///
+/// A release close to where the press started selects the tile under the cursor, or clears
+/// the selection off the map. A longer drag selects every placed entity inside the box.
+#[allow(clippy::too_many_arguments)]
+pub fn select_tile(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    tile_query: Query<(Entity, &Tile)>,
+    placed_query: Query<(Entity, &Placed)>,
+    mut drag: ResMut<SelectionDrag>,
+    mut selected_tile: ResMut<SelectedTile>,
+    mut selection: ResMut<Selection>,
+) {
+    if !mouse_buttons.just_released(MouseButton::Left) || drag.start_screen.is_none() {
+        return;
+    }
+    let press = std::mem::take(&mut *drag);
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let (Some(cursor), Some(world_position)) = (
+        window.cursor_position(),
+        cursor_to_world(window, projection, camera_transform),
+    ) else {
+        return;
+    };
+
+    if press.is_dragging(cursor) {
+        let area = Rect::from_corners(press.start_world, world_position);
+        selection.0 = placed_query
+            .iter()
+            .filter(|(_, placed)| area.contains(config.tile_center(placed.coord)))
+            .map(|(entity, _)| entity)
+            .collect();
+        info!("Selected {} entities", selection.0.len());
+        return;
+    }
+
+    let clicked = config.tile_at(world_position).and_then(|coord| {
+        tile_query
+            .iter()
+            .find(|(_, tile)| tile.coord == coord)
+            .map(|(entity, _)| entity)
+    });
+
+    if selected_tile.0 != clicked {
+        selected_tile.0 = clicked;
+    }
+    if !selection.0.is_empty() {
+        selection.0.clear();
+    }
+}
+
+/// Clear the selection with Escape
+///
+/// Runs before the pause toggle and consumes the key, so the first Escape press clears the
+/// selection and only the next one pauses.
+pub fn clear_selection_on_escape(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut selected_tile: ResMut<SelectedTile>,
+    mut selection: ResMut<Selection>,
+) {
+    let has_selection = selected_tile.0.is_some() || !selection.0.is_empty();
+    if has_selection && keyboard_input.just_pressed(KeyCode::Escape) {
+        keyboard_input.clear_just_pressed(KeyCode::Escape);
+        selected_tile.0 = None;
+        selection.0.clear();
+    }
+}
+
+/// Forget the selection and hovered tile when a new world is loaded
+pub fn reset_selection(
+    mut selected_tile: ResMut<SelectedTile>,
+    mut hovered_tile: ResMut<HoveredTile>,
+    mut selection: ResMut<Selection>,
+    mut drag: ResMut<SelectionDrag>,
+) {
+    selected_tile.0 = None;
+    hovered_tile.0 = None;
+    selection.0.clear();
+    *drag = SelectionDrag::default();
+}
+
+/// Outline the selected tile and circle the selected entities
+///
+/// Drawn with gizmos in world space, so the outline stays on its tile as the camera pans and
+/// zooms.
+pub fn draw_selection_highlight(
+    mut gizmos: Gizmos,
+    selected_tile: Res<SelectedTile>,
+    selection: Res<Selection>,
+    config: Res<WorldConfig>,
+    tile_query: Query<&Tile>,
+    placed_query: Query<&Placed>,
+) {
+    if let Some(tile) = selected_tile.0.and_then(|entity| tile_query.get(entity).ok()) {
+        gizmos.rect_2d(
+            config.tile_center(tile.coord),
+            Rot2::IDENTITY,
+            Vec2::splat(TILE_SIZE),
+            HIGHLIGHT_COLOR,
+        );
+    }
+
+    // Undone placements may have despawned selected entities
+    for placed in selection.0.iter().filter_map(|entity| placed_query.get(*entity).ok()) {
+        gizmos.circle_2d(config.tile_center(placed.coord), TILE_SIZE * 0.45, HIGHLIGHT_COLOR);
+    }
+}
+
+/// Faintly outline the tile under the cursor, unless it is already outlined as selected
+pub fn draw_hover_highlight(
+    mut gizmos: Gizmos,
+    hovered_tile: Res<HoveredTile>,
+    selected_tile: Res<SelectedTile>,
+    config: Res<WorldConfig>,
+    tile_query: Query<&Tile>,
+) {
+    let Some(coord) = hovered_tile.0 else {
+        return;
+    };
+    let selected = selected_tile.0.and_then(|entity| tile_query.get(entity).ok());
+    if selected.is_some_and(|tile| tile.coord == coord) {
+        return;
+    }
+
+    gizmos.rect_2d(config.tile_center(coord), Rot2::IDENTITY, Vec2::splat(TILE_SIZE), HOVER_COLOR);
+}
+
+/// Draw the selection box while the left button is dragged across the map
+pub fn draw_selection_box(
+    mut gizmos: Gizmos,
+    drag: Res<SelectionDrag>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    if !drag.is_dragging(cursor) {
+        return;
+    }
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(world_position) = cursor_to_world(window, projection, camera_transform) else {
+        return;
+    };
+
+    let area = Rect::from_corners(drag.start_world, world_position);
+    gizmos.rect_2d(area.center(), Rot2::IDENTITY, area.size(), SELECTION_BOX_COLOR);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::ui::ToolbarButton;
+    use crate::resources::PlacementTool;
+
+    /// App tracking the hovered tile with the cursor in the middle of the window, over a tile
+    fn hover_test_app(cursor_coord: UVec2) -> App {
+        let mut app = App::new();
+        app.init_resource::<WorldConfig>()
+            .init_resource::<HoveredTile>()
+            .add_systems(Update, hover_tile);
+
+        let mut window = Window::default();
+        window.set_cursor_position(Some(Vec2::new(window.width(), window.height()) / 2.0));
+        app.world_mut().spawn((window, PrimaryWindow));
+
+        let center = app.world().resource::<WorldConfig>().tile_center(cursor_coord);
+        app.world_mut().spawn((
+            Transform::from_translation(center.extend(0.0)),
+            OrthographicProjection::default(),
+            GameCamera,
+        ));
+        app
+    }
+
+    #[test]
+    fn hovering_map_blocking_ui_hides_the_tile() {
+        let coord = UVec2::new(3, 2);
+        let mut app = hover_test_app(coord);
+        let button = app
+            .world_mut()
+            .spawn((Interaction::Hovered, ToolbarButton { tool: PlacementTool::Delete }))
+            .id();
+
+        app.update();
+        assert_eq!(app.world().resource::<HoveredTile>().0, None);
+
+        *app.world_mut().get_mut::<Interaction>(button).unwrap() = Interaction::None;
+        app.update();
+        assert_eq!(app.world().resource::<HoveredTile>().0, Some(coord));
+    }
+
+    #[test]
+    fn other_ui_does_not_hide_the_tile() {
+        let coord = UVec2::new(3, 2);
+        let mut app = hover_test_app(coord);
+        // Such as the buttons of a hidden menu, which keep their last interaction
+        app.world_mut().spawn(Interaction::Hovered);
+
+        app.update();
+        assert_eq!(app.world().resource::<HoveredTile>().0, Some(coord));
+    }
+}
@@ -0,0 +1,704 @@
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, PrimaryWindow};
+use bevy::winit::WinitWindows;
+use crate::components::slider::{spawn_slider, Slider, SliderChanged};
+use crate::components::camera::{GameCamera, UiCamera};
+use crate::components::ui::*;
+use crate::events::ButtonActivated;
+use crate::i18n::Locale;
+use crate::resources::{Resolution, Settings, TerrainPalette};
+use crate::save::{back_up_file, load_settings, save_settings, settings_path, SETTINGS_FILE};
+use crate::systems::menu::{create_labeled_row, create_menu_button, create_sized_button, create_value_text};
+
+/// Setup the settings screen UI
+pub fn setup_settings_menu(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    locale: Res<Locale>,
+    settings: Res<Settings>,
+    game_camera: Query<(), With<GameCamera>>,
+) {
+    info!("Setting up settings menu");
+
+    // Opened from the pause menu, the screen renders through the gameplay camera that is
+    // still alive, so a second camera is only needed when there is no game world
+    if game_camera.is_empty() {
+        commands.spawn((Camera2dBundle::default(), UiCamera, SettingsMenu));
+    }
+
+    // Settings root container
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                background_color: theme.background_color.into(),
+                ..default()
+            },
+            SettingsMenu,
+            ThemeColor::Background,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                TextBundle::from_section(
+                    "Settings",
+                    TextStyle {
+                        font_size: theme.title_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                ),
+                ThemeColor::Title,
+            ));
+
+            // Master volume row
+            create_labeled_row(parent, &theme, "Master Volume", |row| {
+                spawn_slider(
+                    row,
+                    &theme,
+                    Slider::new(0.0, 1.0, settings.master_volume, Settings::VOLUME_STEP),
+                    Val::Px(240.0),
+                    MasterVolumeSlider,
+                );
+                create_value_text(row, &theme, settings.volume_label(), SettingValueText::MasterVolume);
+            });
+
+            // Window mode row
+            create_labeled_row(parent, &theme, "Window Mode", |row| {
+                create_settings_button(row, &theme, "Change", SettingsAction::CycleWindowMode, 140.0);
+                create_value_text(row, &theme, settings.window_mode.label().to_string(), SettingValueText::WindowMode);
+            });
+
+            // Autosave row
+            create_labeled_row(parent, &theme, "Autosave", |row| {
+                spawn_slider(
+                    row,
+                    &theme,
+                    Slider::new(
+                        0.0,
+                        Settings::MAX_AUTOSAVE_GAME_MINUTES as f32,
+                        settings.autosave_game_minutes as f32,
+                        Settings::AUTOSAVE_STEP_GAME_MINUTES as f32,
+                    ),
+                    Val::Px(240.0),
+                    AutosaveSlider,
+                );
+                create_value_text(row, &theme, settings.autosave_label(), SettingValueText::Autosave);
+            });
+
+            // Edge scrolling row
+            create_labeled_row(parent, &theme, "Edge Scrolling", |row| {
+                create_settings_button(row, &theme, "Toggle", SettingsAction::ToggleEdgeScroll, 140.0);
+                create_value_text(row, &theme, settings.edge_scroll_label().to_string(), SettingValueText::EdgeScroll);
+            });
+
+            // Camera inertia row
+            create_labeled_row(parent, &theme, "Camera Inertia", |row| {
+                create_settings_button(row, &theme, "Toggle", SettingsAction::ToggleCameraInertia, 140.0);
+                create_value_text(
+                    row,
+                    &theme,
+                    settings.camera_inertia_label().to_string(),
+                    SettingValueText::CameraInertia,
+                );
+            });
+
+            // Mouse sensitivity row
+            create_labeled_row(parent, &theme, "Mouse Sensitivity", |row| {
+                spawn_slider(
+                    row,
+                    &theme,
+                    Slider::new(
+                        Settings::MIN_PAN_SPEED,
+                        Settings::MAX_PAN_SPEED,
+                        settings.mouse_sensitivity,
+                        Settings::PAN_SPEED_STEP,
+                    ),
+                    Val::Px(240.0),
+                    MouseSensitivitySlider,
+                );
+                create_value_text(
+                    row,
+                    &theme,
+                    settings.mouse_sensitivity_label(),
+                    SettingValueText::MouseSensitivity,
+                );
+            });
+
+            // Keyboard panning speed row
+            create_labeled_row(parent, &theme, "Key Pan Speed", |row| {
+                spawn_slider(
+                    row,
+                    &theme,
+                    Slider::new(
+                        Settings::MIN_PAN_SPEED,
+                        Settings::MAX_PAN_SPEED,
+                        settings.key_pan_speed,
+                        Settings::PAN_SPEED_STEP,
+                    ),
+                    Val::Px(240.0),
+                    KeyPanSpeedSlider,
+                );
+                create_value_text(row, &theme, settings.key_pan_speed_label(), SettingValueText::KeyPanSpeed);
+            });
+
+            // Pause on focus loss row
+            create_labeled_row(parent, &theme, "Pause Unfocused", |row| {
+                create_settings_button(row, &theme, "Toggle", SettingsAction::TogglePauseOnFocusLoss, 140.0);
+                create_value_text(
+                    row,
+                    &theme,
+                    settings.pause_on_focus_loss_label().to_string(),
+                    SettingValueText::PauseOnFocusLoss,
+                );
+            });
+
+            // Vignette row
+            create_labeled_row(parent, &theme, "Vignette", |row| {
+                create_settings_button(row, &theme, "Toggle", SettingsAction::ToggleVignette, 140.0);
+                create_value_text(row, &theme, settings.vignette_label().to_string(), SettingValueText::Vignette);
+            });
+
+            // Frame rate row
+            create_labeled_row(parent, &theme, "Frame Rate", |row| {
+                create_settings_button(row, &theme, "VSync", SettingsAction::ToggleVsync, 100.0);
+                create_settings_button(row, &theme, "Cap", SettingsAction::CycleFpsCap, 100.0);
+                create_value_text(row, &theme, settings.frame_rate_label(), SettingValueText::FrameRate);
+            });
+
+            // Resolution row, with the presets as a button group below it
+            create_labeled_row(parent, &theme, "Resolution", |row| {
+                create_value_text(row, &theme, settings.resolution.label(), SettingValueText::Resolution);
+            });
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for resolution in Resolution::PRESETS {
+                        create_settings_button(
+                            row,
+                            &theme,
+                            &resolution.label(),
+                            SettingsAction::SelectResolution(resolution),
+                            130.0,
+                        );
+                    }
+                });
+
+            // Accessibility group
+            parent.spawn((
+                TextBundle::from_section(
+                    "Accessibility",
+                    TextStyle {
+                        font_size: theme.button_font_size,
+                        color: theme.title_color,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(10.0)),
+                    ..default()
+                }),
+                ThemeColor::Title,
+            ));
+
+            // Theme row, which includes the high-contrast palette
+            create_labeled_row(parent, &theme, "Theme", |row| {
+                create_settings_button(row, &theme, "Change", SettingsAction::CycleTheme, 140.0);
+                create_value_text(row, &theme, settings.theme.label().to_string(), SettingValueText::Theme);
+            });
+
+            // Colorblind mode row, which swaps the terrain palette
+            create_labeled_row(parent, &theme, "Colorblind Mode", |row| {
+                create_settings_button(row, &theme, "Change", SettingsAction::CycleColorblindMode, 140.0);
+                create_value_text(
+                    row,
+                    &theme,
+                    settings.colorblind_mode.label().to_string(),
+                    SettingValueText::ColorblindMode,
+                );
+            });
+
+            // Terrain patterns row
+            create_labeled_row(parent, &theme, "Terrain Patterns", |row| {
+                create_settings_button(row, &theme, "Toggle", SettingsAction::ToggleTerrainPatterns, 140.0);
+                create_value_text(
+                    row,
+                    &theme,
+                    settings.terrain_patterns_label().to_string(),
+                    SettingValueText::TerrainPatterns,
+                );
+            });
+
+            // Text size row
+            create_labeled_row(parent, &theme, "Text Size", |row| {
+                spawn_slider(
+                    row,
+                    &theme,
+                    Slider::new(
+                        Settings::MIN_FONT_SCALE,
+                        Settings::MAX_FONT_SCALE,
+                        settings.font_scale,
+                        Settings::FONT_SCALE_STEP,
+                    ),
+                    Val::Px(240.0),
+                    FontScaleSlider,
+                );
+                create_value_text(row, &theme, settings.font_scale_label(), SettingValueText::FontScale);
+            });
+
+            // Language row
+            create_labeled_row(parent, &theme, "Language", |row| {
+                create_settings_button(row, &theme, "Change", SettingsAction::CycleLanguage, 140.0);
+                create_value_text(row, &theme, settings.language.label().to_string(), SettingValueText::Language);
+            });
+
+            // Button row, side by side to leave room for the option rows
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(15.0),
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    create_menu_button(parent, &theme, &locale, "Controls", MenuAction::Controls);
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        &locale,
+                        "Reset to Defaults",
+                        MenuAction::confirm(
+                            locale.t("settings.reset_confirm"),
+                            MenuAction::ResetSettings,
+                        ),
+                    );
+                    create_menu_button(parent, &theme, &locale, "Back", MenuAction::Back);
+                });
+        });
+}
+
+/// Helper function to create a settings button
+fn create_settings_button(
+    parent: &mut ChildBuilder,
+    theme: &MenuTheme,
+    text: &str,
+    action: SettingsAction,
+    width: f32,
+) {
+    create_sized_button(parent, theme, text, Val::Px(width), Val::Px(40.0), SettingsButton::new(action));
+}
+
+/// Handle activated settings buttons and update the settings resource, including resets
+/// confirmed in the dialog
+pub fn handle_settings_actions(
+    mut activated: EventReader<ButtonActivated>,
+    button_query: Query<&SettingsButton>,
+    menu_button_query: Query<&MenuButton>,
+    mut slider_query: SettingsSliders,
+    mut settings: ResMut<Settings>,
+) {
+    for event in activated.read() {
+        if let Ok(MenuButton {
+            action: MenuAction::ResetSettings,
+        }) = menu_button_query.get(event.entity)
+        {
+            reset_settings(&mut settings, &mut slider_query);
+            continue;
+        }
+
+        let Ok(settings_button) = button_query.get(event.entity) else {
+            continue;
+        };
+        
+        match settings_button.action {
+            SettingsAction::CycleWindowMode => {
+                settings.window_mode = settings.window_mode.next();
+            }
+            SettingsAction::ToggleEdgeScroll => {
+                settings.edge_scroll = !settings.edge_scroll;
+            }
+            SettingsAction::ToggleCameraInertia => {
+                settings.camera_inertia = !settings.camera_inertia;
+            }
+            SettingsAction::TogglePauseOnFocusLoss => {
+                settings.pause_on_focus_loss = !settings.pause_on_focus_loss;
+            }
+            SettingsAction::ToggleVignette => {
+                settings.vignette = !settings.vignette;
+            }
+            SettingsAction::ToggleVsync => {
+                settings.vsync = !settings.vsync;
+            }
+            SettingsAction::CycleFpsCap => {
+                settings.cycle_fps_cap();
+            }
+            SettingsAction::CycleTheme => {
+                settings.theme = settings.theme.next();
+            }
+            SettingsAction::CycleColorblindMode => {
+                settings.colorblind_mode = settings.colorblind_mode.next();
+            }
+            SettingsAction::ToggleTerrainPatterns => {
+                settings.terrain_patterns = !settings.terrain_patterns;
+            }
+            SettingsAction::CycleLanguage => {
+                settings.language = settings.language.next();
+            }
+            SettingsAction::SelectResolution(resolution) => {
+                settings.resolution = resolution;
+            }
+        }
+        info!("Settings changed: {:?}", *settings);
+    }
+}
+
+/// Every slider, with which of the settings screen's sliders it is
+type SettingsSliders<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Slider,
+        Has<MasterVolumeSlider>,
+        Has<AutosaveSlider>,
+        Has<FontScaleSlider>,
+        Has<MouseSensitivitySlider>,
+        Has<KeyPanSpeedSlider>,
+    ),
+>;
+
+/// Restore the default settings and move the sliders to match
+///
+/// The window, volume, theme and language follow on their own since the resource changes, and
+/// `persist_settings` writes the reset to disk.
+fn reset_settings(settings: &mut Settings, slider_query: &mut SettingsSliders) {
+    *settings = Settings::default();
+    info!("Settings reset to defaults");
+
+    for (mut slider, volume, autosave, font_scale, mouse_sensitivity, key_pan_speed) in
+        slider_query.iter_mut()
+    {
+        if volume {
+            slider.value = settings.master_volume;
+        } else if autosave {
+            slider.value = settings.autosave_game_minutes as f32;
+        } else if font_scale {
+            slider.value = settings.font_scale;
+        } else if mouse_sensitivity {
+            slider.value = settings.mouse_sensitivity;
+        } else if key_pan_speed {
+            slider.value = settings.key_pan_speed;
+        }
+    }
+}
+
+/// Apply settings slider changes to the settings resource
+pub fn handle_settings_sliders(
+    mut changed: EventReader<SliderChanged>,
+    volume_query: Query<(), With<MasterVolumeSlider>>,
+    autosave_query: Query<(), With<AutosaveSlider>>,
+    font_scale_query: Query<(), With<FontScaleSlider>>,
+    mouse_sensitivity_query: Query<(), With<MouseSensitivitySlider>>,
+    key_pan_speed_query: Query<(), With<KeyPanSpeedSlider>>,
+    mut settings: ResMut<Settings>,
+) {
+    for event in changed.read() {
+        if volume_query.contains(event.entity) {
+            settings.set_volume(event.value);
+        } else if autosave_query.contains(event.entity) {
+            settings.set_autosave_game_minutes(event.value.round() as u32);
+        } else if font_scale_query.contains(event.entity) {
+            settings.set_font_scale(event.value);
+        } else if mouse_sensitivity_query.contains(event.entity) {
+            settings.set_mouse_sensitivity(event.value);
+        } else if key_pan_speed_query.contains(event.entity) {
+            settings.set_key_pan_speed(event.value);
+        }
+    }
+}
+
+/// Keep the displayed setting values in sync with the settings resource
+pub fn update_settings_text(
+    settings: Res<Settings>,
+    mut text_query: Query<(&mut Text, &SettingValueText)>,
+) {
+    for (mut text, kind) in &mut text_query {
+        text.sections[0].value = match kind {
+            SettingValueText::MasterVolume => settings.volume_label(),
+            SettingValueText::WindowMode => settings.window_mode.label().to_string(),
+            SettingValueText::Resolution => settings.resolution.label(),
+            SettingValueText::Autosave => settings.autosave_label(),
+            SettingValueText::EdgeScroll => settings.edge_scroll_label().to_string(),
+            SettingValueText::CameraInertia => settings.camera_inertia_label().to_string(),
+            SettingValueText::MouseSensitivity => settings.mouse_sensitivity_label(),
+            SettingValueText::KeyPanSpeed => settings.key_pan_speed_label(),
+            SettingValueText::PauseOnFocusLoss => settings.pause_on_focus_loss_label().to_string(),
+            SettingValueText::Vignette => settings.vignette_label().to_string(),
+            SettingValueText::FrameRate => settings.frame_rate_label(),
+            SettingValueText::Theme => settings.theme.label().to_string(),
+            SettingValueText::ColorblindMode => settings.colorblind_mode.label().to_string(),
+            SettingValueText::TerrainPatterns => settings.terrain_patterns_label().to_string(),
+            SettingValueText::Language => settings.language.label().to_string(),
+            SettingValueText::FontScale => settings.font_scale_label(),
+        };
+    }
+}
+
+/// Cleanup settings menu and its UI camera when leaving the settings screen
+///
+/// Only entities tagged with `SettingsMenu` are removed so a paused game world survives.
+pub fn cleanup_settings_menu(
+    mut commands: Commands,
+    menu_query: Query<Entity, With<SettingsMenu>>,
+) {
+    for entity in &menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    info!("Settings menu cleaned up");
+}
+
+/// Read the settings file, falling back to defaults if it is missing or unreadable
+///
+/// A settings file left in the working directory by older builds is used when the config
+/// directory has none yet. A file that can't be parsed is moved aside rather than overwritten.
+pub fn load_startup_settings() -> Settings {
+    let mut path = settings_path();
+    let mut result = load_settings(&path);
+    if matches!(&result, Err(error) if error.kind() == io::ErrorKind::NotFound) {
+        path = PathBuf::from(SETTINGS_FILE);
+        result = load_settings(&path);
+    }
+
+    match result {
+        Ok(settings) => {
+            info!("Loaded settings from {}", path.display());
+            settings
+        }
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Settings::default(),
+        // A file that isn't valid JSON is kept, since the defaults are written in its place
+        Err(error) if matches!(error.kind(), io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof) => {
+            warn!("Failed to read settings from {}, using defaults: {}", path.display(), error);
+            match back_up_file(&path) {
+                Ok(backup) => warn!("Moved the unreadable settings file to {}", backup.display()),
+                Err(error) => error!("Failed to back up {}: {}", path.display(), error),
+            }
+            Settings::default()
+        }
+        Err(error) => {
+            warn!("Failed to load settings from {}, using defaults: {}", path.display(), error);
+            Settings::default()
+        }
+    }
+}
+
+/// Write the settings to the config directory
+///
+/// Runs whenever they change, at most once a second so dragging a slider doesn't write on
+/// every frame, and when leaving the settings screen. The first run writes the defaults when
+/// there was no settings file yet.
+pub fn persist_settings(settings: Res<Settings>) {
+    let path = settings_path();
+    match save_settings(&settings, &path) {
+        Ok(()) => info!("Settings saved to {}", path.display()),
+        Err(error) => error!("Failed to save settings to {}: {}", path.display(), error),
+    }
+}
+
+/// Apply the window mode setting to the primary window
+///
+/// Switching modes recreates the surface on some platforms, so the cursor is explicitly
+/// released and shown again. UI layouts use percentages and cameras follow the window
+/// size, so they adapt to the new resolution on their own.
+pub fn apply_window_mode(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    let mode = settings.window_mode.window_mode();
+    if window.mode != mode {
+        info!("Switching window mode to {}", settings.window_mode.label());
+        window.mode = mode;
+        window.cursor.visible = true;
+        window.cursor.grab_mode = CursorGrabMode::None;
+    }
+}
+
+/// Apply the VSync setting to the primary window
+pub fn apply_present_mode(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    let present_mode = settings.present_mode();
+    if window.present_mode != present_mode {
+        info!("Switching present mode to {:?}", present_mode);
+        window.present_mode = present_mode;
+    }
+}
+
+/// Hold each frame until the frame rate cap allows the next one
+///
+/// Sleeps at the end of the frame, so the F3 diagnostics overlay should settle at or just
+/// under the cap. Turn VSync off to check caps above the monitor's refresh rate.
+pub fn limit_frame_rate(settings: Res<Settings>, mut last_frame: Local<Option<Instant>>) {
+    if settings.fps_cap > 0 {
+        if let Some(last_frame) = *last_frame {
+            let frame_time = Duration::from_secs_f64(1.0 / settings.fps_cap as f64);
+            if let Some(remaining) = frame_time.checked_sub(last_frame.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+    }
+    *last_frame = Some(Instant::now());
+}
+
+/// Switch the menu theme and terrain palette to the ones chosen in the settings
+///
+/// A colorblind mode overrides the theme's terrain palette.
+///
+/// The text size only affects text created afterwards, so open screens keep their layout.
+pub fn apply_theme(
+    settings: Res<Settings>,
+    mut theme: ResMut<MenuTheme>,
+    mut palette: ResMut<TerrainPalette>,
+) {
+    palette.set_if_neq(settings.terrain_palette());
+    let selected = settings.menu_theme();
+    if *theme != selected {
+        info!(
+            "Switching theme to {} with {} text",
+            settings.theme.label(),
+            settings.font_scale_label()
+        );
+        *theme = selected;
+    }
+}
+
+/// Load the strings for the language chosen in the settings
+///
+/// Open screens keep their text, and pick up the new language the next time they are set up.
+pub fn apply_language(settings: Res<Settings>, mut locale: ResMut<Locale>) {
+    if locale.language != settings.language {
+        info!("Switching language to {}", settings.language.label());
+        *locale = Locale::load(settings.language);
+    }
+}
+
+/// Apply the resolution setting to the primary window
+///
+/// Resolutions larger than the primary monitor fall back to the largest preset that fits,
+/// and the setting is updated so the settings screen shows what was actually applied.
+pub fn apply_resolution(
+    mut settings: ResMut<Settings>,
+    winit_windows: Option<NonSend<WinitWindows>>,
+    mut window_query: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+) {
+    let Ok((entity, mut window)) = window_query.get_single_mut() else {
+        return;
+    };
+
+    // The OS window, and so its monitor, may not exist yet on the first frames, in which case
+    // nothing is guarded
+    let monitor = winit_windows
+        .as_ref()
+        .and_then(|winit_windows| winit_windows.get_window(entity))
+        .and_then(|winit_window| winit_window.current_monitor());
+    let mut resolution = settings.resolution;
+    if let Some(monitor) = monitor {
+        let physical_size = monitor.size();
+        let monitor_size = (Vec2::new(physical_size.width as f32, physical_size.height as f32)
+            / monitor.scale_factor() as f32)
+            .as_uvec2();
+        resolution = resolution.fit_to(monitor_size);
+        if resolution != settings.resolution {
+            warn!(
+                "Resolution {} is larger than the monitor, using {}",
+                settings.resolution.label(),
+                resolution.label()
+            );
+            settings.resolution = resolution;
+        }
+    }
+
+    let width = resolution.width as f32;
+    let height = resolution.height as f32;
+    if window.resolution.width() != width || window.resolution.height() != height {
+        info!("Setting window resolution to {}", resolution.label());
+        window.resolution.set(width, height);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_restores_the_defaults_and_moves_the_sliders() {
+        let mut settings = Settings::default();
+        settings.set_volume(0.1);
+        settings.set_autosave_game_minutes(60);
+        settings.set_font_scale(Settings::MAX_FONT_SCALE);
+        settings.set_mouse_sensitivity(2.0);
+        settings.set_key_pan_speed(Settings::MAX_PAN_SPEED);
+        settings.edge_scroll = !settings.edge_scroll;
+
+        let mut app = App::new();
+        app.insert_resource(settings.clone())
+            .add_event::<ButtonActivated>()
+            .add_systems(Update, handle_settings_actions);
+
+        // Wide enough for any value, so only the reset decides where the sliders end up
+        let slider = |value: f32| Slider::new(0.0, 10_000.0, value, 0.01);
+        let world = app.world_mut();
+        let volume = world.spawn((slider(settings.master_volume), MasterVolumeSlider)).id();
+        let autosave = world
+            .spawn((slider(settings.autosave_game_minutes as f32), AutosaveSlider))
+            .id();
+        let font_scale = world.spawn((slider(settings.font_scale), FontScaleSlider)).id();
+        let sensitivity = world
+            .spawn((slider(settings.mouse_sensitivity), MouseSensitivitySlider))
+            .id();
+        let pan_speed = world.spawn((slider(settings.key_pan_speed), KeyPanSpeedSlider)).id();
+        let reset = world.spawn(MenuButton::new(MenuAction::ResetSettings)).id();
+        world.send_event(ButtonActivated { entity: reset });
+        app.update();
+
+        let defaults = Settings::default();
+        assert_eq!(*app.world().resource::<Settings>(), defaults);
+        let slider_value = |entity: Entity| app.world().get::<Slider>(entity).unwrap().value;
+        assert_eq!(slider_value(volume), defaults.master_volume);
+        assert_eq!(slider_value(autosave), defaults.autosave_game_minutes as f32);
+        assert_eq!(slider_value(font_scale), defaults.font_scale);
+        assert_eq!(slider_value(sensitivity), defaults.mouse_sensitivity);
+        assert_eq!(slider_value(pan_speed), defaults.key_pan_speed);
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use crate::resources::{Rng, SimulationClock, SimulationSpeed, WorldConfig};
+use crate::save::UnsavedChanges;
+
+/// Advance the simulation clock by one fixed step scaled by the speed multiplier
+///
+/// Runs in `FixedUpdate`, where `Time` is the fixed timestep, so the world ticks the same way
+/// on every machine. Any tick leaves the world different from its last save.
+pub fn advance_simulation_clock(
+    mut clock: ResMut<SimulationClock>,
+    time: Res<Time>,
+    config: Res<WorldConfig>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
+) {
+    if clock.speed == SimulationSpeed::Paused {
+        return;
+    }
+    clock.advance(time.delta_seconds(), config.day_length_seconds);
+
+    if !unsaved_changes.0 {
+        unsaved_changes.0 = true;
+    }
+}
+
+/// Change simulation speed with 1/2/3, and pause it without leaving the game with 0
+pub fn handle_simulation_speed_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut clock: ResMut<SimulationClock>,
+) {
+    let speed = if keyboard_input.any_just_pressed([KeyCode::Digit0, KeyCode::Numpad0]) {
+        SimulationSpeed::Paused
+    } else if keyboard_input.any_just_pressed([KeyCode::Digit1, KeyCode::Numpad1]) {
+        SimulationSpeed::Normal
+    } else if keyboard_input.any_just_pressed([KeyCode::Digit2, KeyCode::Numpad2]) {
+        SimulationSpeed::Fast
+    } else if keyboard_input.any_just_pressed([KeyCode::Digit3, KeyCode::Numpad3]) {
+        SimulationSpeed::Fastest
+    } else {
+        return;
+    };
+
+    if clock.speed != speed {
+        info!("Simulation speed set to {}", speed.label());
+        clock.speed = speed;
+    }
+}
+
+/// Reseed the gameplay RNG from the world seed, so each world rolls the same numbers every time
+pub fn reset_rng(mut rng: ResMut<Rng>, world_config: Res<WorldConfig>) {
+    *rng = Rng::from_seed(world_config.seed);
+}
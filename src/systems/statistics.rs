@@ -0,0 +1,253 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::ImageSampler;
+use crate::components::ui::*;
+use crate::resources::{
+    DivinePower, Population, ResourceKind, Resources, SimulationClock, Statistic, StatisticsHistory,
+    StatisticsSample,
+};
+
+/// Size of each graph in logical pixels, and of the image it is drawn into
+const GRAPH_WIDTH: u32 = 240;
+const GRAPH_HEIGHT: u32 = 60;
+const LABEL_FONT_SIZE: f32 = 16.0;
+/// The panel has a dark backdrop whatever the menu theme
+const LABEL_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const GRAPH_BACKGROUND: [u8; 4] = [20, 20, 20, 255];
+
+/// Start each new world with no recorded statistics
+pub fn reset_statistics(mut history: ResMut<StatisticsHistory>) {
+    *history = StatisticsHistory::default();
+}
+
+/// Sample the population, food and mana at a fixed interval of in-game time
+pub fn record_statistics(
+    clock: Res<SimulationClock>,
+    population: Res<Population>,
+    resources: Res<Resources>,
+    divine_power: Res<DivinePower>,
+    mut history: ResMut<StatisticsHistory>,
+) {
+    // Only mark the history changed when a sample is taken, so the graphs redraw just then
+    let due = history.bypass_change_detection().record(
+        clock.elapsed_hours,
+        StatisticsSample {
+            population: population.count as f32,
+            food: resources.amount(ResourceKind::Food),
+            mana: divine_power.mana,
+        },
+    );
+    if due {
+        history.set_changed();
+    }
+}
+
+/// Setup the statistics panel to the right of the event log, hidden until opened from the
+/// top bar
+pub fn setup_statistics_panel(
+    mut commands: Commands,
+    theme: Res<MenuTheme>,
+    history: Res<StatisticsHistory>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(54.0),
+                    left: Val::Px(340.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(6.0)),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            StatisticsPanel,
+        ))
+        .with_children(|parent| {
+            for statistic in Statistic::ALL {
+                parent.spawn((
+                    TextBundle::from_section(
+                        label_text(&history, statistic),
+                        TextStyle {
+                            font_size: theme.font_size(LABEL_FONT_SIZE),
+                            color: LABEL_COLOR,
+                            ..default()
+                        },
+                    ),
+                    StatisticsLabel(statistic),
+                ));
+
+                let mut image = blank_graph_image();
+                write_graph_pixels(&mut image, &history, statistic);
+                parent.spawn((
+                    ImageBundle {
+                        style: Style {
+                            width: Val::Px(GRAPH_WIDTH as f32),
+                            height: Val::Px(GRAPH_HEIGHT as f32),
+                            ..default()
+                        },
+                        image: UiImage::new(images.add(image)),
+                        ..default()
+                    },
+                    StatisticsGraph(statistic),
+                ));
+            }
+        });
+}
+
+/// Show or hide the statistics panel when its top bar button is clicked
+pub fn toggle_statistics_panel(
+    toggle_query: Query<&Interaction, (Changed<Interaction>, With<StatisticsToggle>)>,
+    mut panel_query: Query<&mut Visibility, With<StatisticsPanel>>,
+) {
+    for interaction in &toggle_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        for mut visibility in &mut panel_query {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+}
+
+/// Highlight the statistics button while the panel is open, with the same hover and press
+/// feedback as the speed buttons
+pub fn update_statistics_toggle_colors(
+    theme: Res<MenuTheme>,
+    panel_query: Query<&Visibility, With<StatisticsPanel>>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &mut ButtonScale), With<StatisticsToggle>>,
+) {
+    let open = panel_query.iter().any(|visibility| *visibility != Visibility::Hidden);
+
+    for (interaction, mut background_color, mut button_scale) in &mut button_query {
+        let (color, scale) = match *interaction {
+            Interaction::Pressed => (theme.button_pressed, ButtonScale::PRESSED),
+            Interaction::Hovered => (theme.button_hovered, ButtonScale::HOVERED),
+            Interaction::None if open => (theme.button_pressed, ButtonScale::RESTING),
+            Interaction::None => (theme.button_normal, ButtonScale::RESTING),
+        };
+
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+        if button_scale.target != scale {
+            button_scale.target = scale;
+        }
+    }
+}
+
+/// Redraw the graphs and their labels after each new sample
+pub fn update_statistics_graphs(
+    history: Res<StatisticsHistory>,
+    graph_query: Query<(&UiImage, &StatisticsGraph)>,
+    mut label_query: Query<(&mut Text, &StatisticsLabel)>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (ui_image, StatisticsGraph(statistic)) in &graph_query {
+        if let Some(image) = images.get_mut(&ui_image.texture) {
+            write_graph_pixels(image, &history, *statistic);
+        }
+    }
+    for (mut text, StatisticsLabel(statistic)) in &mut label_query {
+        text.sections[0].value = label_text(&history, *statistic);
+    }
+}
+
+/// Cleanup the statistics panel when leaving the game
+pub fn cleanup_statistics_panel(
+    mut commands: Commands,
+    panel_query: Query<Entity, With<StatisticsPanel>>,
+) {
+    for entity in &panel_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// A statistic's name, latest value and the top of its graph, e.g. "Food 42 (scale 50)"
+fn label_text(history: &StatisticsHistory, statistic: Statistic) -> String {
+    let latest = history.latest(statistic).unwrap_or_default();
+    format!(
+        "{} {} (scale {})",
+        statistic.label(),
+        latest.floor() as u32,
+        history.scale(statistic) as u32
+    )
+}
+
+fn blank_graph_image() -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: GRAPH_WIDTH,
+            height: GRAPH_HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &GRAPH_BACKGROUND,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    // Keep the line one crisp pixel wide
+    image.sampler = ImageSampler::nearest();
+    image
+}
+
+/// Draw a statistic's history as a line graph scaled to fit its highest value
+///
+/// The full history spans the width of the graph with the newest sample at the right edge, so
+/// the line scrolls left as samples are added and the oldest fall out of the history.
+fn write_graph_pixels(image: &mut Image, history: &StatisticsHistory, statistic: Statistic) {
+    for pixel in image.data.chunks_exact_mut(4) {
+        pixel.copy_from_slice(&GRAPH_BACKGROUND);
+    }
+
+    let values: Vec<f32> = history.values(statistic).collect();
+    if values.is_empty() {
+        return;
+    }
+    let scale = history.scale(statistic);
+    let color = statistic.color().to_srgba();
+    let line = [
+        (color.red * 255.0) as u8,
+        (color.green * 255.0) as u8,
+        (color.blue * 255.0) as u8,
+        255,
+    ];
+
+    // Pixels per sample, with room for the whole history
+    let spacing = (GRAPH_WIDTH - 1) as f32 / (StatisticsHistory::MAX_SAMPLES - 1) as f32;
+    let start = (GRAPH_WIDTH - 1) as f32 - (values.len() - 1) as f32 * spacing;
+    // Image rows count down from the top while values count up from the bottom
+    let row_of = |value: f32| {
+        let height = (value / scale).clamp(0.0, 1.0) * (GRAPH_HEIGHT - 1) as f32;
+        GRAPH_HEIGHT - 1 - height.round() as u32
+    };
+
+    let mut previous_row = None;
+    for column in start.ceil() as u32..GRAPH_WIDTH {
+        let position = (column as f32 - start) / spacing;
+        let index = (position.floor() as usize).min(values.len() - 1);
+        let next = values.get(index + 1).copied().unwrap_or(values[index]);
+        let value = values[index] + (next - values[index]) * position.fract();
+        let row = row_of(value);
+
+        // Fill the gap to the previous column so steep changes stay a connected line
+        let (top, bottom) = match previous_row {
+            Some(previous) => (row.min(previous), row.max(previous)),
+            None => (row, row),
+        };
+        for fill_row in top..=bottom {
+            let offset = ((fill_row * GRAPH_WIDTH + column) * 4) as usize;
+            image.data[offset..offset + 4].copy_from_slice(&line);
+        }
+        previous_row = Some(row);
+    }
+}
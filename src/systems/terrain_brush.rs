@@ -0,0 +1,253 @@
+use std::collections::HashSet;
+
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::components::camera::GameCamera;
+use crate::components::tile::Tile;
+use crate::components::tooltip::Tooltip;
+use crate::components::ui::*;
+use crate::events::{Notification, PlaySoundEffect};
+use crate::resources::{
+    ActionHistory, DivinePower, SelectedTool, SoundEffect, TerrainBrush, TerrainMaterials,
+    TerrainTool, TileChange, WorldConfig, WorldEdit, TILE_SIZE,
+};
+use crate::systems::minimap::{MINIMAP_BORDER, MINIMAP_MARGIN, MINIMAP_SIZE};
+use crate::utils::cursor_to_world;
+
+/// Gap between the tool bar and the minimap below it
+const TOOLBAR_GAP: f32 = 10.0;
+/// Distance of the tool bar from the bottom of the screen, clear of the minimap below it at
+/// its largest, border included
+const TOOLBAR_BOTTOM: f32 = MINIMAP_MARGIN + MINIMAP_SIZE + 2.0 * MINIMAP_BORDER + TOOLBAR_GAP;
+const BRUSH_PREVIEW_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.35);
+
+/// Start each new world with no terrain tool selected
+pub fn reset_terrain_brush(mut brush: ResMut<TerrainBrush>) {
+    *brush = TerrainBrush::default();
+}
+
+/// Setup the terrain tool bar in the bottom-left corner, stacked above the minimap
+pub fn setup_terrain_toolbar(mut commands: Commands, theme: Res<MenuTheme>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(TOOLBAR_BOTTOM),
+                    left: Val::Px(MINIMAP_MARGIN),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                ..default()
+            },
+            TerrainToolbar,
+        ))
+        .with_children(|parent| {
+            for tool in TerrainTool::ALL {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                min_width: Val::Px(140.0),
+                                min_height: Val::Px(40.0),
+                                padding: UiRect::horizontal(Val::Px(8.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: theme.button_normal.into(),
+                            ..default()
+                        },
+                        TerrainToolButton { tool },
+                        Tooltip::new(format!(
+                            "Costs {:.0} mana per tile. Scroll to resize the brush",
+                            TerrainBrush::MANA_PER_TILE
+                        )),
+                    ))
+                    .with_children(|button| {
+                        button.spawn((
+                            TextBundle::from_section(
+                                tool.name(),
+                                TextStyle {
+                                    font_size: theme.font_size(24.0),
+                                    color: theme.button_text,
+                                    ..default()
+                                },
+                            ),
+                            ThemeColor::Text,
+                        ));
+                    });
+            }
+        });
+}
+
+/// Select the clicked terrain tool, or put it away when clicked again
+///
+/// Selecting a terrain tool puts away any placement tool.
+pub fn handle_terrain_tool_buttons(
+    interaction_query: Query<(&Interaction, &TerrainToolButton), Changed<Interaction>>,
+    mut brush: ResMut<TerrainBrush>,
+    mut selected_tool: ResMut<SelectedTool>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            brush.tool = if brush.tool == Some(button.tool) {
+                None
+            } else {
+                selected_tool.0 = None;
+                Some(button.tool)
+            };
+        }
+    }
+}
+
+/// Color terrain tool buttons to show hover and the selected tool
+pub fn update_terrain_tool_colors(
+    theme: Res<MenuTheme>,
+    brush: Res<TerrainBrush>,
+    mut button_query: Query<(&Interaction, &TerrainToolButton, &mut BackgroundColor)>,
+) {
+    for (interaction, button, mut background_color) in &mut button_query {
+        let color = if brush.tool == Some(button.tool) {
+            theme.button_pressed
+        } else if *interaction == Interaction::Hovered {
+            theme.button_hovered
+        } else {
+            theme.button_normal
+        };
+
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+    }
+}
+
+/// Resize the brush with the mouse wheel while a terrain tool is selected
+pub fn resize_brush(mut scroll_events: EventReader<MouseWheel>, mut brush: ResMut<TerrainBrush>) {
+    let scroll: f32 = scroll_events.read().map(|event| event.y).sum();
+    if brush.tool.is_none() || scroll == 0.0 {
+        return;
+    }
+    brush.resize(scroll.signum() as i32);
+}
+
+/// Reshape the tiles under the brush when the map is left-clicked
+///
+/// Each click is one undoable edit, charged mana for every tile it changes. Clicks that
+/// can't be paid for are rejected with a sound and leave the land untouched.
+#[allow(clippy::too_many_arguments)]
+pub fn paint_terrain(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    brush: Res<TerrainBrush>,
+    config: Res<WorldConfig>,
+    terrain_materials: Res<TerrainMaterials>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    ui_query: Query<&Interaction, MapBlockingUi>,
+    mut tile_query: Query<(&mut Tile, &mut Handle<ColorMaterial>)>,
+    mut divine_power: ResMut<DivinePower>,
+    mut history: ResMut<ActionHistory>,
+    mut sounds: EventWriter<PlaySoundEffect>,
+    mut notifications: EventWriter<Notification>,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(tool) = brush.tool else {
+        return;
+    };
+
+    // Clicks on the toolbars and minimap aren't meant for the map underneath
+    if ui_query.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(center) = cursor_to_world(window, projection, camera_transform)
+        .and_then(|position| config.tile_at(position))
+    else {
+        return;
+    };
+
+    let covered: HashSet<UVec2> = brush.tiles(center, &config).into_iter().collect();
+    let changed = tile_query
+        .iter()
+        .filter(|(tile, _)| covered.contains(&tile.coord) && tool.apply(tile.terrain) != tile.terrain)
+        .count();
+    if changed == 0 {
+        return;
+    }
+
+    let mana_spent = changed as f32 * TerrainBrush::MANA_PER_TILE;
+    if divine_power.try_spend(mana_spent).is_err() {
+        notifications.send(Notification::new("Not enough mana"));
+        sounds.send(PlaySoundEffect(SoundEffect::Reject));
+        return;
+    }
+
+    let mut changes = Vec::with_capacity(changed);
+    for (mut tile, mut material) in &mut tile_query {
+        let terrain = tool.apply(tile.terrain);
+        if covered.contains(&tile.coord) && terrain != tile.terrain {
+            changes.push(TileChange {
+                coord: tile.coord,
+                before: tile.terrain,
+                after: terrain,
+            });
+            tile.terrain = terrain;
+            *material = terrain_materials.get(terrain, tile.biome);
+        }
+    }
+    history.record(WorldEdit::Terrain { changes, mana_spent });
+    info!("{} changed {} tiles around {}", tool.name(), changed, center);
+}
+
+/// Outline the tiles the brush would reshape under the cursor
+pub fn draw_brush_preview(
+    mut gizmos: Gizmos,
+    brush: Res<TerrainBrush>,
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+) {
+    if brush.tool.is_none() {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(center) = cursor_to_world(window, projection, camera_transform)
+        .and_then(|position| config.tile_at(position))
+    else {
+        return;
+    };
+
+    for coord in brush.tiles(center, &config) {
+        gizmos.rect_2d(
+            config.tile_center(coord),
+            Rot2::IDENTITY,
+            Vec2::splat(TILE_SIZE),
+            BRUSH_PREVIEW_COLOR,
+        );
+    }
+}
+
+/// Cleanup the terrain tool bar when leaving the game
+pub fn cleanup_terrain_toolbar(
+    mut commands: Commands,
+    toolbar_query: Query<Entity, With<TerrainToolbar>>,
+) {
+    for entity in &toolbar_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
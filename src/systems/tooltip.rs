@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::components::camera::GameCamera;
+use crate::components::tile::Tile;
+use crate::components::tooltip::{Tooltip, TooltipBox};
+use crate::components::ui::{MenuTheme, Minimap};
+use crate::resources::{GameState, TileEntities, WorldConfig};
+use crate::utils::cursor_to_world;
+
+/// Seconds the cursor must rest on something before its tooltip appears
+const HOVER_DELAY_SECONDS: f32 = 0.5;
+/// Gap between the cursor and the tooltip box, in logical pixels
+const CURSOR_OFFSET: Vec2 = Vec2::new(16.0, 20.0);
+
+/// What the cursor has been resting on, and for how long
+#[derive(Default)]
+pub struct TooltipHover {
+    target: Option<Entity>,
+    seconds: f32,
+}
+
+/// Show a tooltip near the cursor for the hovered button or, in game, the hovered tile
+///
+/// The tooltip appears after a short delay, flips to the other side of the cursor near the
+/// window edges, and is despawned as soon as the hover ends.
+#[allow(clippy::too_many_arguments)]
+pub fn tooltip_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    theme: Res<MenuTheme>,
+    ui_scale: Res<UiScale>,
+    state: Res<State<GameState>>,
+    config: Res<WorldConfig>,
+    tile_entities: Res<TileEntities>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<GameCamera>>,
+    interaction_query: Query<(Entity, &Interaction, Option<&Tooltip>), Without<Minimap>>,
+    minimap_query: Query<&Interaction, With<Minimap>>,
+    tile_query: Query<&Tile>,
+    mut box_query: Query<(Entity, &mut Style, &Node, &mut Text), With<TooltipBox>>,
+    mut hover: Local<TooltipHover>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let cursor = window.cursor_position();
+
+    // Any UI under the cursor hides the world beneath it, tooltip or not
+    let hovered_ui = interaction_query
+        .iter()
+        .find(|(_, interaction, _)| **interaction != Interaction::None);
+    let over_minimap = minimap_query.iter().any(|interaction| *interaction != Interaction::None);
+
+    let hovered = match hovered_ui {
+        Some((entity, _, Some(tooltip))) => Some((entity, tooltip.0.clone())),
+        Some((_, _, None)) => None,
+        None if over_minimap || *state.get() != GameState::InGame => None,
+        None => cursor
+            .and_then(|_| camera_query.get_single().ok())
+            .and_then(|(camera_transform, projection)| {
+                cursor_to_world(window, projection, camera_transform)
+            })
+            .and_then(|world_position| config.tile_at(world_position))
+            .and_then(|coord| tile_entities.get(coord))
+            .and_then(|entity| tile_query.get(entity).ok().map(|tile| (entity, tile_tooltip(tile)))),
+    };
+
+    let target = hovered.as_ref().map(|(entity, _)| *entity);
+    if hover.target != target {
+        hover.target = target;
+        hover.seconds = 0.0;
+    } else {
+        hover.seconds += time.delta_seconds();
+    }
+
+    let shown = hovered
+        .zip(cursor)
+        .filter(|_| hover.seconds >= HOVER_DELAY_SECONDS);
+    let Some(((_, text), cursor)) = shown else {
+        for (entity, ..) in &box_query {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    let Ok((_, mut style, node, mut box_text)) = box_query.get_single_mut() else {
+        spawn_tooltip_box(&mut commands, &theme, text, cursor / ui_scale.0);
+        return;
+    };
+
+    if box_text.sections[0].value != text {
+        box_text.sections[0].value = text;
+    }
+    let position = tooltip_position(cursor, node.size(), window.size()) / ui_scale.0;
+    style.left = Val::Px(position.x);
+    style.top = Val::Px(position.y);
+}
+
+/// Top-left corner of a tooltip box beside the cursor, flipped to stay inside the window
+///
+/// All values are in logical pixels from the top-left of the window.
+pub fn tooltip_position(cursor: Vec2, size: Vec2, window_size: Vec2) -> Vec2 {
+    let mut position = cursor + CURSOR_OFFSET;
+    if position.x + size.x > window_size.x {
+        position.x = cursor.x - CURSOR_OFFSET.x - size.x;
+    }
+    if position.y + size.y > window_size.y {
+        position.y = cursor.y - CURSOR_OFFSET.y - size.y;
+    }
+    position.max(Vec2::ZERO)
+}
+
+/// Tooltip text describing a tile's terrain and biome
+fn tile_tooltip(tile: &Tile) -> String {
+    if tile.terrain.is_water() {
+        tile.terrain.name().to_string()
+    } else {
+        format!("{} ({})", tile.terrain.name(), tile.biome.name())
+    }
+}
+
+fn spawn_tooltip_box(commands: &mut Commands, theme: &MenuTheme, text: String, position: Vec2) {
+    commands.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(position.x),
+                top: Val::Px(position.y),
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                ..default()
+            },
+            background_color: theme.background_color.into(),
+            z_index: ZIndex::Global(1000),
+            ..TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: theme.font_size(18.0),
+                    color: theme.button_text,
+                    ..default()
+                },
+            )
+        },
+        TooltipBox,
+    ));
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Window size the UI's pixel sizes were designed for, where the UI scale is 1.0
+const REFERENCE_RESOLUTION: Vec2 = Vec2::new(1280.0, 720.0);
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 4.0;
+
+/// UI scale for a window size, so layouts keep the proportions they have at the reference
+/// resolution
+///
+/// Uses whichever axis is tighter, so a screen that fits at the reference resolution also
+/// fits in wider or taller windows.
+pub fn ui_scale_for(window_size: Vec2) -> f32 {
+    let ratio = window_size / REFERENCE_RESOLUTION;
+    ratio.min_element().clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+}
+
+/// Scale all UI with the window, so menus stay readable from 720p to 4K
+pub fn update_ui_scale(
+    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    let scale = ui_scale_for(Vec2::new(window.width(), window.height()));
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_resolution_has_unit_scale() {
+        assert_eq!(ui_scale_for(REFERENCE_RESOLUTION), 1.0);
+        assert_eq!(ui_scale_for(Vec2::new(3840.0, 2160.0)), 3.0);
+    }
+
+    #[test]
+    fn tighter_axis_sets_the_scale() {
+        // Ultrawide windows scale with their height, tall ones with their width
+        assert_eq!(ui_scale_for(Vec2::new(3440.0, 1440.0)), 2.0);
+        assert_eq!(ui_scale_for(Vec2::new(1280.0, 1440.0)), 1.0);
+    }
+
+    #[test]
+    fn scale_stays_within_limits() {
+        assert_eq!(ui_scale_for(Vec2::new(320.0, 180.0)), MIN_UI_SCALE);
+        assert_eq!(ui_scale_for(Vec2::new(7680.0, 4320.0) * 2.0), MAX_UI_SCALE);
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use crate::components::tile::{Terrain, Tile};
+use crate::components::ui::WeatherOverlay;
+use crate::resources::{
+    EventLog, Rng, SimulationClock, TerrainMaterials, Weather, WeatherConfig, WeatherTimer,
+};
+use crate::utils::neighbors;
+
+/// Start each new world with clear skies
+pub fn reset_weather(mut weather: ResMut<Weather>, mut timer: ResMut<WeatherTimer>) {
+    *weather = Weather::default();
+    *timer = WeatherTimer::default();
+}
+
+/// Roll for a weather change every `change_interval_hours` of in-game time
+pub fn update_weather(
+    clock: Res<SimulationClock>,
+    config: Res<WeatherConfig>,
+    mut timer: ResMut<WeatherTimer>,
+    mut weather: ResMut<Weather>,
+    mut rng: ResMut<Rng>,
+    mut event_log: ResMut<EventLog>,
+) {
+    if clock.elapsed_hours < timer.next_change_hour {
+        return;
+    }
+    timer.next_change_hour = clock.elapsed_hours + config.change_interval_hours;
+
+    let next = config.next_weather(*weather, rng.roll());
+    if next != *weather {
+        info!("Weather changed from {} to {}", weather.label(), next.label());
+        event_log.push(&clock, format!("Weather changed to {}", next.label()), None);
+        *weather = next;
+    }
+}
+
+/// Let the weather reshape the coastline
+///
+/// Rain and storms flood sand that borders water, while drought dries out water that borders
+/// land, a few tiles at a time.
+pub fn apply_weather_to_terrain(
+    clock: Res<SimulationClock>,
+    config: Res<WeatherConfig>,
+    weather: Res<Weather>,
+    terrain_materials: Res<TerrainMaterials>,
+    mut timer: ResMut<WeatherTimer>,
+    mut rng: ResMut<Rng>,
+    mut tile_query: Query<(&mut Tile, &mut Handle<ColorMaterial>)>,
+) {
+    if clock.elapsed_hours < timer.next_terrain_hour {
+        return;
+    }
+    timer.next_terrain_hour = clock.elapsed_hours + config.terrain_interval_hours;
+
+    let change = weather.shoreline_change();
+    if change == 0 {
+        return;
+    }
+    let (from, to) = if change > 0 {
+        (Terrain::Sand, Terrain::Water)
+    } else {
+        (Terrain::Water, Terrain::Sand)
+    };
+
+    let terrain_by_coord: HashMap<UVec2, Terrain> = tile_query
+        .iter()
+        .map(|(tile, _)| (tile.coord, tile.terrain))
+        .collect();
+
+    // Flooding spreads from water, drying spreads from land
+    let borders_target = |coord: UVec2| {
+        neighbors(coord).any(|neighbor| {
+            terrain_by_coord
+                .get(&neighbor)
+                .is_some_and(|terrain| (*terrain == Terrain::Water) == (to == Terrain::Water))
+        })
+    };
+
+    let mut candidates: Vec<UVec2> = terrain_by_coord
+        .iter()
+        .filter(|(coord, terrain)| **terrain == from && borders_target(**coord))
+        .map(|(coord, _)| *coord)
+        .collect();
+    // HashMap order varies between runs, so sort before shuffling
+    candidates.sort_by_key(|coord| (coord.y, coord.x));
+    candidates.shuffle(&mut *rng);
+    candidates.truncate(change.unsigned_abs() as usize);
+
+    if candidates.is_empty() {
+        return;
+    }
+
+    for (mut tile, mut material) in &mut tile_query {
+        if candidates.contains(&tile.coord) {
+            tile.terrain = to;
+            *material = terrain_materials.get(to, tile.biome);
+        }
+    }
+}
+
+/// Setup the full-screen weather tint, drawn beneath the vignette and the in-game UI
+pub fn setup_weather_overlay(mut commands: Commands, weather: Res<Weather>) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: weather.tint().into(),
+            z_index: ZIndex::Global(-2),
+            ..default()
+        },
+        WeatherOverlay,
+    ));
+}
+
+/// Retint the overlay when the weather changes
+pub fn update_weather_overlay(
+    weather: Res<Weather>,
+    mut overlay_query: Query<&mut BackgroundColor, With<WeatherOverlay>>,
+) {
+    for mut background_color in &mut overlay_query {
+        background_color.0 = weather.tint();
+    }
+}
+
+/// Cleanup the weather overlay when leaving the game
+pub fn cleanup_weather_overlay(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<WeatherOverlay>>,
+) {
+    for entity in &overlay_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
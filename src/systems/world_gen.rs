@@ -0,0 +1,892 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::sprite::Mesh2dHandle;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use noise::{NoiseFn, Perlin};
+use rand::seq::SliceRandom;
+use crate::components::{Biome, Terrain, Tile, TileChunk};
+use crate::events::Notification;
+use crate::resources::{
+    BiomeTable, GameState, Rng, TerrainMaterials, TerrainPalette, TileEntities, TileRendering,
+    WorldConfig, WorldGenBudget, WorldGenProgress, TILE_SIZE,
+};
+
+/// Spatial frequency of the base noise octave, in cycles per tile
+const NOISE_FREQUENCY: f64 = 0.043;
+/// Number of noise octaves summed for the elevation and moisture maps
+const NOISE_OCTAVES: u32 = 4;
+/// Mixed into the world seed so moisture doesn't follow the elevation noise
+const MOISTURE_SEED_SALT: u32 = 0x9e37_79b9;
+
+/// Width of the sandy band above the water level, in normalized elevation
+const BEACH_HEIGHT: f64 = 0.05;
+/// Normalized elevation above which tiles become mountains
+const MOUNTAIN_LEVEL: f64 = 0.68;
+
+/// Map area per river, in tiles
+const TILES_PER_RIVER: u32 = 1024;
+/// Longest path a river may take to reach water before it is abandoned
+const MAX_RIVER_LENGTH: usize = 200;
+/// Random elevation added when choosing each river step, so rivers meander
+const RIVER_MEANDER: f64 = 0.02;
+
+/// Elevation of a tile in the range 0.0..=1.0, using fractal Perlin noise
+pub fn elevation_at(noise: &Perlin, x: u32, y: u32) -> f64 {
+    fractal_noise(noise, x, y)
+}
+
+/// Moisture of a tile in the range 0.0..=1.0, from the world's moisture noise
+pub fn moisture_at(noise: &Perlin, x: u32, y: u32) -> f64 {
+    fractal_noise(noise, x, y)
+}
+
+/// Sum of noise octaves at a tile, normalized to the range 0.0..=1.0
+fn fractal_noise(noise: &Perlin, x: u32, y: u32) -> f64 {
+    let mut value = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = NOISE_FREQUENCY;
+    let mut total_amplitude = 0.0;
+
+    for _ in 0..NOISE_OCTAVES {
+        value += noise.get([x as f64 * frequency, y as f64 * frequency]) * amplitude;
+        total_amplitude += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+
+    // Perlin output is roughly -1.0..=1.0
+    ((value / total_amplitude + 1.0) / 2.0).clamp(0.0, 1.0)
+}
+
+/// Classify a normalized elevation into a terrain type
+pub fn terrain_for_elevation(elevation: f64, water_level: f64) -> Terrain {
+    if elevation < water_level {
+        Terrain::Water
+    } else if elevation < water_level + BEACH_HEIGHT {
+        Terrain::Sand
+    } else if elevation < MOUNTAIN_LEVEL {
+        Terrain::Grass
+    } else {
+        Terrain::Mountain
+    }
+}
+
+/// Elevation noise source for a world, derived from its seed
+pub fn world_noise(config: &WorldConfig) -> Perlin {
+    // The noise crate takes a 32-bit seed, so fold the high bits in
+    Perlin::new((config.seed ^ (config.seed >> 32)) as u32)
+}
+
+/// Moisture noise source for a world, independent of its elevation noise
+pub fn moisture_noise(config: &WorldConfig) -> Perlin {
+    Perlin::new((config.seed ^ (config.seed >> 32)) as u32 ^ MOISTURE_SEED_SALT)
+}
+
+/// Index of a tile in a row-major grid of the world
+fn tile_index(config: &WorldConfig, coord: UVec2) -> usize {
+    (coord.y * config.width + coord.x) as usize
+}
+
+/// Orthogonal neighbors of a tile that lie inside the map
+fn grid_neighbors(config: &WorldConfig, coord: UVec2) -> impl Iterator<Item = UVec2> {
+    let size = UVec2::new(config.width, config.height).as_ivec2();
+    let coord = coord.as_ivec2();
+    [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+        .into_iter()
+        .map(move |offset| coord + offset)
+        .filter(move |neighbor| neighbor.cmpge(IVec2::ZERO).all() && neighbor.cmplt(size).all())
+        .map(|neighbor| neighbor.as_uvec2())
+}
+
+/// Terrain and biome of every tile of a world, generated without touching the ECS
+///
+/// `generate_world_step` spawns the tiles as entities, but the map itself is plain data that
+/// can be built in tests, benchmarks or a background task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldMap {
+    pub width: u32,
+    pub height: u32,
+    /// Every tile in row-major order, starting from the bottom-left corner
+    pub tiles: Vec<Tile>,
+}
+
+impl WorldMap {
+    /// Generate the map for a config, with rivers and coastlines applied
+    ///
+    /// Rivers are drawn from an `Rng` seeded with the world seed, so the same config always
+    /// generates the same map and loaded saves match the world they were saved from.
+    pub fn generate(config: &WorldConfig, biome_table: &BiomeTable) -> Self {
+        Self::generate_with_progress(config, biome_table, &AtomicU32::new(0))
+    }
+
+    /// Generate the map, counting each finished pass in `passes`
+    ///
+    /// There are `WorldGenProgress::MAP_PASSES` passes, so another thread can follow along.
+    pub fn generate_with_progress(config: &WorldConfig, biome_table: &BiomeTable, passes: &AtomicU32) -> Self {
+        let elevation = generate_elevation(config);
+        passes.fetch_add(1, Ordering::Relaxed);
+        let terrain = generate_terrain(config, &elevation);
+        passes.fetch_add(1, Ordering::Relaxed);
+        let biomes = generate_biomes(config, &elevation, biome_table);
+        passes.fetch_add(1, Ordering::Relaxed);
+
+        let tiles = grid_coords(config)
+            .zip(terrain.into_iter().zip(biomes))
+            .map(|(coord, (terrain, biome))| Tile { coord, terrain, biome })
+            .collect();
+        Self {
+            width: config.width,
+            height: config.height,
+            tiles,
+        }
+    }
+
+    /// The tile at grid coordinates, if they lie on the map
+    pub fn tile(&self, coord: UVec2) -> Option<&Tile> {
+        if coord.x < self.width && coord.y < self.height {
+            self.tiles.get((coord.y * self.width + coord.x) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// Coordinates of every tile in row-major order
+fn grid_coords(config: &WorldConfig) -> impl Iterator<Item = UVec2> + '_ {
+    (0..config.height).flat_map(|y| (0..config.width).map(move |x| UVec2::new(x, y)))
+}
+
+/// Elevation of every tile in row-major order, from the world's elevation noise
+pub fn generate_elevation(config: &WorldConfig) -> Vec<f64> {
+    let noise = world_noise(config);
+    grid_coords(config)
+        .map(|coord| elevation_at(&noise, coord.x, coord.y))
+        .collect()
+}
+
+/// Terrain pass: classify every tile by elevation, then carve rivers and line the coasts
+pub fn generate_terrain(config: &WorldConfig, elevation: &[f64]) -> Vec<Terrain> {
+    let mut terrain: Vec<Terrain> = elevation
+        .iter()
+        .map(|elevation| terrain_for_elevation(*elevation, config.water_level))
+        .collect();
+
+    let mut rng = Rng::from_seed(config.seed);
+    carve_rivers(&mut terrain, elevation, config, &mut rng);
+    smooth_coastlines(&mut terrain, config);
+    terrain
+}
+
+/// Biome pass: classify every tile by its elevation and the world's moisture noise
+pub fn generate_biomes(config: &WorldConfig, elevation: &[f64], biome_table: &BiomeTable) -> Vec<Biome> {
+    let noise = moisture_noise(config);
+    grid_coords(config)
+        .zip(elevation)
+        .map(|(coord, elevation)| biome_table.classify(*elevation, moisture_at(&noise, coord.x, coord.y)))
+        .collect()
+}
+
+/// Run rivers from random mountain tiles downhill until they reach water or another river
+///
+/// Each step flows to the lowest neighbor not yet on the river, climbing out of pits when it
+/// has to. Rivers that can't reach water within `MAX_RIVER_LENGTH` tiles are dropped, so every
+/// river is a contiguous path ending at water.
+fn carve_rivers(terrain: &mut [Terrain], elevation: &[f64], config: &WorldConfig, rng: &mut Rng) {
+    let mut sources: Vec<UVec2> = (0..config.height)
+        .flat_map(|y| (0..config.width).map(move |x| UVec2::new(x, y)))
+        .filter(|coord| terrain[tile_index(config, *coord)] == Terrain::Mountain)
+        .collect();
+    sources.shuffle(rng);
+    sources.truncate((config.width * config.height / TILES_PER_RIVER).max(1) as usize);
+
+    for source in sources {
+        let mut path = vec![source];
+        let mut current = source;
+
+        let reached_water = loop {
+            if path.len() > MAX_RIVER_LENGTH {
+                break false;
+            }
+            let next = grid_neighbors(config, current)
+                .filter(|neighbor| !path.contains(neighbor))
+                .map(|neighbor| {
+                    let height = elevation[tile_index(config, neighbor)] + rng.range(0.0, RIVER_MEANDER);
+                    (neighbor, height)
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(neighbor, _)| neighbor);
+            let Some(next) = next else {
+                break false;
+            };
+
+            if matches!(terrain[tile_index(config, next)], Terrain::Water | Terrain::River) {
+                break true;
+            }
+            path.push(next);
+            current = next;
+        };
+
+        if reached_water {
+            for coord in path {
+                terrain[tile_index(config, coord)] = Terrain::River;
+            }
+        }
+    }
+}
+
+/// Turn land bordering open water into sand, so coasts are lined with beaches
+fn smooth_coastlines(terrain: &mut [Terrain], config: &WorldConfig) {
+    let coast: Vec<usize> = (0..config.height)
+        .flat_map(|y| (0..config.width).map(move |x| UVec2::new(x, y)))
+        .filter(|coord| matches!(terrain[tile_index(config, *coord)], Terrain::Grass | Terrain::Mountain))
+        .filter(|coord| {
+            grid_neighbors(config, *coord)
+                .any(|neighbor| terrain[tile_index(config, neighbor)] == Terrain::Water)
+        })
+        .map(|coord| tile_index(config, coord))
+        .collect();
+
+    for index in coast {
+        terrain[index] = Terrain::Sand;
+    }
+}
+
+/// Width and height of a generation chunk, in tiles
+pub const CHUNK_SIZE: u32 = 16;
+
+/// Tiles covered by the chunk at a chunk origin, clipped to the map edges
+pub fn chunk_tiles(config: &WorldConfig, origin: UVec2) -> URect {
+    let max = (origin + UVec2::splat(CHUNK_SIZE)).min(UVec2::new(config.width, config.height));
+    URect::from_corners(origin, max)
+}
+
+/// Origins of every chunk in generation order: row by row from the bottom-left
+///
+/// The order depends only on the map size, so the same seed always spawns the same tiles in
+/// the same order.
+pub fn chunk_origins(config: &WorldConfig) -> Vec<UVec2> {
+    (0..config.height)
+        .step_by(CHUNK_SIZE as usize)
+        .flat_map(|y| {
+            (0..config.width)
+                .step_by(CHUNK_SIZE as usize)
+                .map(move |x| UVec2::new(x, y))
+        })
+        .collect()
+}
+
+/// In-progress world generation, present only while the world is loading
+///
+/// Removing it drops the background task, which cancels the task if it hasn't started yet; a
+/// task already running finishes and its map is discarded.
+#[derive(Resource)]
+pub struct WorldGenerator {
+    /// Builds the whole map off the main thread, so rivers and coasts can span chunks
+    task: Option<Task<WorldMap>>,
+    /// Passes the task has finished, shared with it for the loading bar
+    passes: Arc<AtomicU32>,
+    /// The finished map, once the task is done
+    map: Option<WorldMap>,
+    tile_mesh: Handle<Mesh>,
+    /// Shared by every chunk mesh when tiles are batched; the tile colors come from the vertices
+    chunk_material: Option<Handle<ColorMaterial>>,
+    chunks: Vec<UVec2>,
+    next_chunk: usize,
+    /// Tiles of the current chunk already spawned, when a chunk spans several frames
+    next_tile_in_chunk: u32,
+}
+
+/// Start generating the world from `WorldConfig` when loading begins
+pub fn start_world_generation(
+    mut commands: Commands,
+    config: Res<WorldConfig>,
+    biome_table: Res<BiomeTable>,
+    palette: Res<TerrainPalette>,
+    rendering: Res<TileRendering>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    info!(
+        "Generating {}x{} world with seed {}",
+        config.width, config.height, config.seed
+    );
+
+    let chunks = chunk_origins(&config);
+    let chunk_material = match *rendering {
+        TileRendering::PerTile => None,
+        TileRendering::Batched => {
+            info!(
+                "Batching {} tiles into {} chunk meshes",
+                config.width * config.height,
+                chunks.len()
+            );
+            Some(materials.add(Color::WHITE))
+        }
+    };
+
+    // All tiles share one mesh and one material per terrain type and biome
+    let tile_mesh = meshes.add(Rectangle::new(TILE_SIZE, TILE_SIZE));
+    let terrain_materials = Terrain::ALL
+        .iter()
+        .flat_map(|terrain| Biome::ALL.iter().map(move |biome| (*terrain, *biome)))
+        .map(|(terrain, biome)| ((terrain, biome), materials.add(palette.color(terrain, biome))))
+        .collect();
+
+    let passes = Arc::new(AtomicU32::new(0));
+    let task = {
+        let config = config.clone();
+        let biome_table = biome_table.clone();
+        let passes = passes.clone();
+        AsyncComputeTaskPool::get()
+            .spawn(async move { WorldMap::generate_with_progress(&config, &biome_table, &passes) })
+    };
+
+    // Kept after generation so systems that change terrain can swap tile materials
+    commands.insert_resource(TerrainMaterials(terrain_materials));
+    commands.insert_resource(WorldGenerator {
+        task: Some(task),
+        passes,
+        map: None,
+        tile_mesh,
+        chunk_material,
+        chunks,
+        next_chunk: 0,
+        next_tile_in_chunk: 0,
+    });
+    commands.insert_resource(WorldGenProgress::new(config.width * config.height));
+    commands.insert_resource(TileEntities::default());
+
+    // Let the camera clamp to the generated map
+    commands.insert_resource(config.bounds());
+}
+
+/// Wait for the background task's map, then spawn tiles chunk by chunk, stopping each frame
+/// once the tile budget is spent
+///
+/// Batched tiles are spawned without a mesh, and each chunk's mesh is spawned once its last
+/// tile is.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_world_step(
+    mut commands: Commands,
+    config: Res<WorldConfig>,
+    budget: Res<WorldGenBudget>,
+    palette: Res<TerrainPalette>,
+    terrain_materials: Res<TerrainMaterials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut generator: ResMut<WorldGenerator>,
+    mut progress: ResMut<WorldGenProgress>,
+    mut tile_entities: ResMut<TileEntities>,
+) {
+    progress.passes = generator.passes.load(Ordering::Relaxed);
+    if let Some(task) = generator.task.as_mut() {
+        let Some(map) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        generator.task = None;
+        generator.map = Some(map);
+    }
+
+    let generator = &mut *generator;
+    let Some(map) = generator.map.as_ref() else {
+        return;
+    };
+    let mut remaining = budget.tiles_per_frame.max(1);
+
+    while remaining > 0 && generator.next_chunk < generator.chunks.len() {
+        let chunk = chunk_tiles(&config, generator.chunks[generator.next_chunk]);
+        let chunk_width = chunk.width();
+        let chunk_tile_count = chunk_width * chunk.height();
+
+        let start = generator.next_tile_in_chunk;
+        let end = (start + remaining).min(chunk_tile_count);
+
+        for index in start..end {
+            let coord = chunk.min + UVec2::new(index % chunk_width, index / chunk_width);
+            let tile = map.tiles[tile_index(&config, coord)].clone();
+            let material = terrain_materials.get(tile.terrain, tile.biome);
+
+            let entity = if generator.chunk_material.is_some() {
+                // Not drawn, but kept so systems that change terrain can still swap the material
+                commands.spawn((tile, material)).id()
+            } else {
+                let position = config.tile_center(coord);
+                commands.spawn((
+                    ColorMesh2dBundle {
+                        mesh: generator.tile_mesh.clone().into(),
+                        material,
+                        transform: Transform::from_translation(position.extend(0.0)),
+                        ..default()
+                    },
+                    tile,
+                )).id()
+            };
+            tile_entities.0.insert(coord, entity);
+        }
+
+        let spawned = end - start;
+        remaining -= spawned;
+        progress.generated += spawned;
+
+        if end == chunk_tile_count {
+            if let Some(material) = generator.chunk_material.clone() {
+                commands.spawn((
+                    ColorMesh2dBundle {
+                        mesh: meshes.add(chunk_mesh(&config, &palette, chunk, map)).into(),
+                        material,
+                        ..default()
+                    },
+                    TileChunk { origin: chunk.min },
+                ));
+            }
+            generator.next_chunk += 1;
+            generator.next_tile_in_chunk = 0;
+        } else {
+            generator.next_tile_in_chunk = end;
+        }
+    }
+}
+
+/// Vertex color of a tile, as stored in chunk meshes
+fn tile_vertex_color(palette: &TerrainPalette, terrain: Terrain, biome: Biome) -> [f32; 4] {
+    let color = palette.color(terrain, biome).to_linear();
+    [color.red, color.green, color.blue, color.alpha]
+}
+
+/// One quad per tile of a chunk, row by row in the order the tiles are spawned
+fn chunk_mesh(config: &WorldConfig, palette: &TerrainPalette, chunk: URect, map: &WorldMap) -> Mesh {
+    let half = TILE_SIZE / 2.0;
+    let corners = [
+        Vec2::new(-half, -half),
+        Vec2::new(half, -half),
+        Vec2::new(half, half),
+        Vec2::new(-half, half),
+    ];
+
+    let tile_count = (chunk.width() * chunk.height()) as usize;
+    let mut positions = Vec::with_capacity(tile_count * 4);
+    let mut colors = Vec::with_capacity(tile_count * 4);
+    let mut indices = Vec::with_capacity(tile_count * 6);
+
+    for y in chunk.min.y..chunk.max.y {
+        for x in chunk.min.x..chunk.max.x {
+            let coord = UVec2::new(x, y);
+            let tile = &map.tiles[tile_index(config, coord)];
+            let center = config.tile_center(coord);
+            let first = positions.len() as u32;
+
+            for corner in corners {
+                positions.push((center + corner).extend(0.0).to_array());
+                colors.push(tile_vertex_color(palette, tile.terrain, tile.biome));
+            }
+            indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+    }
+
+    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+        .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Recolor the quads of batched tiles whose terrain or biome changed
+///
+/// Only the changed tiles' vertex colors are rewritten, so painting terrain doesn't rebuild
+/// whole chunk meshes.
+pub fn recolor_tile_chunks(
+    config: Res<WorldConfig>,
+    palette: Res<TerrainPalette>,
+    changed_tiles: Query<Ref<Tile>, Changed<Tile>>,
+    chunk_query: Query<(&TileChunk, &Mesh2dHandle)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    if changed_tiles.is_empty() {
+        return;
+    }
+
+    // Chunk meshes are built with the colors tiles are spawned with
+    let tiles = changed_tiles
+        .iter()
+        .filter(|tile| !tile.is_added())
+        .map(|tile| tile.into_inner());
+    write_chunk_colors(&config, &palette, tiles, &chunk_query, &mut meshes);
+}
+
+/// Recolor the whole world when the terrain palette changes
+///
+/// Tile materials and chunk mesh colors are updated in place, so no geometry is rebuilt.
+pub fn repaint_terrain(
+    config: Res<WorldConfig>,
+    palette: Res<TerrainPalette>,
+    terrain_materials: Res<TerrainMaterials>,
+    tile_query: Query<&Tile>,
+    chunk_query: Query<(&TileChunk, &Mesh2dHandle)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for ((terrain, biome), handle) in &terrain_materials.0 {
+        if let Some(material) = materials.get_mut(handle) {
+            material.color = palette.color(*terrain, *biome);
+        }
+    }
+    write_chunk_colors(&config, &palette, tile_query.iter(), &chunk_query, &mut meshes);
+}
+
+/// Rewrite the vertex colors of tiles in their chunk meshes
+fn write_chunk_colors<'a>(
+    config: &WorldConfig,
+    palette: &TerrainPalette,
+    tiles: impl Iterator<Item = &'a Tile>,
+    chunk_query: &Query<(&TileChunk, &Mesh2dHandle)>,
+    meshes: &mut Assets<Mesh>,
+) {
+    if chunk_query.is_empty() {
+        return;
+    }
+
+    let chunk_meshes: HashMap<UVec2, &Mesh2dHandle> = chunk_query
+        .iter()
+        .map(|(chunk, mesh)| (chunk.origin, mesh))
+        .collect();
+
+    for tile in tiles {
+        let origin = tile.coord / CHUNK_SIZE * CHUNK_SIZE;
+        let Some(mesh) = chunk_meshes.get(&origin).and_then(|handle| meshes.get_mut(&handle.0)) else {
+            continue;
+        };
+        let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR) else {
+            continue;
+        };
+
+        let chunk = chunk_tiles(config, origin);
+        let local = tile.coord - origin;
+        let first = ((local.y * chunk.width() + local.x) * 4) as usize;
+        colors[first..first + 4].fill(tile_vertex_color(palette, tile.terrain, tile.biome));
+    }
+}
+
+/// Enter the game once every tile has been generated
+pub fn finish_world_generation(
+    progress: Res<WorldGenProgress>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut notifications: EventWriter<Notification>,
+) {
+    if progress.is_complete() {
+        info!("World generated");
+        notifications.send(Notification::new("World created"));
+        next_state.set(GameState::InGame);
+    }
+}
+
+/// Drop the generator when leaving the loading screen, whether generation finished or Escape
+/// cancelled it
+///
+/// Tiles spawned before a cancel are despawned with the rest of the game world when the main
+/// menu opens.
+pub fn drop_world_generator(mut commands: Commands) {
+    commands.remove_resource::<WorldGenerator>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_seed(seed: u64) -> WorldConfig {
+        WorldConfig {
+            seed,
+            ..default()
+        }
+    }
+
+    /// A map whose width and height aren't whole chunks, so the edge chunks are clipped
+    fn small_config(seed: u64) -> WorldConfig {
+        WorldConfig {
+            seed,
+            width: 40,
+            height: 24,
+            ..default()
+        }
+    }
+
+    /// App that spawns an already generated map, with every tile material a default handle
+    fn generation_test_app(config: &WorldConfig, tiles_per_frame: u32, rendering: TileRendering) -> App {
+        let chunks = chunk_origins(config);
+        let terrain_materials = Terrain::ALL
+            .iter()
+            .flat_map(|terrain| Biome::ALL.iter().map(move |biome| ((*terrain, *biome), Handle::default())))
+            .collect();
+
+        let mut app = App::new();
+        app.insert_resource(config.clone())
+            .insert_resource(WorldGenBudget { tiles_per_frame })
+            .insert_resource(WorldGenProgress::new(config.width * config.height))
+            .insert_resource(TerrainMaterials(terrain_materials))
+            .init_resource::<TerrainPalette>()
+            .init_resource::<TileEntities>()
+            .init_resource::<Assets<Mesh>>()
+            .insert_resource(WorldGenerator {
+                task: None,
+                passes: Arc::new(AtomicU32::new(WorldGenProgress::MAP_PASSES)),
+                map: Some(WorldMap::generate(config, &BiomeTable::default())),
+                tile_mesh: Handle::default(),
+                chunk_material: match rendering {
+                    TileRendering::PerTile => None,
+                    TileRendering::Batched => Some(Handle::default()),
+                },
+                chunks,
+                next_chunk: 0,
+                next_tile_in_chunk: 0,
+            })
+            .add_systems(Update, generate_world_step);
+        app
+    }
+
+    fn spawned_tiles(app: &mut App) -> Vec<Tile> {
+        let world = app.world_mut();
+        world.query::<&Tile>().iter(world).cloned().collect()
+    }
+
+    #[test]
+    fn same_seed_generates_identical_maps() {
+        let config = config_with_seed(1234);
+        let biome_table = BiomeTable::default();
+
+        assert_eq!(
+            WorldMap::generate(&config, &biome_table),
+            WorldMap::generate(&config, &biome_table)
+        );
+    }
+
+    #[test]
+    fn different_seeds_generate_different_maps() {
+        let biome_table = BiomeTable::default();
+
+        assert_ne!(
+            WorldMap::generate(&config_with_seed(1), &biome_table),
+            WorldMap::generate(&config_with_seed(2), &biome_table)
+        );
+    }
+
+    #[test]
+    fn map_covers_every_tile_in_row_major_order() {
+        let config = config_with_seed(7);
+        let map = WorldMap::generate(&config, &BiomeTable::default());
+
+        assert_eq!(map.tiles.len(), (config.width * config.height) as usize);
+        for (index, tile) in map.tiles.iter().enumerate() {
+            assert_eq!(tile_index(&config, tile.coord), index);
+        }
+        assert_eq!(map.tile(UVec2::new(3, 5)).map(|tile| tile.coord), Some(UVec2::new(3, 5)));
+        assert!(map.tile(UVec2::new(config.width, 0)).is_none());
+    }
+
+    #[test]
+    fn chunks_cover_every_tile_once() {
+        let config = small_config(0);
+        let mut covered = vec![0; (config.width * config.height) as usize];
+
+        for origin in chunk_origins(&config) {
+            let chunk = chunk_tiles(&config, origin);
+            assert!(chunk.width() <= CHUNK_SIZE && chunk.height() <= CHUNK_SIZE);
+            for y in chunk.min.y..chunk.max.y {
+                for x in chunk.min.x..chunk.max.x {
+                    covered[tile_index(&config, UVec2::new(x, y))] += 1;
+                }
+            }
+        }
+
+        assert!(covered.iter().all(|count| *count == 1));
+    }
+
+    #[test]
+    fn generation_spawns_at_most_the_budget_per_frame() {
+        let config = small_config(3);
+        let total = config.width * config.height;
+        let budget = 100;
+        let mut app = generation_test_app(&config, budget, TileRendering::PerTile);
+
+        let mut frames = 0;
+        let mut spawned = 0;
+        while !app.world().resource::<WorldGenProgress>().is_complete() {
+            app.update();
+            frames += 1;
+            let now = spawned_tiles(&mut app).len() as u32;
+            assert!(now - spawned <= budget);
+            assert_eq!(app.world().resource::<WorldGenProgress>().generated, now);
+            spawned = now;
+        }
+
+        assert_eq!(frames, total.div_ceil(budget));
+        assert_eq!(spawned, total);
+    }
+
+    #[test]
+    fn spawned_tiles_can_be_found_by_coordinate() {
+        let config = small_config(4);
+        let mut app = generation_test_app(&config, 256, TileRendering::Batched);
+        while !app.world().resource::<WorldGenProgress>().is_complete() {
+            app.update();
+        }
+
+        let tile_entities = app.world().resource::<TileEntities>().clone();
+        assert_eq!(tile_entities.0.len(), (config.width * config.height) as usize);
+        for (coord, entity) in tile_entities.0 {
+            assert_eq!(app.world().get::<Tile>(entity).map(|tile| tile.coord), Some(coord));
+        }
+    }
+
+    #[test]
+    fn generation_spawns_the_same_tiles_in_the_same_order() {
+        let config = small_config(11);
+        let map = WorldMap::generate(&config, &BiomeTable::default());
+
+        let mut app = generation_test_app(&config, 7, TileRendering::PerTile);
+        app.update();
+        let mut first_frame = spawned_tiles(&mut app);
+        while !app.world().resource::<WorldGenProgress>().is_complete() {
+            app.update();
+        }
+
+        // The first frame fills the bottom-left chunk row by row
+        let expected: Vec<Tile> = (0..7).map(|x| map.tile(UVec2::new(x, 0)).unwrap().clone()).collect();
+        first_frame.sort_by_key(|tile| tile.coord.x);
+        assert_eq!(first_frame, expected);
+
+        let mut tiles = spawned_tiles(&mut app);
+        tiles.sort_by_key(|tile| tile_index(&config, tile.coord));
+        assert_eq!(tiles, map.tiles);
+    }
+
+    #[test]
+    fn rivers_flow_contiguously_into_water() {
+        let mut rivers = 0;
+        for seed in 0..10 {
+            let config = config_with_seed(seed);
+            let terrain = generate_terrain(&config, &generate_elevation(&config));
+
+            let terrain_of = |coord: UVec2| terrain[tile_index(&config, coord)];
+
+            for coord in grid_coords(&config).filter(|coord| terrain_of(*coord) == Terrain::River) {
+                rivers += 1;
+                assert!(
+                    grid_neighbors(&config, coord)
+                        .any(|neighbor| matches!(terrain_of(neighbor), Terrain::River | Terrain::Water)),
+                    "river tile {} on seed {} is cut off",
+                    coord,
+                    seed
+                );
+            }
+        }
+        assert!(rivers > 0);
+    }
+
+    #[test]
+    fn coasts_are_lined_with_sand() {
+        let config = config_with_seed(5);
+        let terrain = generate_terrain(&config, &generate_elevation(&config));
+
+        let terrain_of = |coord: UVec2| terrain[tile_index(&config, coord)];
+
+        for coord in grid_coords(&config) {
+            if matches!(terrain_of(coord), Terrain::Grass | Terrain::Mountain) {
+                assert!(grid_neighbors(&config, coord).all(|neighbor| terrain_of(neighbor) != Terrain::Water));
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_carves_the_same_rivers() {
+        let config = config_with_seed(99);
+        let elevation = generate_elevation(&config);
+
+        assert_eq!(generate_terrain(&config, &elevation), generate_terrain(&config, &elevation));
+    }
+
+    fn chunk_colors(app: &mut App, origin: UVec2) -> Vec<[f32; 4]> {
+        let world = app.world_mut();
+        let handle = world
+            .query::<(&TileChunk, &Mesh2dHandle)>()
+            .iter(world)
+            .find(|(chunk, _)| chunk.origin == origin)
+            .map(|(_, mesh)| mesh.0.clone())
+            .unwrap();
+        match world.resource::<Assets<Mesh>>().get(&handle).unwrap().attribute(Mesh::ATTRIBUTE_COLOR) {
+            Some(VertexAttributeValues::Float32x4(colors)) => colors.clone(),
+            _ => panic!("chunk mesh has no vertex colors"),
+        }
+    }
+
+    #[test]
+    fn chunk_meshes_have_a_colored_quad_per_tile() {
+        let config = small_config(6);
+        let palette = TerrainPalette::default();
+        let map = WorldMap::generate(&config, &BiomeTable::default());
+        // The last chunk is clipped by both map edges
+        let chunk = chunk_tiles(&config, *chunk_origins(&config).last().unwrap());
+        let tile_count = (chunk.width() * chunk.height()) as usize;
+
+        let mesh = chunk_mesh(&config, &palette, chunk, &map);
+
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            panic!("chunk mesh has no positions");
+        };
+        let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR) else {
+            panic!("chunk mesh has no vertex colors");
+        };
+        assert_eq!(positions.len(), tile_count * 4);
+        assert_eq!(mesh.indices().map(|indices| indices.len()), Some(tile_count * 6));
+
+        for (index, coord) in (chunk.min.y..chunk.max.y)
+            .flat_map(|y| (chunk.min.x..chunk.max.x).map(move |x| UVec2::new(x, y)))
+            .enumerate()
+        {
+            let tile = &map.tiles[tile_index(&config, coord)];
+            let quad = index * 4..index * 4 + 4;
+            let center = positions[quad.clone()]
+                .iter()
+                .fold(Vec2::ZERO, |sum, position| sum + Vec2::new(position[0], position[1]))
+                / 4.0;
+            assert!(center.abs_diff_eq(config.tile_center(coord), 1e-3));
+            assert!(colors[quad]
+                .iter()
+                .all(|color| *color == tile_vertex_color(&palette, tile.terrain, tile.biome)));
+        }
+    }
+
+    #[test]
+    fn changed_tiles_recolor_only_their_quad() {
+        let config = small_config(8);
+        let mut app = generation_test_app(&config, 256, TileRendering::Batched);
+        app.add_systems(Update, recolor_tile_chunks.after(generate_world_step));
+        while !app.world().resource::<WorldGenProgress>().is_complete() {
+            app.update();
+        }
+        // Let the chunk spawned on the last frame be seen by the recolor
+        app.update();
+
+        let coord = UVec2::new(5, 3);
+        let before = chunk_colors(&mut app, UVec2::ZERO);
+        let entity = app.world().resource::<TileEntities>().get(coord).unwrap();
+        let mut tile = app.world_mut().get_mut::<Tile>(entity).unwrap();
+        let terrain = Terrain::ALL.into_iter().find(|terrain| *terrain != tile.terrain).unwrap();
+        tile.terrain = terrain;
+        let biome = tile.biome;
+        app.update();
+
+        let after = chunk_colors(&mut app, UVec2::ZERO);
+        let palette = TerrainPalette::default();
+        let first = ((coord.y * CHUNK_SIZE + coord.x) * 4) as usize;
+        let quad = first..first + 4;
+        for (index, (old, new)) in before.iter().zip(&after).enumerate() {
+            if quad.contains(&index) {
+                assert_eq!(*new, tile_vertex_color(&palette, terrain, biome));
+            } else {
+                assert_eq!(old, new);
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+
+/// World position under the cursor, or `None` when the cursor is outside the window
+///
+/// Takes the camera's orthographic projection and transform, so the result accounts for
+/// panning and the current zoom. Assumes the default `ScalingMode::WindowSize`, where one
+/// logical pixel covers `projection.scale` world units.
+pub fn cursor_to_world(
+    window: &Window,
+    projection: &OrthographicProjection,
+    camera_transform: &Transform,
+) -> Option<Vec2> {
+    let cursor = window.cursor_position()?;
+    let viewport_size = Vec2::new(window.width(), window.height());
+    Some(viewport_to_world(cursor, viewport_size, projection, camera_transform))
+}
+
+/// World position of a point in the viewport, given in logical pixels from the top-left
+pub fn viewport_to_world(
+    viewport_position: Vec2,
+    viewport_size: Vec2,
+    projection: &OrthographicProjection,
+    camera_transform: &Transform,
+) -> Vec2 {
+    // Viewport y points down while world y points up
+    let offset = viewport_position - viewport_size * projection.viewport_origin;
+    let view_position = Vec2::new(offset.x, -offset.y) * projection.scale;
+    camera_transform
+        .transform_point(view_position.extend(0.0))
+        .truncate()
+}
+
+/// Area of the world the camera currently shows, for a viewport size in logical pixels
+pub fn visible_world_rect(
+    viewport_size: Vec2,
+    projection: &OrthographicProjection,
+    camera_transform: &Transform,
+) -> Rect {
+    Rect::from_corners(
+        viewport_to_world(Vec2::ZERO, viewport_size, projection, camera_transform),
+        viewport_to_world(viewport_size, viewport_size, projection, camera_transform),
+    )
+}
+
+/// The four orthogonal neighbors of a tile, skipping those below zero
+pub fn neighbors(coord: UVec2) -> impl Iterator<Item = UVec2> {
+    let coord = coord.as_ivec2();
+    [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+        .into_iter()
+        .map(move |offset| coord + offset)
+        .filter(|neighbor| neighbor.x >= 0 && neighbor.y >= 0)
+        .map(|neighbor| neighbor.as_uvec2())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIEWPORT: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn projection(scale: f32) -> OrthographicProjection {
+        OrthographicProjection {
+            scale,
+            ..default()
+        }
+    }
+
+    fn window_with_cursor(cursor: Option<Vec2>) -> Window {
+        let mut window = Window {
+            resolution: (VIEWPORT.x, VIEWPORT.y).into(),
+            ..default()
+        };
+        window.set_cursor_position(cursor);
+        window
+    }
+
+    #[test]
+    fn viewport_center_maps_to_camera_position() {
+        let transform = Transform::from_xyz(120.0, -40.0, 0.0);
+        let world = viewport_to_world(VIEWPORT / 2.0, VIEWPORT, &projection(1.0), &transform);
+        assert_eq!(world, Vec2::new(120.0, -40.0));
+    }
+
+    #[test]
+    fn viewport_y_is_flipped() {
+        let world = viewport_to_world(Vec2::new(400.0, 200.0), VIEWPORT, &projection(1.0), &Transform::IDENTITY);
+        assert_eq!(world, Vec2::new(0.0, 100.0));
+    }
+
+    #[test]
+    fn top_left_corner_maps_to_visible_area_corner() {
+        let world = viewport_to_world(Vec2::ZERO, VIEWPORT, &projection(1.0), &Transform::IDENTITY);
+        assert_eq!(world, Vec2::new(-400.0, 300.0));
+    }
+
+    #[test]
+    fn zoom_scales_offset_from_camera() {
+        let transform = Transform::from_xyz(50.0, 50.0, 0.0);
+        let cursor = Vec2::new(500.0, 300.0);
+
+        let zoomed_out = viewport_to_world(cursor, VIEWPORT, &projection(2.0), &transform);
+        assert_eq!(zoomed_out, Vec2::new(250.0, 50.0));
+
+        let zoomed_in = viewport_to_world(cursor, VIEWPORT, &projection(0.5), &transform);
+        assert_eq!(zoomed_in, Vec2::new(100.0, 50.0));
+    }
+
+    #[test]
+    fn visible_rect_grows_with_zoom() {
+        let transform = Transform::from_xyz(100.0, 0.0, 0.0);
+        let visible = visible_world_rect(VIEWPORT, &projection(1.0), &transform);
+        assert_eq!(visible.min, Vec2::new(-300.0, -300.0));
+        assert_eq!(visible.max, Vec2::new(500.0, 300.0));
+
+        let zoomed_out = visible_world_rect(VIEWPORT, &projection(2.0), &transform);
+        assert_eq!(zoomed_out.size(), VIEWPORT * 2.0);
+        assert_eq!(zoomed_out.center(), Vec2::new(100.0, 0.0));
+    }
+
+    #[test]
+    fn cursor_to_world_uses_window_cursor() {
+        let window = window_with_cursor(Some(Vec2::new(600.0, 450.0)));
+        let transform = Transform::from_xyz(10.0, 20.0, 0.0);
+        let world = cursor_to_world(&window, &projection(1.0), &transform);
+        assert_eq!(world, Some(Vec2::new(210.0, -130.0)));
+    }
+
+    #[test]
+    fn cursor_outside_window_returns_none() {
+        let window = window_with_cursor(None);
+        assert_eq!(cursor_to_world(&window, &projection(1.0), &Transform::IDENTITY), None);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod coordinates;
+
+pub use coordinates::*;
\ No newline at end of file